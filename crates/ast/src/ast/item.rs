@@ -310,7 +310,66 @@ pub enum UserDefinableOperator {
     Ne,
 }
 
+impl fmt::Display for UserDefinableOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
 impl UserDefinableOperator {
+    /// Returns the string representation of the operator.
+    pub const fn to_str(self) -> &'static str {
+        match self.to_op() {
+            Either::Left(unop) => unop.to_str(),
+            Either::Right(binop) => binop.to_str(),
+        }
+    }
+
+    /// Returns the user-definable operator for the given binary operator, if any.
+    pub const fn from_bin_op(op: BinOpKind) -> Option<Self> {
+        Some(match op {
+            BinOpKind::BitAnd => Self::BitAnd,
+            BinOpKind::BitOr => Self::BitOr,
+            BinOpKind::BitXor => Self::BitXor,
+            BinOpKind::Add => Self::Add,
+            BinOpKind::Div => Self::Div,
+            BinOpKind::Rem => Self::Rem,
+            BinOpKind::Mul => Self::Mul,
+            BinOpKind::Sub => Self::Sub,
+            BinOpKind::Eq => Self::Eq,
+            BinOpKind::Ge => Self::Ge,
+            BinOpKind::Gt => Self::Gt,
+            BinOpKind::Le => Self::Le,
+            BinOpKind::Lt => Self::Lt,
+            BinOpKind::Ne => Self::Ne,
+            _ => return None,
+        })
+    }
+
+    /// Returns the user-definable operator for the given unary operator, if any.
+    pub const fn from_un_op(op: UnOpKind) -> Option<Self> {
+        Some(match op {
+            UnOpKind::BitNot => Self::BitNot,
+            UnOpKind::Neg => Self::Sub,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if the operator can be defined as a unary operator.
+    pub const fn is_unary(self) -> bool {
+        matches!(self, Self::BitNot | Self::Sub)
+    }
+
+    /// Returns `true` if the operator can be defined as a binary operator.
+    pub const fn is_binary(self) -> bool {
+        !matches!(self, Self::BitNot)
+    }
+
+    /// Returns `true` if the operator is a comparison operator, which must return `bool`.
+    pub const fn is_comparison(self) -> bool {
+        matches!(self, Self::Eq | Self::Ne | Self::Lt | Self::Le | Self::Gt | Self::Ge)
+    }
+
     /// Returns this operator as a binary or unary operator.
     pub const fn to_op(self) -> Either<UnOpKind, BinOpKind> {
         match self {
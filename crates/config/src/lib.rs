@@ -19,6 +19,11 @@ mod utils;
 #[cfg(feature = "version")]
 pub mod version;
 
+/// The Solidity version that is targeted by default.
+///
+/// See [`Opts::solc_version`].
+pub const SOLC_VERSION: semver::Version = semver::Version::new(0, 8, 28);
+
 str_enum! {
     /// Compiler stage.
     #[derive(strum::EnumIs)]
@@ -114,8 +119,18 @@ str_enum! {
         // Bin,
         // /// Runtime bytecode.
         // BinRuntime,
-        /// Function signature hashes.
+        /// Function selectors, event topics and error selectors.
         Hashes,
+        /// Function selectors, in the same format as solc's `methodIdentifiers` output.
+        MethodIdentifiers,
+        /// Values of constant variables, by contract and of file-level ones by source.
+        Constants,
+        /// Minimum Solidity version required by each source.
+        MinVersion,
+        /// Storage layout of state variables.
+        StorageLayout,
+        /// Storage layout changes compared to `--storage-layout-base`.
+        StorageLayoutDiff,
     }
 }
 
@@ -149,6 +164,8 @@ str_enum! {
         Ast,
         /// Print the HIR.
         Hir,
+        /// Print the control-flow graphs of function bodies in the Graphviz DOT format.
+        Cfg,
     }
 }
 
@@ -44,6 +44,11 @@ pub struct Opts {
     /// EVM version.
     #[cfg_attr(feature = "clap", arg(long, value_enum, default_value_t))]
     pub evm_version: EvmVersion,
+    /// Solidity version to check `pragma solidity` requirements against.
+    ///
+    /// Defaults to the latest supported version.
+    #[cfg_attr(feature = "clap", arg(long, value_name = "VERSION"))]
+    pub solc_version: Option<semver::Version>,
     /// Stop execution after the given compiler stage.
     #[cfg_attr(feature = "clap", arg(long, value_enum))]
     pub stop_after: Option<CompilerStage>,
@@ -54,6 +59,14 @@ pub struct Opts {
     /// Comma separated list of types of output for the compiler to emit.
     #[cfg_attr(feature = "clap", arg(long, value_delimiter = ','))]
     pub emit: Vec<CompilerOutput>,
+    /// Storage layouts to check the storage layouts of the compiled contracts against.
+    ///
+    /// This is the JSON output of a previous `--emit storage-layout` run. Contracts are matched by
+    /// their fully qualified name, or by their name if it is unique. Changes that are not
+    /// compatible with upgrading a deployed contract, such as removed, reordered or retyped state
+    /// variables, are reported as errors.
+    #[cfg_attr(feature = "clap", arg(long, value_name = "PATH", value_hint = ValueHint::FilePath))]
+    pub storage_layout_base: Option<PathBuf>,
 
     /// Coloring.
     #[cfg(feature = "clap")] // TODO
@@ -104,6 +117,12 @@ impl Opts {
         self.threads.0
     }
 
+    /// Returns the Solidity version to check `pragma solidity` requirements against.
+    #[inline]
+    pub fn solc_version(&self) -> semver::Version {
+        self.solc_version.clone().unwrap_or(crate::SOLC_VERSION)
+    }
+
     /// Finishes argument parsing.
     ///
     /// This currently only parses the `-Z` arguments into the `unstable` field, but may be extended
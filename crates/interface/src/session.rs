@@ -138,6 +138,12 @@ impl Session {
     pub fn validate(&self) -> crate::Result<()> {
         let mut result = Ok(());
         result = result.and(self.check_unique("emit", &self.opts.emit));
+        if self.do_emit(CompilerOutput::StorageLayoutDiff)
+            && self.opts.storage_layout_base.is_none()
+        {
+            let msg = "`--emit storage-layout-diff` requires `--storage-layout-base`";
+            result = Err(self.dcx.err(msg).emit());
+        }
         result
     }
 
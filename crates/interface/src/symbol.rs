@@ -845,6 +845,8 @@ symbols! {
     // There is currently no checking that all symbols are used; that would be
     // nice to have.
     Symbols {
+        Error,
+        Panic,
         X,
         __tmp_struct,
         abi,
@@ -879,6 +881,7 @@ symbols! {
         require,
         ripemd160,
         runtimeCode,
+        salt,
         selector,
         send,
         sender,
@@ -4,6 +4,7 @@ use crate::{
 };
 use solar_ast as ast;
 use solar_data_structures::{index::IndexVec, smallvec::SmallVec};
+use solar_interface::Symbol;
 
 impl<'ast> super::LoweringContext<'_, 'ast, '_> {
     #[instrument(level = "debug", skip_all)]
@@ -16,15 +17,16 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
                 file: source.file.clone(),
                 imports: self.arena.alloc_slice_copy(&source.imports),
                 items: &[],
+                usings: &[],
+                min_version: None,
             };
             if let Some(ast) = &source.ast {
                 let mut items = SmallVec::<[_; 16]>::new();
                 self.current_source_id = id;
                 for item in ast.items.iter() {
                     match &item.kind {
-                        ast::ItemKind::Pragma(_)
-                        | ast::ItemKind::Import(_)
-                        | ast::ItemKind::Using(_) => {}
+                        ast::ItemKind::Pragma(_) | ast::ItemKind::Import(_) => {}
+                        ast::ItemKind::Using(_) => self.using_directives.push((id, None, item)),
                         ast::ItemKind::Contract(_)
                         | ast::ItemKind::Function(_)
                         | ast::ItemKind::Variable(_)
@@ -55,12 +57,14 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
 
             // Set later.
             bases: &[],
+            bases_args: &[],
             linearized_bases: &[],
 
             ctor: None,
             fallback: None,
             receive: None,
             items: &[],
+            usings: &[],
         });
         let prev_contract_id = std::mem::replace(&mut self.current_contract_id, Some(id));
         debug_assert_eq!(prev_contract_id, None);
@@ -71,7 +75,10 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
                 ast::ItemKind::Pragma(_)
                 | ast::ItemKind::Import(_)
                 | ast::ItemKind::Contract(_) => unreachable!("illegal item in contract body"),
-                ast::ItemKind::Using(_) => continue,
+                ast::ItemKind::Using(_) => {
+                    self.using_directives.push((self.current_source_id, Some(id), item));
+                    continue;
+                }
                 ast::ItemKind::Variable(_) => {
                     let hir::ItemId::Variable(id) = self.lower_item(item) else { unreachable!() };
                     items.push(hir::ItemId::Variable(id));
@@ -193,6 +200,9 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
             span: item.span,
             name,
             fields: &[],
+            namespace: crate::layout::find_storage_location(&item.docs)
+                .and_then(|(_, value)| crate::layout::parse_storage_location(value))
+                .map(Symbol::intern),
         })
     }
 
@@ -309,7 +319,7 @@ fn generate_partial_getter(hir: &mut hir::Hir<'_>, id: hir::VariableId) -> hir::
         getter,
     } = *hir.variable(id);
     debug_assert!(!indexed);
-    debug_assert!(data_location.is_none());
+    debug_assert!(matches!(data_location, None | Some(ast::DataLocation::Transient)));
     debug_assert_eq!(visibility, Some(ast::Visibility::Public));
     debug_assert!(kind.is_state());
     debug_assert!(getter.is_none());
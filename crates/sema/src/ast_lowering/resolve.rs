@@ -1,5 +1,5 @@
 use crate::{builtins::Builtin, hir, ParsedSources};
-use solar_ast as ast;
+use solar_ast::{self as ast, yul};
 use solar_data_structures::{
     index::{Idx, IndexVec},
     map::{FxIndexMap, IndexEntry},
@@ -8,7 +8,7 @@ use solar_data_structures::{
 };
 use solar_interface::{
     diagnostics::{DiagCtxt, ErrorGuaranteed},
-    sym, Ident, Session, Span, Symbol,
+    kw, sym, Ident, Session, Span, Symbol,
 };
 use std::{fmt, sync::atomic::AtomicUsize};
 
@@ -294,6 +294,28 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
                 cx.lower_variables(ast_event.parameters, hir::VarKind::Event);
         }
 
+        self.resolve_using_directives(next_id);
+
+        for id in self.hir.contract_ids() {
+            let ast_item = self.hir_to_ast[&hir::ItemId::Contract(id)];
+            let ast::ItemKind::Contract(ast_contract) = &ast_item.kind else { unreachable!() };
+            let &hir::Contract { source, bases, .. } = self.hir.contract(id);
+            // The bases are resolved in the source scope, and were already reported if invalid.
+            let source_scopes = SymbolResolverScopes::new_in(source, None);
+            let mut cx = mk_resolver!(@scopes SymbolResolverScopes::new_in(source, Some(id)), None);
+            let mut bases_args = SmallVec::<[_; 8]>::new();
+            for base in ast_contract.bases.iter().filter(|base| !base.arguments.is_empty()) {
+                let Ok(decl) = cx.resolver.resolve_path(base.name, &source_scopes) else {
+                    continue;
+                };
+                let Res::Item(base_id @ hir::ItemId::Contract(c)) = decl.res else { continue };
+                if bases.contains(&c) {
+                    bases_args.push(cx.lower_modifier(base_id, base));
+                }
+            }
+            self.hir.contracts[id].bases_args = self.arena.alloc_smallvec(bases_args);
+        }
+
         // Resolve constants and state variables.
         let normal_vars = self.hir.variables.len();
         for id in self.hir.variable_ids() {
@@ -315,7 +337,7 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
 
             let scopes = SymbolResolverScopes::new_in(func.source, func.contract);
 
-            self.hir.functions[id].modifiers = {
+            let modifiers = {
                 let mut modifiers = SmallVec::<[_; 8]>::new();
                 for modifier in ast_func.header.modifiers.iter() {
                     let expected = if func.kind.is_constructor() {
@@ -343,40 +365,24 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
                             continue;
                         }
                     }
-                    modifiers.push(id);
+                    modifiers.push((id, modifier));
                 }
-                self.arena.alloc_smallvec(modifiers)
+                modifiers
             };
 
-            let func = self.hir.function(id);
-            self.hir.functions[id].overrides = {
-                let mut overrides = SmallVec::<[_; 8]>::new();
-                if let Some(ov) = &ast_func.header.override_ {
-                    for path in ov.paths.iter() {
-                        let Ok(id) = self.resolver.resolve_path_as(path, &scopes, "contract")
-                        else {
-                            continue;
-                        };
-                        // TODO: Move to override checker.
-                        let Some(c) = func.contract else {
-                            self.dcx().err("free functions cannot override").span(ov.span).emit();
-                            continue;
-                        };
-                        if !self.hir.contract(c).linearized_bases[1..].contains(&id) {
-                            self.dcx().err("override is not a base contract").span(ov.span).emit();
-                            continue;
-                        }
-                        overrides.push(id);
-                    }
-                }
-                self.arena.alloc_smallvec(overrides)
-            };
+            self.hir.functions[id].overrides =
+                self.resolve_overrides(ast_func.header.override_.as_ref(), &scopes);
 
             let mut cx = ResolveContext::new(self, scopes, next_id, Some(id));
             cx.hir.functions[id].parameters =
                 cx.lower_variables(ast_func.header.parameters, hir::VarKind::FunctionParam);
             cx.hir.functions[id].returns =
                 cx.lower_variables(ast_func.header.returns, hir::VarKind::FunctionReturn);
+            // Modifier arguments can refer to the parameters.
+            let arena = cx.arena;
+            cx.hir.functions[id].modifiers = arena.alloc_slice_fill_iter(
+                modifiers.into_iter().map(|(id, modifier)| cx.lower_modifier(id, modifier)),
+            );
             if let Some(body) = &ast_func.body {
                 cx.hir.functions[id].body = Some(cx.lower_stmts(body));
             }
@@ -398,6 +404,13 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
         let ast::ItemKind::Variable(ast_var) = &ast_item.kind else { unreachable!() };
 
         let scopes = SymbolResolverScopes::new_in(var.source, var.contract);
+        let overrides = self.resolve_overrides(ast_var.override_.as_ref(), &scopes);
+        let getter = var.getter;
+        self.hir.variables[id].overrides = overrides;
+        if let Some(getter) = getter {
+            self.hir.functions[getter].overrides = overrides;
+        }
+
         let mut cx = ResolveContext::new(self, scopes, next_id, None);
         let init = ast_var.initializer.as_deref().map(|init| cx.lower_expr(init));
         let ty = cx.lower_type(&ast_var.ty);
@@ -405,6 +418,24 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
         self.hir.variables[id].ty = ty;
     }
 
+    /// Resolves the contracts listed in an `override` specifier.
+    ///
+    /// These are checked in the override checker.
+    fn resolve_overrides(
+        &self,
+        override_: Option<&ast::Override<'_>>,
+        scopes: &SymbolResolverScopes,
+    ) -> &'hir [hir::ContractId] {
+        let Some(override_) = override_ else { return &[] };
+        let mut overrides = SmallVec::<[_; 8]>::new();
+        for path in override_.paths.iter() {
+            if let Ok(id) = self.resolver.resolve_path_as(path, scopes, "contract") {
+                overrides.push(id);
+            }
+        }
+        self.arena.alloc_smallvec(overrides)
+    }
+
     /// Resolves a getter function.
     ///
     /// # Examples
@@ -591,6 +622,117 @@ impl<'hir> super::LoweringContext<'_, '_, 'hir> {
         }
     }
 
+    fn resolve_using_directives(&mut self, next_id: &AtomicUsize) {
+        let mut directives = FxIndexMap::<_, Vec<_>>::default();
+        for (source, contract, item) in std::mem::take(&mut self.using_directives) {
+            let ast::ItemKind::Using(using) = &item.kind else { unreachable!() };
+            let scopes = SymbolResolverScopes::new_in(source, contract);
+            let list = match &using.list {
+                ast::UsingList::Single(path) => {
+                    let Ok(id) = self.resolver.resolve_path_as(path, &scopes, "library") else {
+                        continue;
+                    };
+                    match id {
+                        hir::ItemId::Contract(id) if self.hir.contract(id).kind.is_library() => {
+                            hir::UsingList::Library(id)
+                        }
+                        _ => {
+                            self.resolver.report_expected(
+                                "library",
+                                self.hir.item(id).description(),
+                                path.span(),
+                            );
+                            continue;
+                        }
+                    }
+                }
+                ast::UsingList::Multiple(paths) => {
+                    let mut functions = SmallVec::<[_; 8]>::new();
+                    for (path, operator) in paths.iter() {
+                        let Ok(id) = self.resolver.resolve_path_as(path, &scopes, "function")
+                        else {
+                            continue;
+                        };
+                        let id = match id {
+                            hir::ItemId::Function(f)
+                                if self.hir.function(f).kind.is_function()
+                                    && self
+                                        .hir
+                                        .function(f)
+                                        .contract
+                                        .is_none_or(|c| self.hir.contract(c).kind.is_library()) =>
+                            {
+                                f
+                            }
+                            _ => {
+                                let msg = "only file-level functions and library functions can \
+                                           be attached to a type in a `using for` directive";
+                                self.dcx().err(msg).span(path.span()).emit();
+                                continue;
+                            }
+                        };
+                        functions.push(hir::UsingFunction {
+                            span: path.span(),
+                            id,
+                            operator: *operator,
+                        });
+                    }
+                    hir::UsingList::Functions(self.arena.alloc_smallvec(functions))
+                }
+            };
+            let ty = using.ty.as_ref().map(|ty| {
+                let mut cx = ResolveContext::new(self, scopes, next_id, None);
+                cx.lower_type(ty)
+            });
+            if using.global {
+                if let Some(ty) = &ty {
+                    self.check_global_using_type(ty, source);
+                }
+            }
+            directives.entry((source, contract)).or_default().push(hir::UsingDirective {
+                span: item.span,
+                list,
+                ty,
+                global: using.global,
+            });
+        }
+
+        for ((source, contract), directives) in directives {
+            let directives = &*self.arena.alloc_vec(directives);
+            match contract {
+                Some(id) => self.hir.contracts[id].usings = directives,
+                None => self.hir.sources[source].usings = directives,
+            }
+        }
+    }
+
+    /// Checks that the type of a `global` directive is a user-defined type declared at file level
+    /// in the same source.
+    fn check_global_using_type(&self, ty: &hir::Type<'_>, source: hir::SourceId) {
+        let hir::TypeKind::Custom(id) = ty.kind else {
+            if !matches!(ty.kind, hir::TypeKind::Err(_)) {
+                let msg = "can only use `global` with user-defined types";
+                self.dcx().err(msg).span(ty.span).emit();
+            }
+            return;
+        };
+        let (item_source, item_contract) = match id {
+            hir::ItemId::Struct(id) => (self.hir.strukt(id).source, self.hir.strukt(id).contract),
+            hir::ItemId::Enum(id) => (self.hir.enumm(id).source, self.hir.enumm(id).contract),
+            hir::ItemId::Udvt(id) => (self.hir.udvt(id).source, self.hir.udvt(id).contract),
+            _ => {
+                let msg = "can only use `global` with user-defined types";
+                self.dcx().err(msg).span(ty.span).emit();
+                return;
+            }
+        };
+        if item_source != source || item_contract.is_some() {
+            let msg =
+                "can only use `global` with types defined in the same source unit at file level";
+            self.dcx().err(msg).span(ty.span).emit();
+        }
+    }
+
     fn mk_var(
         &mut self,
         function: Option<hir::FunctionId>,
@@ -728,10 +870,13 @@ impl<'sess, 'hir, 'a> ResolveContext<'sess, 'hir, 'a> {
                 })),
                 self.lower_expr(expr),
             ),
-            ast::StmtKind::Assembly(_) => hir::StmtKind::Err(
-                // self.dcx().err("assembly is not yet implemented").span(stmt.span).emit(),
-                ErrorGuaranteed::new_unchecked(),
-            ),
+            ast::StmtKind::Assembly(assembly) => {
+                let mut state_accesses = Vec::new();
+                collect_yul_state_accesses(assembly.block, &mut state_accesses);
+                hir::StmtKind::Assembly(self.arena.alloc(hir::StmtAssembly {
+                    state_accesses: self.arena.alloc_slice_copy(&state_accesses),
+                }))
+            }
             ast::StmtKind::Block(stmts) => hir::StmtKind::Block(self.lower_block(stmts)),
             ast::StmtKind::UncheckedBlock(stmts) => {
                 hir::StmtKind::UncheckedBlock(self.lower_block(stmts))
@@ -1038,6 +1183,20 @@ impl<'sess, 'hir, 'a> ResolveContext<'sess, 'hir, 'a> {
         )
     }
 
+    fn lower_modifier(
+        &mut self,
+        id: hir::ItemId,
+        modifier: &ast::Modifier<'_>,
+    ) -> hir::Modifier<'hir> {
+        let args = self.lower_call_args(&modifier.arguments);
+        let span = if args.is_empty() {
+            modifier.name.span()
+        } else {
+            modifier.name.span().to(args.span())
+        };
+        hir::Modifier { span, id, args }
+    }
+
     fn lower_call_args(&mut self, args: &ast::CallArgs<'_>) -> hir::CallArgs<'hir> {
         match args {
             ast::CallArgs::Unnamed(args) => hir::CallArgs::Unnamed(self.lower_exprs(&**args)),
@@ -1082,6 +1241,113 @@ impl<'sess, 'hir, 'a> ResolveContext<'sess, 'hir, 'a> {
     }
 }
 
+/// Collects the calls to the EVM builtins in the given Yul block which read or modify the state.
+fn collect_yul_state_accesses(
+    block: &[yul::Stmt<'_>],
+    accesses: &mut Vec<(hir::StateMutability, Span)>,
+) {
+    for stmt in block {
+        match &stmt.kind {
+            yul::StmtKind::Block(block) => collect_yul_state_accesses(block, accesses),
+            yul::StmtKind::AssignSingle(_, expr) => collect_yul_expr(expr, accesses),
+            yul::StmtKind::AssignMulti(_, call) | yul::StmtKind::Expr(call) => {
+                collect_yul_call(call, accesses)
+            }
+            yul::StmtKind::If(cond, block) => {
+                collect_yul_expr(cond, accesses);
+                collect_yul_state_accesses(block, accesses);
+            }
+            yul::StmtKind::For { init, cond, step, body } => {
+                collect_yul_state_accesses(init, accesses);
+                collect_yul_expr(cond, accesses);
+                collect_yul_state_accesses(step, accesses);
+                collect_yul_state_accesses(body, accesses);
+            }
+            yul::StmtKind::Switch(switch) => {
+                collect_yul_expr(&switch.selector, accesses);
+                for case in switch.branches.iter() {
+                    collect_yul_state_accesses(case.body, accesses);
+                }
+                if let Some(block) = &switch.default_case {
+                    collect_yul_state_accesses(block, accesses);
+                }
+            }
+            yul::StmtKind::FunctionDef(function) => {
+                collect_yul_state_accesses(function.body, accesses)
+            }
+            yul::StmtKind::VarDecl(_, expr) => {
+                if let Some(expr) = expr {
+                    collect_yul_expr(expr, accesses);
+                }
+            }
+            yul::StmtKind::Leave | yul::StmtKind::Break | yul::StmtKind::Continue => {}
+        }
+    }
+}
+
+fn collect_yul_expr(expr: &yul::Expr<'_>, accesses: &mut Vec<(hir::StateMutability, Span)>) {
+    if let yul::ExprKind::Call(call) = &expr.kind {
+        collect_yul_call(call, accesses);
+    }
+}
+
+fn collect_yul_call(call: &yul::ExprCall<'_>, accesses: &mut Vec<(hir::StateMutability, Span)>) {
+    if let Some(mutability) = yul_builtin_state_mutability(call.name.name) {
+        accesses.push((mutability, call.name.span));
+    }
+    for arg in call.arguments.iter() {
+        collect_yul_expr(arg, accesses);
+    }
+}
+
+/// Returns the state mutability required by calling the given Yul EVM builtin, if it accesses the
+/// state.
+///
+/// Reference: <https://docs.soliditylang.org/en/latest/yul.html#evm-dialect>
+fn yul_builtin_state_mutability(name: Symbol) -> Option<hir::StateMutability> {
+    match name {
+        kw::Address
+        | kw::Balance
+        | kw::Basefee
+        | kw::Blobbasefee
+        | kw::Blobhash
+        | kw::Blockhash
+        | kw::Caller
+        | kw::Callvalue
+        | kw::Chainid
+        | kw::Coinbase
+        | kw::Difficulty
+        | kw::Extcodecopy
+        | kw::Extcodehash
+        | kw::Extcodesize
+        | kw::Gas
+        | kw::Gaslimit
+        | kw::Gasprice
+        | kw::Number
+        | kw::Origin
+        | kw::Prevrandao
+        | kw::Selfbalance
+        | kw::Sload
+        | kw::Staticcall
+        | kw::Timestamp
+        | kw::Tload => Some(hir::StateMutability::View),
+        kw::Call
+        | kw::Callcode
+        | kw::Create
+        | kw::Create2
+        | kw::Delegatecall
+        | kw::Log0
+        | kw::Log1
+        | kw::Log2
+        | kw::Log3
+        | kw::Log4
+        | kw::Selfdestruct
+        | kw::Sstore
+        | kw::Tstore => Some(hir::StateMutability::NonPayable),
+        _ => None,
+    }
+}
+
 struct ResolverError {
     name: Ident,
     kind: ResolverErrorKind,
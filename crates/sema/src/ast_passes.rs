@@ -1,13 +1,16 @@
 //! AST-related passes.
 
-use solar_ast::{self as ast, visit::Visit};
+use crate::min_version::MinVersion;
+use solar_ast::{self as ast, visit::Visit, yul};
 use solar_data_structures::Never;
 use solar_interface::{diagnostics::DiagCtxt, sym, Session, Span};
 use std::ops::ControlFlow;
 
+/// Runs all AST passes, returning the minimum Solidity version required by the source.
 #[instrument(name = "ast_passes", level = "debug", skip_all)]
-pub(crate) fn run(sess: &Session, ast: &ast::SourceUnit<'_>) {
+pub(crate) fn run(sess: &Session, ast: &ast::SourceUnit<'_>) -> MinVersion {
     validate(sess, ast);
+    crate::min_version::check(sess, ast)
 }
 
 /// Performs AST validation.
@@ -20,6 +23,7 @@ pub fn validate(sess: &Session, ast: &ast::SourceUnit<'_>) {
 /// AST validator.
 struct AstValidator<'sess, 'ast> {
     item_span: Span,
+    sess: &'sess Session,
     dcx: &'sess DiagCtxt,
     contract: Option<&'ast ast::ItemContract<'ast>>,
     function_kind: Option<ast::FunctionKind>,
@@ -32,6 +36,7 @@ impl<'sess> AstValidator<'sess, '_> {
     fn new(sess: &'sess Session) -> Self {
         Self {
             item_span: Span::DUMMY,
+            sess,
             dcx: &sess.dcx,
             contract: None,
             function_kind: None,
@@ -99,6 +104,20 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
 
     fn visit_item(&mut self, item: &'ast ast::Item<'ast>) -> ControlFlow<Self::BreakValue> {
         self.item_span = item.span;
+        if let Some((span, value)) = crate::layout::find_storage_location(&item.docs) {
+            if !matches!(item.kind, ast::ItemKind::Struct(_)) {
+                self.dcx()
+                    .err("`@custom:storage-location` can only be used on structs")
+                    .span(span)
+                    .emit();
+            } else if crate::layout::parse_storage_location(value).is_none() {
+                self.dcx()
+                    .err(format!("unsupported storage location `{value}`"))
+                    .span(span)
+                    .help("the storage location must be of the form `erc7201:<namespace id>`")
+                    .emit();
+            }
+        }
         self.walk_item(item)
     }
 
@@ -132,10 +151,24 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
         pragma: &'ast ast::PragmaDirective<'ast>,
     ) -> ControlFlow<Self::BreakValue> {
         match &pragma.tokens {
-            ast::PragmaTokens::Version(name, _version) => {
+            ast::PragmaTokens::Version(name, req) => {
                 if name.name != sym::solidity {
                     let msg = "only `solidity` is supported as a version pragma";
                     self.dcx().err(msg).span(name.span).emit();
+                    return ControlFlow::Continue(());
+                }
+                let version = self.sess.opts.solc_version();
+                if !req.matches(&version.clone().into()) {
+                    let msg = "source file requires a different compiler version";
+                    let note = format!(
+                        "the requirement is `{req}`, but the active version is `{version}`"
+                    );
+                    self.dcx()
+                        .err(msg)
+                        .span(self.item_span)
+                        .note(note)
+                        .help("use `--solc-version` to select a different version")
+                        .emit();
                 }
             }
             ast::PragmaTokens::Custom(name, value) => {
@@ -243,6 +276,17 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
             }
         }
 
+        if contract.kind.is_interface() {
+            for item in contract.body.iter() {
+                if let ast::ItemKind::Variable(var) = &item.kind {
+                    self.dcx()
+                        .err("variables cannot be declared in interfaces")
+                        .span(var.span)
+                        .emit();
+                }
+            }
+        }
+
         let r = self.walk_item_contract(contract);
         self.contract = None;
         r
@@ -266,6 +310,27 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
                     }
                 }
             }
+            if contract.kind.is_interface() {
+                if func.kind.is_constructor() {
+                    self.dcx()
+                        .err("constructors cannot be defined in interfaces")
+                        .span(self.item_span)
+                        .emit();
+                } else {
+                    if func.header.visibility.is_some_and(|v| v != ast::Visibility::External) {
+                        self.dcx()
+                            .err("functions in interfaces must be declared external")
+                            .span(self.item_span)
+                            .emit();
+                    }
+                    if func.is_implemented() && !func.kind.is_modifier() {
+                        self.dcx()
+                            .err("functions in interfaces cannot have an implementation")
+                            .span(func.body_span)
+                            .emit();
+                    }
+                }
+            }
             if contract.kind.is_interface() && !func.header.modifiers.is_empty() {
                 self.dcx()
                     .err("functions in interfaces cannot have modifiers")
@@ -332,6 +397,9 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
                     .help(format!("remove `{visibility}` from the declaration"))
                     .emit();
             }
+            if let Some(override_) = &func.header.override_ {
+                self.dcx().err("free functions cannot override").span(override_.span).emit();
+            }
         }
 
         let current_placeholder_count = self.placeholder_count;
@@ -392,6 +460,26 @@ impl<'ast> Visit<'ast> for AstValidator<'_, 'ast> {
         self.walk_expr(expr)
     }
 
+    fn visit_yul_expr_call(
+        &mut self,
+        call: &'ast yul::ExprCall<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        let name = call.name;
+        if let Some(required) = crate::builtins::yul_min_evm_version(name.name) {
+            let evm_version = self.sess.opts.evm_version;
+            if evm_version < required {
+                crate::builtins::unsupported_evm_version(
+                    self.dcx(),
+                    name.span,
+                    name.as_str(),
+                    required,
+                    evm_version,
+                );
+            }
+        }
+        self.walk_yul_expr_call(call)
+    }
+
     fn visit_ty(&mut self, ty: &'ast solar_ast::Type<'ast>) -> ControlFlow<Self::BreakValue> {
         if let ast::TypeKind::Function(f) = &ty.kind {
             for param in f.returns.iter() {
@@ -3,7 +3,7 @@ use crate::{
     hir,
     ty::{Gcx, Ty, TyFnPtr, TyKind},
 };
-use solar_ast::{DataLocation, ElementaryType, StateMutability as SM};
+use solar_ast::{DataLocation, ElementaryType, StateMutability as SM, Visibility};
 use solar_data_structures::BumpExt;
 use solar_interface::{kw, sym, Symbol};
 
@@ -26,7 +26,7 @@ pub(crate) fn members_of<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>) -> MemberList<'gcx>
         },
         TyKind::StringLiteral(_utf8, _size) => Default::default(),
         TyKind::IntLiteral(_size) => Default::default(),
-        TyKind::Ref(inner, loc) => reference(gcx, ty, inner, loc),
+        TyKind::Ref(inner, loc) => reference(gcx, inner, loc),
         TyKind::DynArray(_ty) => expected_ref(),
         TyKind::Array(_ty, _len) => expected_ref(),
         TyKind::Tuple(_tys) => Default::default(),
@@ -51,8 +51,8 @@ pub(crate) fn members_of<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>) -> MemberList<'gcx>
             .iter()
             .map(|&b| Member::of_builtin(gcx, b))
             .collect(),
-        TyKind::Type(_ty) => type_type(gcx, ty),
-        TyKind::Meta(_ty) => meta(gcx, ty),
+        TyKind::Type(ty) => type_type(gcx, ty),
+        TyKind::Meta(ty) => meta(gcx, ty),
         TyKind::Err(_guar) => Default::default(),
     })
 }
@@ -78,7 +78,7 @@ impl<'gcx> Member<'gcx> {
     }
 
     pub fn of_builtin(gcx: Gcx<'gcx>, builtin: Builtin) -> Self {
-        Self { name: builtin.name(), ty: builtin.ty(gcx), res: None }
+        Self::with_builtin(builtin, builtin.ty(gcx))
     }
 
     pub fn of_builtins(
@@ -135,17 +135,98 @@ pub(crate) fn contract(gcx: Gcx<'_>, id: hir::ContractId) -> MemberListOwned<'_>
         .collect()
 }
 
-fn function<'gcx>(gcx: Gcx<'gcx>, f: &'gcx TyFnPtr<'gcx>) -> MemberListOwned<'gcx> {
-    let _ = (gcx, f);
-    todo!()
+/// Returns the functions attached to `ty` by the `using for` directives declared in the given
+/// contract, at file level in the given source, and the `global` directives of the source that
+/// defines `ty`.
+///
+/// Like in solc, directives of base contracts are not inherited.
+pub(crate) fn attached_functions<'gcx>(
+    gcx: Gcx<'gcx>,
+    ty: Ty<'gcx>,
+    source: hir::SourceId,
+    contract: Option<hir::ContractId>,
+) -> MemberListOwned<'gcx> {
+    let contract_usings = contract.map(|id| gcx.hir.contract(id).usings).unwrap_or_default();
+    let source_usings = gcx.hir.source(source).usings;
+    let defining_source = match ty.peel_refs().kind {
+        TyKind::Struct(id) => Some(gcx.hir.strukt(id).source),
+        TyKind::Enum(id) => Some(gcx.hir.enumm(id).source),
+        TyKind::Udvt(_, id) => Some(gcx.hir.udvt(id).source),
+        _ => None,
+    };
+    let global_usings = defining_source
+        .filter(|&id| id != source)
+        .map(|id| gcx.hir.source(id).usings)
+        .unwrap_or_default()
+        .iter()
+        .filter(|using| using.global);
+
+    let mut members = MemberListOwned::new();
+    let mut attach = |id: hir::FunctionId| {
+        let res = hir::Res::Item(id.into());
+        if members.iter().any(|m: &Member<'_>| m.res == Some(res)) {
+            return;
+        }
+        if let Some(bound) = bind_first_parameter(gcx, ty, id) {
+            members.push(Member::with_res(gcx.item_name(id).name, bound, res));
+        }
+    };
+    for using in contract_usings.iter().chain(source_usings).chain(global_usings) {
+        if let Some(using_ty) = &using.ty {
+            if gcx.type_of_hir_ty(using_ty).peel_refs() != ty.peel_refs() {
+                continue;
+            }
+        }
+        match using.list {
+            hir::UsingList::Library(id) => {
+                for f in gcx.hir.contract(id).functions() {
+                    let func = gcx.hir.function(f);
+                    if func.kind.is_function() && func.visibility >= Visibility::Internal {
+                        attach(f);
+                    }
+                }
+            }
+            hir::UsingList::Functions(functions) => {
+                // Functions bound to operators are not attached as members.
+                for f in functions.iter().filter(|f| f.operator.is_none()) {
+                    attach(f.id);
+                }
+            }
+        }
+    }
+    members
 }
 
-fn reference<'gcx>(
+/// Returns the type of the given function with its first parameter bound to a value of type
+/// `ty`, if the value can be passed to it.
+fn bind_first_parameter<'gcx>(
     gcx: Gcx<'gcx>,
-    this: Ty<'gcx>,
-    inner: Ty<'gcx>,
-    loc: DataLocation,
-) -> MemberListOwned<'gcx> {
+    ty: Ty<'gcx>,
+    id: hir::FunctionId,
+) -> Option<Ty<'gcx>> {
+    let TyKind::FnPtr(f) = gcx.type_of_item(id.into()).kind else { unreachable!() };
+    let (&first, rest) = f.parameters.split_first()?;
+    // Storage pointers can't be created from values in other locations.
+    if let (TyKind::Ref(_, from), TyKind::Ref(_, DataLocation::Storage)) = (&ty.kind, &first.kind) {
+        if *from != DataLocation::Storage {
+            return None;
+        }
+    }
+    if !ty.convert_implicit_to(first, gcx) {
+        return None;
+    }
+    Some(gcx.mk_ty_fn_ptr(TyFnPtr { parameters: rest, ..*f }))
+}
+
+fn function<'gcx>(gcx: Gcx<'gcx>, f: &'gcx TyFnPtr<'gcx>) -> MemberListOwned<'gcx> {
+    if f.visibility == Visibility::External {
+        Member::of_builtins(gcx, [Builtin::FunctionSelector, Builtin::FunctionAddress])
+    } else {
+        Default::default()
+    }
+}
+
+fn reference<'gcx>(gcx: Gcx<'gcx>, inner: Ty<'gcx>, loc: DataLocation) -> MemberListOwned<'gcx> {
     match (&inner.kind, loc) {
         (&TyKind::Struct(id), _) => {
             let fields = gcx.hir.strukt(id).fields;
@@ -154,7 +235,7 @@ fn reference<'gcx>(
             fields
                 .iter()
                 .zip(tys)
-                .map(|(&f, &ty)| Member::new(gcx.item_name(f).name, ty.with_loc(gcx, loc)))
+                .map(|(&f, &ty)| Member::new(gcx.item_name(f).name, ty.with_loc_if_ref(gcx, loc)))
                 .collect()
         }
         (
@@ -166,11 +247,12 @@ fn reference<'gcx>(
             } else {
                 gcx.types.fixed_bytes(1)
             };
+            let inner_ref = inner.with_loc_if_ref(gcx, loc);
             vec![
                 Member::new(sym::length, gcx.types.uint(256)),
-                Member::new(sym::push, gcx.mk_builtin_fn(&[this, inner], SM::NonPayable, &[])),
-                Member::new(sym::push, gcx.mk_builtin_fn(&[this], SM::NonPayable, &[inner])),
-                Member::new(kw::Pop, gcx.mk_builtin_fn(&[this], SM::NonPayable, &[])),
+                Member::new(sym::push, gcx.mk_builtin_fn(&[inner], SM::NonPayable, &[])),
+                Member::new(sym::push, gcx.mk_builtin_fn(&[], SM::NonPayable, &[inner_ref])),
+                Member::new(kw::Pop, gcx.mk_builtin_fn(&[], SM::NonPayable, &[])),
             ]
         }
         (
@@ -4,7 +4,11 @@ use crate::{
     ty::{Gcx, Ty},
 };
 use solar_ast::StateMutability as SM;
-use solar_interface::{kw, sym, Span, Symbol};
+use solar_interface::{
+    config::EvmVersion,
+    diagnostics::{DiagCtxt, ErrorGuaranteed},
+    kw, sym, Span, Symbol,
+};
 
 pub(crate) mod members;
 pub use members::{Member, MemberList};
@@ -90,13 +94,13 @@ declare_builtins! {
                            => gcx.mk_builtin_fn(&[gcx.types.uint(256), gcx.types.uint(256), gcx.types.uint(256)], SM::Pure, &[gcx.types.uint(256)]);
 
     Keccak256              => kw::Keccak256
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.fixed_bytes(32)]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::Pure, &[gcx.types.fixed_bytes(32)]);
     Sha256                 => sym::sha256
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.fixed_bytes(32)]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::Pure, &[gcx.types.fixed_bytes(32)]);
     Ripemd160              => sym::ripemd160
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.fixed_bytes(20)]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::Pure, &[gcx.types.fixed_bytes(20)]);
     EcRecover              => sym::ecrecover
-                           => gcx.mk_builtin_fn(&[gcx.types.fixed_bytes(32), gcx.types.uint(8), gcx.types.fixed_bytes(32), gcx.types.fixed_bytes(32)], SM::View, &[gcx.types.address]);
+                           => gcx.mk_builtin_fn(&[gcx.types.fixed_bytes(32), gcx.types.uint(8), gcx.types.fixed_bytes(32), gcx.types.fixed_bytes(32)], SM::Pure, &[gcx.types.address]);
 
     Block                  => sym::block
                            => gcx.mk_builtin_mod(Self::Block);
@@ -178,11 +182,11 @@ declare_builtins! {
     AddressCodehash        => sym::codehash
                            => gcx.types.fixed_bytes(32);
     AddressCall            => kw::Call
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.bytes_ref.memory]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::Payable, &[gcx.types.bool, gcx.types.bytes_ref.memory]);
     AddressDelegatecall    => kw::Delegatecall
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.bytes_ref.memory]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::NonPayable, &[gcx.types.bool, gcx.types.bytes_ref.memory]);
     AddressStaticcall      => kw::Staticcall
-                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.bytes_ref.memory]);
+                           => gcx.mk_builtin_fn(&[gcx.types.bytes_ref.memory], SM::View, &[gcx.types.bool, gcx.types.bytes_ref.memory]);
 
     AddressPayableTransfer => sym::transfer
                            => gcx.mk_builtin_fn(&[gcx.types.uint(256)], SM::NonPayable, &[]);
@@ -201,6 +205,11 @@ declare_builtins! {
     EventSelector          => sym::selector
                            => gcx.types.fixed_bytes(32);
 
+    FunctionSelector       => sym::selector
+                           => gcx.types.fixed_bytes(4);
+    FunctionAddress        => kw::Address
+                           => gcx.types.address;
+
     // `type(T)`
     ContractCreationCode   => sym::creationCode
                            => gcx.types.bytes_ref.memory;
@@ -281,6 +290,38 @@ impl Builtin {
         builtin_range_slice!(Self::FIRST_GLOBAL, Self::LAST_GLOBAL)
     }
 
+    /// Returns `true` if the builtin is a function that accepts a variable number of arguments,
+    /// which are not described by its type.
+    pub fn is_variadic(self) -> bool {
+        use Builtin::*;
+        matches!(
+            self,
+            AbiEncode
+                | AbiEncodePacked
+                | AbiEncodeWithSelector
+                | AbiEncodeCall
+                | AbiEncodeWithSignature
+                | AbiDecode
+                | StringConcat
+                | BytesConcat
+        )
+    }
+
+    /// Returns the oldest EVM version that supports the builtin, or `None` if it is supported by
+    /// all EVM versions.
+    pub fn min_evm_version(self) -> Option<EvmVersion> {
+        use Builtin::*;
+        Some(match self {
+            AddressStaticcall => EvmVersion::Byzantium,
+            AddressCodehash => EvmVersion::Constantinople,
+            BlockChainid => EvmVersion::Istanbul,
+            BlockBasefee => EvmVersion::London,
+            BlockPrevrandao => EvmVersion::Paris,
+            Blobhash | BlockBlobbasefee => EvmVersion::Cancun,
+            _ => return None,
+        })
+    }
+
     /// Returns the builtin's members.
     pub fn members(self) -> Option<&'static [Self]> {
         use Builtin::*;
@@ -294,6 +335,37 @@ impl Builtin {
     }
 }
 
+/// Returns the oldest EVM version that supports the given Yul builtin, or `None` if it is
+/// supported by all EVM versions.
+pub fn yul_min_evm_version(name: Symbol) -> Option<EvmVersion> {
+    Some(match name {
+        kw::Returndatasize | kw::Returndatacopy | kw::Staticcall => EvmVersion::Byzantium,
+        kw::Shl | kw::Shr | kw::Sar | kw::Create2 | kw::Extcodehash => EvmVersion::Constantinople,
+        kw::Chainid | kw::Selfbalance => EvmVersion::Istanbul,
+        kw::Basefee => EvmVersion::London,
+        kw::Prevrandao => EvmVersion::Paris,
+        kw::Blobbasefee | kw::Blobhash | kw::Mcopy | kw::Tload | kw::Tstore => EvmVersion::Cancun,
+        _ => return None,
+    })
+}
+
+/// Reports the use of `name`, which requires `required`, with an older EVM version.
+pub(crate) fn unsupported_evm_version(
+    dcx: &DiagCtxt,
+    span: Span,
+    name: &str,
+    required: EvmVersion,
+    evm_version: EvmVersion,
+) -> ErrorGuaranteed {
+    dcx.err(format!("`{name}` is not supported by the selected EVM version"))
+        .span(span)
+        .note(format!(
+            "`{name}` requires EVM version `{required}` or later, but `{evm_version}` is selected"
+        ))
+        .help("use `--evm-version` to select a newer EVM version")
+        .emit()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
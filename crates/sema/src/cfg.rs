@@ -0,0 +1,465 @@
+//! Control-flow graphs of function and modifier bodies.
+//!
+//! The graph is built directly from the HIR statements. Expressions are not split, so a basic
+//! block is a list of straight-line statements followed by a [`Terminator`] which determines where
+//! the control flow goes next.
+
+use crate::{
+    builtins::Builtin,
+    hir::{self, ExprKind, Res, StmtKind},
+    ty::Gcx,
+};
+use solar_data_structures::{
+    index::{IndexSlice, IndexVec},
+    newtype_index,
+    smallvec::SmallVec,
+    BumpExt,
+};
+use solar_interface::{Session, Span};
+use std::fmt::{self, Write};
+
+newtype_index! {
+    /// A [`BasicBlock`] ID.
+    pub struct BlockId;
+}
+
+/// The control-flow graph of a function or modifier body.
+///
+/// Return type of [`Gcx::cfg`].
+#[derive(Debug)]
+pub struct Cfg<'hir> {
+    /// The basic blocks. The first one is the entry block.
+    pub blocks: &'hir IndexSlice<BlockId, [BasicBlock<'hir>]>,
+}
+
+impl<'hir> Cfg<'hir> {
+    /// The entry block ID.
+    pub const ENTRY: BlockId = BlockId::new(0);
+
+    /// Returns the basic block with the given ID.
+    #[inline]
+    pub fn block(&self, id: BlockId) -> &BasicBlock<'hir> {
+        &self.blocks[id]
+    }
+
+    /// Returns an iterator over all the basic blocks and their IDs.
+    pub fn iter_blocks(&self) -> impl ExactSizeIterator<Item = (BlockId, &BasicBlock<'hir>)> {
+        self.blocks.iter_enumerated()
+    }
+
+    /// Returns the blocks which can be reached from the entry block.
+    pub fn reachable_blocks(&self) -> IndexVec<BlockId, bool> {
+        let mut reachable = IndexVec::from_vec(vec![false; self.blocks.len()]);
+        let mut stack = vec![Self::ENTRY];
+        while let Some(id) = stack.pop() {
+            if std::mem::replace(&mut reachable[id], true) {
+                continue;
+            }
+            stack.extend(self.blocks[id].terminator.successors());
+        }
+        reachable
+    }
+
+    /// Returns a value that formats the graph in the Graphviz DOT format.
+    pub fn dot<'a>(&'a self, sess: &'a Session, name: &'a str) -> impl fmt::Display + 'a {
+        let sm = sess.source_map();
+        solar_data_structures::fmt::from_fn(move |f| {
+            let snippet = |span| {
+                let s = sm.span_to_snippet(span).unwrap_or_else(|_| "<unknown>".into());
+                escape(&s.split_whitespace().collect::<Vec<_>>().join(" "))
+            };
+
+            writeln!(f, "digraph \"{}\" {{", escape(name))?;
+            writeln!(f, "    node [shape=box, fontname=monospace];")?;
+            for (id, block) in self.iter_blocks() {
+                let id = id.index();
+                let mut label = format!("bb{id}\\l");
+                for stmt in block.stmts {
+                    let _ = write!(label, "{}\\l", snippet(stmt.span));
+                }
+                match block.terminator {
+                    Terminator::Goto(_) => {}
+                    Terminator::Branch { cond, .. } => {
+                        let _ = write!(label, "if {}\\l", snippet(cond.span));
+                    }
+                    Terminator::Try { stmt, .. } => {
+                        let _ = write!(label, "try {}\\l", snippet(stmt.expr.span));
+                    }
+                    Terminator::Placeholder { .. } => label.push_str("_;\\l"),
+                    Terminator::Return => label.push_str("return\\l"),
+                    Terminator::Revert => label.push_str("revert\\l"),
+                }
+                writeln!(f, "    bb{id} [label=\"{label}\"];")?;
+
+                match block.terminator {
+                    Terminator::Branch { then, else_, .. } => {
+                        let (then, else_) = (then.index(), else_.index());
+                        writeln!(f, "    bb{id} -> bb{then} [label=\"true\"];")?;
+                        writeln!(f, "    bb{id} -> bb{else_} [label=\"false\"];")?;
+                    }
+                    Terminator::Try { clauses, .. } => {
+                        for (i, clause) in clauses.iter().enumerate() {
+                            let label = if i == 0 { "returns" } else { "catch" };
+                            let clause = clause.index();
+                            writeln!(f, "    bb{id} -> bb{clause} [label=\"{label}\"];")?;
+                        }
+                    }
+                    // The body of the modified function is executed along this edge.
+                    Terminator::Placeholder { target, .. } => {
+                        let target = target.index();
+                        writeln!(f, "    bb{id} -> bb{target} [label=\"body\", style=dashed];")?;
+                    }
+                    terminator => {
+                        for succ in terminator.successors() {
+                            writeln!(f, "    bb{id} -> bb{};", succ.index())?;
+                        }
+                    }
+                }
+            }
+            writeln!(f, "}}")
+        })
+    }
+}
+
+/// A basic block: a sequence of statements that are executed in order, followed by a
+/// terminator.
+#[derive(Debug)]
+pub struct BasicBlock<'hir> {
+    /// The statements executed in this block.
+    ///
+    /// These are never compound statements like blocks, loops, `if` or `try`, or modifier
+    /// placeholders, which are instead represented by the edges of the graph. Statements which transfer control, like `return`,
+    /// `break` or `revert`, are the last statement of their block.
+    pub stmts: &'hir [&'hir hir::Stmt<'hir>],
+    /// Where the control flow goes after the statements.
+    pub terminator: Terminator<'hir>,
+}
+
+/// How a [`BasicBlock`] ends.
+#[derive(Clone, Copy, Debug)]
+pub enum Terminator<'hir> {
+    /// Jumps to the given block.
+    Goto(BlockId),
+    /// Evaluates the condition and jumps to one of the two blocks.
+    Branch {
+        /// The condition.
+        cond: &'hir hir::Expr<'hir>,
+        /// The block executed if the condition is true.
+        then: BlockId,
+        /// The block executed if the condition is false.
+        else_: BlockId,
+    },
+    /// Performs the external call of a `try` statement and jumps to one of its clauses.
+    Try {
+        /// The `try` statement.
+        stmt: &'hir hir::StmtTry<'hir>,
+        /// The entry blocks of the clauses, in the same order as [`hir::StmtTry::clauses`].
+        clauses: &'hir [BlockId],
+    },
+    /// Executes the body of the function that the modifier is applied to at a placeholder
+    /// statement, `_;`, and then jumps to the given block.
+    Placeholder {
+        /// The span of the placeholder statement.
+        span: Span,
+        /// The block executed after the function body.
+        target: BlockId,
+    },
+    /// Returns from the function, either with a `return` statement or by reaching the end of
+    /// the body.
+    Return,
+    /// Reverts the execution, either with a `revert` statement or a call to a builtin that
+    /// always reverts, like `revert()` or `assert(false)`.
+    Revert,
+}
+
+impl Terminator<'_> {
+    /// Returns the blocks that the control flow can go to.
+    pub fn successors(&self) -> SmallVec<[BlockId; 2]> {
+        match *self {
+            Self::Goto(target) | Self::Placeholder { target, .. } => {
+                SmallVec::from_slice(&[target])
+            }
+            Self::Branch { then, else_, .. } => SmallVec::from_slice(&[then, else_]),
+            Self::Try { clauses, .. } => SmallVec::from_slice(clauses),
+            Self::Return | Self::Revert => SmallVec::new(),
+        }
+    }
+}
+
+/// Builds the control-flow graph of the given function's body.
+pub(crate) fn build<'gcx>(gcx: Gcx<'gcx>, id: hir::FunctionId) -> Option<&'gcx Cfg<'gcx>> {
+    let body = gcx.hir.function(id).body?;
+    let mut builder =
+        CfgBuilder { gcx, blocks: IndexVec::new(), current: Cfg::ENTRY, loops: Vec::new() };
+    builder.current = builder.new_block();
+    builder.lower_block(body);
+    builder.blocks[builder.current].terminator = Some(Terminator::Return);
+
+    let blocks = builder.finish();
+    Some(gcx.bump().alloc(Cfg { blocks: IndexSlice::new(blocks) }))
+}
+
+struct BlockBuilder<'hir> {
+    stmts: Vec<&'hir hir::Stmt<'hir>>,
+    terminator: Option<Terminator<'hir>>,
+}
+
+struct CfgBuilder<'hir> {
+    gcx: Gcx<'hir>,
+    blocks: IndexVec<BlockId, BlockBuilder<'hir>>,
+    /// The block that statements are currently being added to.
+    current: BlockId,
+    /// The `continue` and `break` targets of the enclosing loops.
+    loops: Vec<(BlockId, BlockId)>,
+}
+
+impl<'hir> CfgBuilder<'hir> {
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(BlockBuilder { stmts: Vec::new(), terminator: None })
+    }
+
+    /// Terminates the current block and continues in a new, unreachable block.
+    fn terminate(&mut self, terminator: Terminator<'hir>) {
+        self.blocks[self.current].terminator = Some(terminator);
+        self.current = self.new_block();
+    }
+
+    /// Terminates the current block with a jump, and continues in the target block.
+    fn goto(&mut self, target: BlockId) {
+        self.blocks[self.current].terminator = Some(Terminator::Goto(target));
+        self.current = target;
+    }
+
+    fn push(&mut self, stmt: &'hir hir::Stmt<'hir>) {
+        self.blocks[self.current].stmts.push(stmt);
+    }
+
+    fn lower_block(&mut self, block: hir::Block<'hir>) {
+        for stmt in block {
+            self.lower_stmt(stmt);
+        }
+    }
+
+    fn lower_stmt(&mut self, stmt: &'hir hir::Stmt<'hir>) {
+        match stmt.kind {
+            StmtKind::Block(block) | StmtKind::UncheckedBlock(block) => self.lower_block(block),
+            StmtKind::Return(_) => {
+                self.push(stmt);
+                self.terminate(Terminator::Return);
+            }
+            StmtKind::Revert(_) => {
+                self.push(stmt);
+                self.terminate(Terminator::Revert);
+            }
+            StmtKind::Expr(expr) if always_reverts(expr) => {
+                self.push(stmt);
+                self.terminate(Terminator::Revert);
+            }
+            StmtKind::Break | StmtKind::Continue => {
+                self.push(stmt);
+                // `break` and `continue` outside of loops are rejected in `ast_passes`.
+                let Some(&(continue_, break_)) = self.loops.last() else {
+                    self.terminate(Terminator::Return);
+                    return;
+                };
+                let target = if matches!(stmt.kind, StmtKind::Break) { break_ } else { continue_ };
+                self.terminate(Terminator::Goto(target));
+            }
+            StmtKind::Loop(body, source) => self.lower_loop(stmt.span, body, source),
+            StmtKind::If(cond, then, else_) => {
+                let then_block = self.new_block();
+                let else_block = self.new_block();
+                let join = if else_.is_some() { self.new_block() } else { else_block };
+                self.blocks[self.current].terminator =
+                    Some(Terminator::Branch { cond, then: then_block, else_: else_block });
+
+                self.current = then_block;
+                self.lower_stmt(then);
+                self.goto(join);
+                if let Some(else_) = else_ {
+                    self.current = else_block;
+                    self.lower_stmt(else_);
+                    self.goto(join);
+                }
+                self.current = join;
+            }
+            StmtKind::Try(try_) => {
+                let join = self.new_block();
+                let clauses = try_.clauses.iter().map(|_| self.new_block()).collect::<Vec<_>>();
+                let from = self.current;
+                for (clause, &block) in try_.clauses.iter().zip(&clauses) {
+                    self.current = block;
+                    self.lower_block(clause.block);
+                    self.goto(join);
+                }
+                self.blocks[from].terminator = Some(Terminator::Try {
+                    stmt: try_,
+                    clauses: self.gcx.bump().alloc_vec(clauses),
+                });
+                self.current = join;
+            }
+            StmtKind::Placeholder => {
+                let target = self.new_block();
+                self.blocks[self.current].terminator =
+                    Some(Terminator::Placeholder { span: stmt.span, target });
+                self.current = target;
+            }
+            StmtKind::Assembly(_)
+            | StmtKind::DeclSingle(_)
+            | StmtKind::DeclMulti(..)
+            | StmtKind::Emit(_)
+            | StmtKind::Expr(_)
+            | StmtKind::Err(_) => self.push(stmt),
+        }
+    }
+
+    fn lower_loop(&mut self, span: Span, body: hir::Block<'hir>, source: hir::LoopSource) {
+        let header = self.new_block();
+        let exit = self.new_block();
+        self.goto(header);
+
+        // The conditions of the desugared loops are lowered directly into branches, so that the
+        // synthesized `break` and `continue` statements don't appear in the graph.
+        if let (hir::LoopSource::DoWhile, [body, check]) = (source, body) {
+            let StmtKind::If(cond, ..) = check.kind else { unreachable!("invalid do while loop") };
+            let check = self.new_block();
+            self.loops.push((check, exit));
+            self.lower_stmt(body);
+            self.loops.pop();
+            self.goto(check);
+            self.blocks[check].terminator =
+                Some(Terminator::Branch { cond, then: header, else_: exit });
+            self.current = exit;
+            return;
+        }
+
+        let (cond, body, next) = split_loop_body(span, body, source);
+        // `while (true)` loops can only be exited with `break`.
+        if let Some(cond) = cond.filter(|&cond| !is_bool_lit(cond, true)) {
+            let body_block = self.new_block();
+            self.blocks[header].terminator =
+                Some(Terminator::Branch { cond, then: body_block, else_: exit });
+            self.current = body_block;
+        }
+        // `continue` jumps to the increment expression of `for` loops.
+        let continue_ = if next.is_some() { self.new_block() } else { header };
+        self.loops.push((continue_, exit));
+        self.lower_block(body);
+        self.loops.pop();
+        if let Some(next) = next {
+            self.goto(continue_);
+            self.lower_stmt(next);
+        }
+        self.goto(header);
+        self.current = exit;
+    }
+
+    /// Removes the empty blocks that can't be reached, which are created after statements that
+    /// transfer control, and allocates the graph.
+    fn finish(mut self) -> &'hir mut [BasicBlock<'hir>] {
+        let gcx = self.gcx;
+        let mut has_predecessors = IndexVec::from_vec(vec![false; self.blocks.len()]);
+        has_predecessors[Cfg::ENTRY] = true;
+        for block in &self.blocks {
+            for succ in block.terminator.unwrap().successors() {
+                has_predecessors[succ] = true;
+            }
+        }
+        let is_removed = |id: BlockId, block: &BlockBuilder<'_>| {
+            !has_predecessors[id]
+                && block.stmts.is_empty()
+                && matches!(block.terminator, Some(Terminator::Goto(_) | Terminator::Return))
+        };
+
+        let mut new_ids = IndexVec::<BlockId, _>::with_capacity(self.blocks.len());
+        let mut next_id = 0;
+        for (id, block) in self.blocks.iter_enumerated() {
+            new_ids.push(BlockId::from_usize(next_id));
+            if !is_removed(id, block) {
+                next_id += 1;
+            }
+        }
+        let remap = |id: &mut BlockId| *id = new_ids[*id];
+
+        let blocks = std::mem::take(&mut self.blocks)
+            .into_iter_enumerated()
+            .filter(|(id, block)| !is_removed(*id, block))
+            .map(|(_, block)| {
+                let mut terminator = block.terminator.unwrap();
+                match &mut terminator {
+                    Terminator::Goto(target) | Terminator::Placeholder { target, .. } => {
+                        remap(target)
+                    }
+                    Terminator::Branch { then, else_, .. } => {
+                        remap(then);
+                        remap(else_);
+                    }
+                    Terminator::Try { clauses, .. } => {
+                        let mut new = clauses.to_vec();
+                        new.iter_mut().for_each(remap);
+                        *clauses = gcx.bump().alloc_vec(new);
+                    }
+                    Terminator::Return | Terminator::Revert => {}
+                }
+                BasicBlock { stmts: gcx.bump().alloc_vec(block.stmts), terminator }
+            });
+        gcx.bump().alloc_from_iter(blocks)
+    }
+}
+
+/// Splits the body of a desugared `while` or `for` loop into its condition, the original body,
+/// and the increment expression of `for` loops.
+///
+/// See `lower_loop_stmt` in `ast_lowering`.
+#[allow(clippy::type_complexity)]
+fn split_loop_body<'hir>(
+    span: Span,
+    body: hir::Block<'hir>,
+    source: hir::LoopSource,
+) -> (Option<&'hir hir::Expr<'hir>>, hir::Block<'hir>, Option<&'hir hir::Stmt<'hir>>) {
+    let [stmt] = body else { return (None, body, None) };
+    // The synthesized `break` statement has the span of the whole loop.
+    let (cond, body) = match stmt.kind {
+        StmtKind::If(cond, then, Some(hir::Stmt { kind: StmtKind::Break, span: break_span }))
+            if *break_span == span =>
+        {
+            (Some(cond), then)
+        }
+        _ => (None, stmt),
+    };
+    if source == hir::LoopSource::For {
+        // The synthesized block has the same span as the loop body, while a user-written block
+        // also includes the braces.
+        if let StmtKind::Block([inner, next @ hir::Stmt { kind: StmtKind::Expr(_), .. }]) =
+            body.kind
+        {
+            if body.span == inner.span {
+                return (cond, std::slice::from_ref(inner), Some(next));
+            }
+        }
+    }
+    (cond, std::slice::from_ref(body), None)
+}
+
+/// Returns `true` if the expression is a call to a builtin which always reverts.
+fn always_reverts(expr: &hir::Expr<'_>) -> bool {
+    let ExprKind::Call(callee, args, _) = &expr.kind else { return false };
+    let ExprKind::Ident(res @ [_, ..]) = callee.kind else { return false };
+    let is_builtin = |builtins: &[Builtin]| {
+        res.iter().all(|res| matches!(res, Res::Builtin(b) if builtins.contains(b)))
+    };
+    is_builtin(&[Builtin::Revert, Builtin::RevertMsg])
+        || (is_builtin(&[Builtin::Assert, Builtin::Require, Builtin::RequireMsg])
+            && args.exprs().next().is_some_and(|arg| is_bool_lit(arg, false)))
+}
+
+fn is_bool_lit(expr: &hir::Expr<'_>, value: bool) -> bool {
+    matches!(
+        expr.peel_parens().kind,
+        ExprKind::Lit(hir::Lit { kind: solar_ast::LitKind::Bool(b), .. }) if *b == value
+    )
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
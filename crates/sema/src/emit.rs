@@ -1,5 +1,12 @@
-use crate::ty::Gcx;
+use crate::{
+    eval::ConstValue,
+    hir,
+    layout::{diff::LayoutChange, json::StorageLayout},
+    ty::{Gcx, Ty, TyKind},
+};
+use alloy_primitives::hex;
 use serde::Serialize;
+use solar_ast::ElementaryType;
 use solar_interface::config::CompilerOutput;
 use std::{
     collections::BTreeMap,
@@ -11,6 +18,8 @@ use std::{
 struct CombinedJson {
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     contracts: BTreeMap<String, CombinedJsonContract>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    sources: BTreeMap<String, CombinedJsonSource>,
     version: &'static str,
 }
 
@@ -20,31 +29,114 @@ struct CombinedJsonContract {
     abi: Option<Abi>,
     #[serde(skip_serializing_if = "Option::is_none")]
     hashes: Option<Hashes>,
+    #[serde(rename = "method-identifiers", skip_serializing_if = "Option::is_none")]
+    method_identifiers: Option<MethodIdentifiers>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constants: Option<Constants>,
+    #[serde(rename = "storage-layout", skip_serializing_if = "Option::is_none")]
+    storage_layout: Option<StorageLayout>,
+    #[serde(rename = "transient-storage-layout", skip_serializing_if = "Option::is_none")]
+    transient_storage_layout: Option<StorageLayout>,
+    /// `null` if the contract is not in the storage layout base.
+    #[serde(rename = "storage-layout-diff", skip_serializing_if = "Option::is_none")]
+    storage_layout_diff: Option<Option<Vec<LayoutChange>>>,
+}
+
+#[derive(Default, Serialize)]
+struct CombinedJsonSource {
+    #[serde(rename = "min-version", skip_serializing_if = "Option::is_none")]
+    min_version: Option<String>,
+    /// The file-level constants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constants: Option<Constants>,
+}
+
+#[derive(Serialize)]
+struct Constant {
+    #[serde(rename = "type")]
+    ty: String,
+    value: String,
+}
+
+/// The selectors of the functions, events and errors of a contract, by signature.
+#[derive(Serialize)]
+struct Hashes {
+    /// 4-byte error selectors.
+    errors: BTreeMap<String, String>,
+    /// 32-byte event topics.
+    events: BTreeMap<String, String>,
+    /// 4-byte function selectors.
+    methods: MethodIdentifiers,
 }
 
 type Abi = Vec<alloy_json_abi::AbiItem<'static>>;
-type Hashes = BTreeMap<String, String>;
+type MethodIdentifiers = BTreeMap<String, String>;
+type Constants = BTreeMap<String, Constant>;
 
-pub(crate) fn emit(gcx: Gcx<'_>) {
-    let mut output =
-        CombinedJson { contracts: Default::default(), version: solar_interface::VERSION };
+pub(crate) fn emit(gcx: Gcx<'_>, layout_changes: &BTreeMap<String, Option<Vec<LayoutChange>>>) {
+    let mut output = CombinedJson {
+        contracts: Default::default(),
+        sources: Default::default(),
+        version: solar_interface::VERSION,
+    };
     for id in gcx.hir.contract_ids() {
         let name = gcx.contract_fully_qualified_name(id).to_string();
-        let contract_output = output.contracts.entry(name).or_default();
+        let contract_output = output.contracts.entry(name.clone()).or_default();
         for &emit in &gcx.sess.opts.emit {
             match emit {
                 CompilerOutput::Abi => contract_output.abi = Some(gcx.contract_abi(id)),
                 CompilerOutput::Hashes => {
-                    let mut hashes = Hashes::default();
-                    for f in gcx.interface_functions(id) {
-                        hashes.insert(
-                            gcx.item_signature(f.id.into()).to_string(),
-                            alloy_primitives::hex::encode(f.selector),
-                        );
-                    }
-                    contract_output.hashes = Some(hashes);
+                    let (events, errors) = gcx.interface_events_and_errors(id);
+                    let signature = |id: hir::ItemId| gcx.item_signature(id).to_string();
+                    contract_output.hashes = Some(Hashes {
+                        errors: errors
+                            .into_iter()
+                            .map(|id| {
+                                (signature(id.into()), hex::encode(gcx.function_selector(id)))
+                            })
+                            .collect(),
+                        events: events
+                            .into_iter()
+                            .map(|id| (signature(id.into()), hex::encode(gcx.event_selector(id))))
+                            .collect(),
+                        methods: method_identifiers(gcx, id),
+                    });
+                }
+                CompilerOutput::MethodIdentifiers => {
+                    contract_output.method_identifiers = Some(method_identifiers(gcx, id));
+                }
+                CompilerOutput::Constants => {
+                    contract_output.constants =
+                        Some(constants(gcx, gcx.hir.contract(id).variables()));
+                }
+                CompilerOutput::MinVersion => {}
+                CompilerOutput::StorageLayout => {
+                    contract_output.storage_layout = Some(StorageLayout::new(gcx, id));
+                    contract_output.transient_storage_layout = StorageLayout::transient(gcx, id);
                 }
+                CompilerOutput::StorageLayoutDiff => {
+                    contract_output.storage_layout_diff = layout_changes.get(&name).cloned();
+                }
+            }
+        }
+    }
+    if gcx.sess.do_emit(CompilerOutput::Constants) {
+        for source in gcx.hir.sources() {
+            let variables = source.items.iter().filter_map(hir::ItemId::as_variable);
+            let constants = constants(gcx, variables);
+            if constants.is_empty() {
+                continue;
             }
+            let name = source.file.name.display().to_string();
+            output.sources.entry(name).or_default().constants = Some(constants);
+        }
+    }
+    if gcx.sess.do_emit(CompilerOutput::MinVersion) {
+        for source in gcx.hir.sources() {
+            let Some(min_version) = &source.min_version else { continue };
+            let name = source.file.name.display().to_string();
+            let source_output = output.sources.entry(name).or_default();
+            source_output.min_version = Some(min_version.to_string());
         }
     }
     let _ = (|| {
@@ -57,6 +149,40 @@ pub(crate) fn emit(gcx: Gcx<'_>) {
     .map_err(|e| gcx.dcx().err(format!("failed to write to output: {e}")).emit());
 }
 
+/// Returns the selectors of the functions in the interface of the given contract, by signature.
+fn method_identifiers(gcx: Gcx<'_>, id: hir::ContractId) -> MethodIdentifiers {
+    gcx.interface_functions(id)
+        .iter()
+        .map(|f| (gcx.item_signature(f.id.into()).to_string(), hex::encode(f.selector)))
+        .collect()
+}
+
+/// Returns the values of the given variables that are constants evaluated at compile time, by
+/// name.
+fn constants(gcx: Gcx<'_>, variables: impl Iterator<Item = hir::VariableId>) -> Constants {
+    let mut constants = Constants::default();
+    for id in variables {
+        let var = gcx.hir.variable(id);
+        let (Some(name), Some(value)) = (var.name, gcx.constant_value(id)) else { continue };
+        let ty = gcx.type_of_item(id.into()).peel_refs();
+        let value = json_value(value, ty);
+        constants.insert(name.to_string(), Constant { ty: ty.display(gcx).to_string(), value });
+    }
+    constants
+}
+
+/// Formats a constant value of the given type for JSON output. `string` values are written as
+/// text, and `bytes` values in hex.
+fn json_value(value: &ConstValue, ty: Ty<'_>) -> String {
+    match value {
+        ConstValue::Str(bytes) if matches!(ty.kind, TyKind::Elementary(ElementaryType::String)) => {
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+        ConstValue::Str(bytes) => hex::encode_prefixed(bytes),
+        value => value.to_string(),
+    }
+}
+
 fn out_writer(path: Option<&Path>) -> io::Result<impl io::Write> {
     let out: Box<dyn io::Write> = if let Some(path) = path {
         Box::new(std::fs::File::create(path)?)
@@ -1,25 +1,42 @@
-use crate::{hir, ty::Gcx};
-use alloy_primitives::U256;
+use crate::{
+    builtins::Builtin,
+    hir::{self, BinOpKind, ElementaryType, Res, UnOpKind, Visit},
+    ty::{Gcx, Ty, TyKind},
+};
+use alloy_primitives::{hex, keccak256, Address, U256};
+use num_bigint::{BigInt, Sign};
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
 use solar_ast::LitKind;
-use solar_interface::{diagnostics::ErrorGuaranteed, Span};
-use std::fmt;
+use solar_data_structures::{
+    cycle::{CycleDetector, CycleDetectorResult},
+    map::{FxHashMap, FxHashSet},
+    Never,
+};
+use solar_interface::{diagnostics::ErrorGuaranteed, sym, Span};
+use std::{cell::RefCell, cmp::Ordering, fmt, ops::ControlFlow, sync::Arc};
 
 const RECURSION_LIMIT: usize = 64;
 
-// TODO: `convertType` for truncating and extending correctly: https://github.com/ethereum/solidity/blob/de1a017ccb935d149ed6bcbdb730d89883f8ce02/libsolidity/analysis/ConstantEvaluator.cpp#L234
+/// The maximum number of bits of the numerator and denominator of rational values.
+///
+/// Same as the limit on `RationalNumberType` in solc.
+const MAX_RATIONAL_BITS: u64 = 4096;
 
 /// Evaluates simple constants.
 ///
-/// This only supports basic arithmetic and logical operations, and does not support more complex
-/// operations like function calls or memory allocation.
+/// This supports arithmetic, bitwise and logical operations on number literals, integers, fixed
+/// bytes, addresses and booleans, explicit type conversions, `type(T).min/max`, and `keccak256` of
+/// literals. Arithmetic on number literals is exact, like in solc.
 ///
-/// This is only supposed to be used for array sizes and other simple constants.
+/// This is only supposed to be used for array sizes and the initializers of constant variables,
+/// and does not support more complex operations like function calls or memory allocation.
 pub struct ConstantEvaluator<'gcx> {
     pub gcx: Gcx<'gcx>,
     depth: usize,
 }
 
-type EvalResult<'gcx> = Result<IntScalar, EvalError>;
+type EvalResult = Result<ConstValue, EvalError>;
 
 impl<'gcx> ConstantEvaluator<'gcx> {
     /// Creates a new constant evaluator.
@@ -28,12 +45,20 @@ impl<'gcx> ConstantEvaluator<'gcx> {
     }
 
     /// Evaluates the given expression, emitting an error diagnostic if it fails.
-    pub fn eval(&mut self, expr: &hir::Expr<'_>) -> Result<IntScalar, ErrorGuaranteed> {
+    pub fn eval(&mut self, expr: &hir::Expr<'_>) -> Result<ConstValue, ErrorGuaranteed> {
         self.try_eval(expr).map_err(|err| self.emit_eval_error(expr, err))
     }
 
+    /// Evaluates the given expression to an unsigned 256-bit integer, such as an array length,
+    /// emitting an error diagnostic if it fails.
+    pub fn eval_u256(&mut self, expr: &hir::Expr<'_>) -> Result<U256, ErrorGuaranteed> {
+        self.try_eval(expr)
+            .and_then(|value| value.to_u256().map_err(|kind| kind.spanned(expr.span)))
+            .map_err(|err| self.emit_eval_error(expr, err))
+    }
+
     /// Evaluates the given expression, returning an error if it fails.
-    pub fn try_eval(&mut self, expr: &hir::Expr<'_>) -> EvalResult<'gcx> {
+    pub fn try_eval(&mut self, expr: &hir::Expr<'_>) -> EvalResult {
         self.depth += 1;
         if self.depth > RECURSION_LIMIT {
             return Err(EE::RecursionLimitReached.spanned(expr.span));
@@ -48,6 +73,24 @@ impl<'gcx> ConstantEvaluator<'gcx> {
         res
     }
 
+    /// Evaluates the value of the given constant variable, converted to its declared type.
+    pub fn try_eval_variable(&mut self, id: hir::VariableId) -> EvalResult {
+        let v = self.gcx.hir.variable(id);
+        if !v.is_constant() {
+            return Err(EE::NonConstantVar.into());
+        }
+        if let Some(&guar) = self.gcx.initializer_cycles(()).get(&id) {
+            return Err(EE::AlreadyEmitted(guar).into());
+        }
+        let init = v.initializer.expect("constant variable has no initializer");
+        let value = self.try_eval(init)?;
+        let ty = self.gcx.type_of_item(id.into());
+        if let Err(guar) = ty.has_error() {
+            return Err(EE::AlreadyEmitted(guar).into());
+        }
+        value.convert(ty, false).map_err(|kind| kind.spanned(init.span))
+    }
+
     /// Emits a diagnostic for the given evaluation error.
     pub fn emit_eval_error(&self, expr: &hir::Expr<'_>, err: EvalError) -> ErrorGuaranteed {
         match err.kind {
@@ -59,7 +102,7 @@ impl<'gcx> ConstantEvaluator<'gcx> {
         }
     }
 
-    fn eval_expr(&mut self, expr: &hir::Expr<'_>) -> EvalResult<'gcx> {
+    fn eval_expr(&mut self, expr: &hir::Expr<'_>) -> EvalResult {
         let expr = expr.peel_parens();
         match expr.kind {
             // hir::ExprKind::Array(_) => unimplemented!(),
@@ -69,33 +112,71 @@ impl<'gcx> ConstantEvaluator<'gcx> {
                 let r = self.try_eval(r)?;
                 l.binop(&r, bin_op.kind).map_err(Into::into)
             }
-            // hir::ExprKind::Call(_, _) => unimplemented!(),
-            // hir::ExprKind::CallOptions(_, _) => unimplemented!(),
+            hir::ExprKind::Call(callee, ref args, None) => {
+                let [arg] = args.exprs().collect::<Vec<_>>()[..] else {
+                    return Err(EE::UnsupportedExpr.into());
+                };
+                match callee.peel_parens().kind {
+                    // Explicit type conversion: `uint8(x)`.
+                    hir::ExprKind::Type(ref ty) => {
+                        let ty = self.gcx.type_of_hir_ty(ty);
+                        let value = self.try_eval(arg)?;
+                        value.convert(ty, true).map_err(Into::into)
+                    }
+                    // `keccak256` of a literal.
+                    hir::ExprKind::Ident(&[Res::Builtin(Builtin::Keccak256)]) => {
+                        match self.try_eval(arg)? {
+                            ConstValue::Str(bytes) => {
+                                Ok(ConstValue::FixedBytes(keccak256(&bytes).to_vec()))
+                            }
+                            _ => Err(EE::UnsupportedExpr.into()),
+                        }
+                    }
+                    _ => Err(EE::UnsupportedExpr.into()),
+                }
+            }
             // hir::ExprKind::Delete(_) => unimplemented!(),
             hir::ExprKind::Ident(res) => {
                 // Ignore invalid overloads since they will get correctly detected later.
-                let Some(v) = res.iter().find_map(|res| res.as_variable()) else {
+                let Some(id) = res.iter().find_map(|res| res.as_variable()) else {
                     return Err(EE::NonConstantVar.into());
                 };
 
-                let v = self.gcx.hir.variable(v);
-                if v.mutability != Some(hir::VarMut::Constant) {
-                    return Err(EE::NonConstantVar.into());
-                }
-                self.try_eval(v.initializer.expect("constant variable has no initializer"))
+                self.try_eval_variable(id)
             }
             // hir::ExprKind::Index(_, _) => unimplemented!(),
             // hir::ExprKind::Slice(_, _, _) => unimplemented!(),
             hir::ExprKind::Lit(lit) => self.eval_lit(lit),
-            // hir::ExprKind::Member(_, _) => unimplemented!(),
+            // `type(T).min` and `type(T).max`.
+            hir::ExprKind::Member(base, member) => {
+                let hir::ExprKind::TypeCall(ref ty) = base.peel_parens().kind else {
+                    return Err(EE::UnsupportedExpr.into());
+                };
+                let ty = self.gcx.type_of_hir_ty(ty);
+                let TyKind::Elementary(ElementaryType::Int(size) | ElementaryType::UInt(size)) =
+                    ty.kind
+                else {
+                    return Err(EE::UnsupportedExpr.into());
+                };
+                let signed = matches!(ty.kind, TyKind::Elementary(ElementaryType::Int(_)));
+                let (min, max) = int_bounds(size.bits(), signed);
+                let value = match member.name {
+                    sym::min => min,
+                    sym::max => max,
+                    _ => return Err(EE::UnsupportedExpr.into()),
+                };
+                Ok(ConstValue::Int { value, bits: size.bits(), signed })
+            }
             // hir::ExprKind::New(_) => unimplemented!(),
             // hir::ExprKind::Payable(_) => unimplemented!(),
             hir::ExprKind::Ternary(cond, t, f) => {
                 let cond = self.try_eval(cond)?;
-                Ok(if cond.to_bool() { self.try_eval(t)? } else { self.try_eval(f)? })
+                let ConstValue::Bool(cond) = cond else {
+                    return Err(EE::TypeMismatch.into());
+                };
+                Ok(if cond { self.try_eval(t)? } else { self.try_eval(f)? })
             }
             // hir::ExprKind::Tuple(_) => unimplemented!(),
-            // hir::ExprKind::TypeCall(_) => unimplemented!(),
             // hir::ExprKind::Type(_) => unimplemented!(),
             hir::ExprKind::Unary(un_op, v) => {
                 let v = self.try_eval(v)?;
@@ -106,103 +187,579 @@ impl<'gcx> ConstantEvaluator<'gcx> {
         }
     }
 
-    fn eval_lit(&mut self, lit: &hir::Lit) -> EvalResult<'gcx> {
+    fn eval_lit(&mut self, lit: &hir::Lit) -> EvalResult {
         match lit.kind {
-            // LitKind::Str(str_kind, arc) => todo!(),
+            LitKind::Str(_, ref bytes) => Ok(ConstValue::Str(bytes.clone())),
             LitKind::Number(ref big_int) => {
-                let (_, bytes) = big_int.to_bytes_be();
-                if bytes.len() > 32 {
-                    return Err(EE::IntTooBig.into());
-                }
-                Ok(IntScalar::from_be_bytes(&bytes))
+                Ok(ConstValue::Rational(BigRational::from_integer(big_int.clone())))
             }
-            // LitKind::Rational(ratio) => todo!(),
-            LitKind::Address(address) => Ok(IntScalar::from_be_bytes(address.as_slice())),
-            LitKind::Bool(bool) => Ok(IntScalar::from_be_bytes(&[bool as u8])),
+            LitKind::Rational(ref ratio) => Ok(ConstValue::Rational(ratio.clone())),
+            LitKind::Address(address) => Ok(ConstValue::Address(address)),
+            LitKind::Bool(bool) => Ok(ConstValue::Bool(bool)),
             LitKind::Err(guar) => Err(EE::AlreadyEmitted(guar).into()),
-            _ => Err(EE::UnsupportedLiteral.into()),
         }
     }
 }
 
-pub struct IntScalar {
-    pub data: U256,
+/// Detects cyclic dependencies between the initializers of constant and immutable variables,
+/// emitting an error for each cycle.
+///
+/// Returns the variables that are part of a cycle.
+pub(crate) fn initializer_cycles(gcx: Gcx<'_>) -> FxHashMap<hir::VariableId, ErrorGuaranteed> {
+    type Cx<'a, 'gcx> = (Gcx<'gcx>, &'a RefCell<Vec<hir::VariableId>>);
+
+    let path = RefCell::new(Vec::new());
+    let mut reported = FxHashSet::<Vec<hir::VariableId>>::default();
+    let mut in_cycle = FxHashMap::default();
+    let mut cd = CycleDetector::new((gcx, &path), visit_initializer);
+    for id in gcx.hir.variable_ids() {
+        let CycleDetectorResult::Break(mut cycle) = cd.run(id) else { continue };
+        cd = CycleDetector::new((gcx, &path), visit_initializer);
+        path.borrow_mut().clear();
+
+        // Start from the first declared variable so that each cycle is reported the same way.
+        let start = (0..cycle.len()).min_by_key(|&i| gcx.hir.variable(cycle[i]).span.lo()).unwrap();
+        cycle.rotate_left(start);
+        let mut key = cycle.clone();
+        key.sort_unstable();
+        if !reported.insert(key) {
+            continue;
+        }
+
+        let name = |id: hir::VariableId| gcx.hir.variable(id).name.unwrap();
+        let first = gcx.hir.variable(cycle[0]);
+        let msg = format!("cycle detected when evaluating the initializer of `{}`", name(cycle[0]));
+        let mut err = gcx.dcx().err(msg).span(first.span);
+        for &id in &cycle[1..] {
+            let note = format!("...which requires evaluating the initializer of `{}`...", name(id));
+            err = err.span_note(gcx.hir.variable(id).span, note);
+        }
+        err = err.note(format!(
+            "...which again requires evaluating the initializer of `{}`, completing the cycle",
+            name(cycle[0])
+        ));
+        let guar = err.emit();
+        in_cycle.extend(cycle.into_iter().map(|id| (id, guar)));
+    }
+    return in_cycle;
+
+    fn visit_initializer(
+        (gcx, path): Cx<'_, '_>,
+        cd: &mut CycleDetector<Cx<'_, '_>, hir::VariableId, Vec<hir::VariableId>>,
+        id: hir::VariableId,
+    ) -> CycleDetectorResult<Vec<hir::VariableId>, hir::VariableId> {
+        let Some(init) = initializer(gcx, id) else { return CycleDetectorResult::Continue };
+        let mut deps = VariableRefs { gcx, vars: Vec::new() };
+        let _ = deps.visit_expr(init);
+
+        path.borrow_mut().push(id);
+        for dep in deps.vars {
+            if initializer(gcx, dep).is_none() {
+                continue;
+            }
+            match cd.run(dep) {
+                CycleDetectorResult::Continue => {}
+                CycleDetectorResult::Cycle(first) => {
+                    let path = path.borrow();
+                    let start = path.iter().position(|&id| id == first).unwrap();
+                    return CycleDetectorResult::Break(path[start..].to_vec());
+                }
+                r => return r,
+            }
+        }
+        path.borrow_mut().pop();
+        CycleDetectorResult::Continue
+    }
+
+    /// Returns the initializer of the given variable if it is constant or immutable.
+    fn initializer<'gcx>(gcx: Gcx<'gcx>, id: hir::VariableId) -> Option<&'gcx hir::Expr<'gcx>> {
+        let v = gcx.hir.variable(id);
+        if !(v.is_constant() || v.is_immutable()) {
+            return None;
+        }
+        v.initializer
+    }
 }
 
-impl IntScalar {
-    pub fn new(data: U256) -> Self {
-        Self { data }
+/// Collects the variables that are referenced in an expression.
+struct VariableRefs<'gcx> {
+    gcx: Gcx<'gcx>,
+    vars: Vec<hir::VariableId>,
+}
+
+impl<'gcx> Visit<'gcx> for VariableRefs<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
     }
 
-    /// Creates a new integer value from a boolean.
-    pub fn from_bool(value: bool) -> Self {
-        Self { data: U256::from(value as u8) }
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        if let hir::ExprKind::Ident(res) = expr.kind {
+            self.vars.extend(res.iter().filter_map(|res| res.as_variable()));
+        }
+        self.walk_expr(expr)
     }
+}
 
-    /// Creates a new integer value from big-endian bytes.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `bytes` is empty or has a length greater than 32.
-    pub fn from_be_bytes(bytes: &[u8]) -> Self {
-        Self { data: U256::from_be_slice(bytes) }
+/// A constant value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConstValue {
+    /// A number literal, or the exact result of an operation on number literals.
+    Rational(BigRational),
+    /// An integer of the given type.
+    Int {
+        /// The value. Always in the range of the type.
+        value: BigInt,
+        /// The number of bits of the type.
+        bits: u16,
+        /// Whether the type is signed.
+        signed: bool,
+    },
+    /// A fixed-size byte array, `bytesN`.
+    FixedBytes(Vec<u8>),
+    /// An address.
+    Address(Address),
+    /// A boolean.
+    Bool(bool),
+    /// A string or hex string literal, or a `string` or `bytes` constant.
+    Str(Arc<[u8]>),
+}
+
+impl ConstValue {
+    /// Converts the value to an unsigned 256-bit integer.
+    pub fn to_u256(&self) -> Result<U256, EE> {
+        let value = match self {
+            Self::Rational(r) if r.is_integer() => r.to_integer(),
+            Self::Int { value, .. } => value.clone(),
+            Self::Rational(_) => return Err(EE::NotAnInteger),
+            _ => return Err(EE::TypeMismatch),
+        };
+        if value.is_negative() {
+            return Err(EE::NegativeValue);
+        }
+        let (_, bytes) = value.to_bytes_be();
+        if bytes.len() > 32 {
+            return Err(EE::IntTooBig);
+        }
+        Ok(U256::from_be_slice(&bytes))
     }
 
-    /// Converts the integer value to a boolean.
-    pub fn to_bool(&self) -> bool {
-        !self.data.is_zero()
+    /// Returns the value as an integer, if it is an integer or a number literal without a
+    /// fractional part.
+    pub fn as_integer(&self) -> Option<BigInt> {
+        match self {
+            Self::Rational(r) if r.is_integer() => Some(r.to_integer()),
+            Self::Int { value, .. } => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Converts the value to the given type.
+    ///
+    /// Explicit conversions truncate integers and fixed bytes like at runtime, while implicit
+    /// conversions fail if the value doesn't fit in the type.
+    pub fn convert(self, ty: Ty<'_>, explicit: bool) -> Result<Self, EE> {
+        let ty = ty.peel_refs();
+        let TyKind::Elementary(ty) = ty.kind else {
+            return match ty.kind {
+                TyKind::Udvt(inner, _) => self.convert(inner, explicit),
+                _ => Err(EE::UnsupportedConversion),
+            };
+        };
+        Ok(match (ty, self) {
+            (ElementaryType::Int(size) | ElementaryType::UInt(size), value) => {
+                let signed = matches!(ty, ElementaryType::Int(_));
+                let bits = size.bits();
+                let value = match value {
+                    Self::Rational(r) if r.is_integer() => {
+                        let value = r.to_integer();
+                        if !explicit && !int_fits(&value, bits, signed) {
+                            return Err(EE::IntTooBig);
+                        }
+                        value
+                    }
+                    Self::Rational(_) => return Err(EE::NotAnInteger),
+                    Self::Int { value, .. } => value,
+                    Self::FixedBytes(bytes) => BigInt::from_bytes_be(Sign::Plus, &bytes),
+                    Self::Address(address) => BigInt::from_bytes_be(Sign::Plus, address.as_slice()),
+                    Self::Bool(_) | Self::Str(_) => return Err(EE::UnsupportedConversion),
+                };
+                Self::Int { value: wrap_int(&value, bits, signed), bits, signed }
+            }
+            (ElementaryType::FixedBytes(size), value) => {
+                let len = size.bytes() as usize;
+                let bytes = match value {
+                    // Fixed bytes and literals are aligned to the left.
+                    Self::FixedBytes(mut bytes) => {
+                        bytes.resize(len, 0);
+                        bytes
+                    }
+                    Self::Str(bytes) => {
+                        if !explicit && bytes.len() > len {
+                            return Err(EE::IntTooBig);
+                        }
+                        let mut bytes = bytes.to_vec();
+                        bytes.resize(len, 0);
+                        bytes
+                    }
+                    // Integers are aligned to the right.
+                    value @ (Self::Rational(_) | Self::Int { .. }) => {
+                        let Some(value) = value.as_integer() else {
+                            return Err(EE::NotAnInteger);
+                        };
+                        int_to_be_bytes(&value, len)
+                    }
+                    Self::Address(_) | Self::Bool(_) => return Err(EE::UnsupportedConversion),
+                };
+                Self::FixedBytes(bytes)
+            }
+            (ElementaryType::Address(_), value) => match value {
+                Self::Address(address) => Self::Address(address),
+                Self::FixedBytes(bytes) => Self::Address(Address::from_slice(&int_to_be_bytes(
+                    &BigInt::from_bytes_be(Sign::Plus, &bytes),
+                    20,
+                ))),
+                value @ (Self::Rational(_) | Self::Int { .. }) => {
+                    let Some(value) = value.as_integer() else {
+                        return Err(EE::NotAnInteger);
+                    };
+                    Self::Address(Address::from_slice(&int_to_be_bytes(&value, 20)))
+                }
+                Self::Bool(_) | Self::Str(_) => return Err(EE::UnsupportedConversion),
+            },
+            (ElementaryType::Bool, value @ Self::Bool(_)) => value,
+            (ElementaryType::String | ElementaryType::Bytes, value @ Self::Str(_)) => value,
+            _ => return Err(EE::UnsupportedConversion),
+        })
     }
 
     /// Applies the given unary operation to this value.
-    pub fn unop(&self, op: hir::UnOpKind) -> Result<Self, EE> {
-        Ok(match op {
-            hir::UnOpKind::PreInc
-            | hir::UnOpKind::PreDec
-            | hir::UnOpKind::PostInc
-            | hir::UnOpKind::PostDec => return Err(EE::UnsupportedUnaryOp),
-            hir::UnOpKind::Not | hir::UnOpKind::BitNot => Self::new(!self.data),
-            hir::UnOpKind::Neg => Self::new(self.data.wrapping_neg()),
+    pub fn unop(&self, op: UnOpKind) -> Result<Self, EE> {
+        Ok(match (op, self) {
+            (UnOpKind::PreInc | UnOpKind::PreDec | UnOpKind::PostInc | UnOpKind::PostDec, _) => {
+                return Err(EE::UnsupportedUnaryOp);
+            }
+            (UnOpKind::Not, Self::Bool(b)) => Self::Bool(!b),
+            (UnOpKind::Neg, Self::Rational(r)) => Self::Rational(-r),
+            (UnOpKind::Neg, &Self::Int { ref value, bits, signed }) => {
+                checked_int(-value, bits, signed)?
+            }
+            // Bitwise negation on literals uses two's complement with infinite precision.
+            (UnOpKind::BitNot, Self::Rational(r)) if r.is_integer() => {
+                Self::Rational(BigRational::from_integer(!r.to_integer()))
+            }
+            (UnOpKind::BitNot, &Self::Int { ref value, bits, signed }) => {
+                Self::Int { value: wrap_int(&!value, bits, signed), bits, signed }
+            }
+            (UnOpKind::BitNot, Self::FixedBytes(bytes)) => {
+                Self::FixedBytes(bytes.iter().map(|b| !b).collect())
+            }
+            _ => return Err(EE::UnsupportedUnaryOp),
         })
     }
 
     /// Applies the given binary operation to this value.
-    pub fn binop(&self, r: &Self, op: hir::BinOpKind) -> Result<Self, EE> {
+    pub fn binop(&self, r: &Self, op: BinOpKind) -> Result<Self, EE> {
         let l = self;
-        Ok(match op {
-            hir::BinOpKind::Lt => Self::from_bool(l.data < r.data),
-            hir::BinOpKind::Le => Self::from_bool(l.data <= r.data),
-            hir::BinOpKind::Gt => Self::from_bool(l.data > r.data),
-            hir::BinOpKind::Ge => Self::from_bool(l.data >= r.data),
-            hir::BinOpKind::Eq => Self::from_bool(l.data == r.data),
-            hir::BinOpKind::Ne => Self::from_bool(l.data != r.data),
-            hir::BinOpKind::Or | hir::BinOpKind::BitOr => Self::new(l.data | r.data),
-            hir::BinOpKind::And | hir::BinOpKind::BitAnd => Self::new(l.data & r.data),
-            hir::BinOpKind::BitXor => Self::new(l.data ^ r.data),
-            hir::BinOpKind::Shr => {
-                Self::new(l.data.wrapping_shr(r.data.try_into().unwrap_or(usize::MAX)))
-            }
-            hir::BinOpKind::Shl => {
-                Self::new(l.data.wrapping_shl(r.data.try_into().unwrap_or(usize::MAX)))
-            }
-            hir::BinOpKind::Sar => {
-                Self::new(l.data.arithmetic_shr(r.data.try_into().unwrap_or(usize::MAX)))
-            }
-            hir::BinOpKind::Add => {
-                Self::new(l.data.checked_add(r.data).ok_or(EE::ArithmeticOverflow)?)
-            }
-            hir::BinOpKind::Sub => {
-                Self::new(l.data.checked_sub(r.data).ok_or(EE::ArithmeticOverflow)?)
-            }
-            hir::BinOpKind::Pow => {
-                Self::new(l.data.checked_pow(r.data).ok_or(EE::ArithmeticOverflow)?)
-            }
-            hir::BinOpKind::Mul => {
-                Self::new(l.data.checked_mul(r.data).ok_or(EE::ArithmeticOverflow)?)
-            }
-            hir::BinOpKind::Div => Self::new(l.data.checked_div(r.data).ok_or(EE::DivisionByZero)?),
-            hir::BinOpKind::Rem => Self::new(l.data.checked_rem(r.data).ok_or(EE::DivisionByZero)?),
-        })
+        match (l, r) {
+            (Self::Rational(l), Self::Rational(r)) => rational_binop(l, r, op),
+
+            // The right operand of shifts and exponentiations can have any integer type.
+            (&Self::Int { ref value, bits, signed }, r)
+                if matches!(
+                    op,
+                    BinOpKind::Shl | BinOpKind::Shr | BinOpKind::Sar | BinOpKind::Pow
+                ) =>
+            {
+                let r = r.as_integer().ok_or(EE::TypeMismatch)?;
+                int_binop(value, &r, op, bits, signed)
+            }
+            (&Self::Int { ref value, bits, signed }, Self::Int { value: r, bits: r_bits, .. }) => {
+                int_binop(value, r, op, bits.max(*r_bits), signed)
+            }
+            (&Self::Int { bits, signed, .. }, Self::Rational(_)) => {
+                let r = r.clone().convert_int(bits, signed)?;
+                l.binop(&r, op)
+            }
+            (Self::Rational(_), &Self::Int { bits, signed, .. }) => {
+                let l = l.clone().convert_int(bits, signed)?;
+                l.binop(r, op)
+            }
+
+            (Self::Bool(l), Self::Bool(r)) => Ok(Self::Bool(match op {
+                BinOpKind::And => *l && *r,
+                BinOpKind::Or => *l || *r,
+                BinOpKind::Eq => l == r,
+                BinOpKind::Ne => l != r,
+                _ => return Err(EE::UnsupportedBinaryOp),
+            })),
+
+            (Self::FixedBytes(l), Self::FixedBytes(r)) if l.len() == r.len() => {
+                let bitwise = |f: fn(u8, u8) -> u8| {
+                    Self::FixedBytes(l.iter().zip(r).map(|(&l, &r)| f(l, r)).collect())
+                };
+                Ok(match op {
+                    BinOpKind::BitAnd => bitwise(|l, r| l & r),
+                    BinOpKind::BitOr => bitwise(|l, r| l | r),
+                    BinOpKind::BitXor => bitwise(|l, r| l ^ r),
+                    _ => Self::Bool(compare(l.cmp(r), op)?),
+                })
+            }
+            (Self::FixedBytes(l), r)
+                if matches!(op, BinOpKind::Shl | BinOpKind::Shr | BinOpKind::Sar) =>
+            {
+                let value = BigInt::from_bytes_be(Sign::Plus, l);
+                let bits = l.len() as u16 * 8;
+                let r = r.as_integer().ok_or(EE::TypeMismatch)?;
+                let Self::Int { value, .. } = int_binop(&value, &r, op, bits, false)? else {
+                    unreachable!()
+                };
+                Ok(Self::FixedBytes(int_to_be_bytes(&value, l.len())))
+            }
+
+            (Self::Address(l), Self::Address(r)) => Ok(Self::Bool(compare(l.cmp(r), op)?)),
+
+            _ => Err(EE::TypeMismatch),
+        }
+    }
+
+    /// Implicitly converts a number literal to an integer type.
+    fn convert_int(self, bits: u16, signed: bool) -> Result<Self, EE> {
+        let value = self.as_integer().ok_or(EE::NotAnInteger)?;
+        if !int_fits(&value, bits, signed) {
+            return Err(EE::ArithmeticOverflow);
+        }
+        Ok(Self::Int { value, bits, signed })
+    }
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rational(r) if r.is_integer() => r.numer().fmt(f),
+            Self::Rational(r) => write!(f, "{}/{}", r.numer(), r.denom()),
+            Self::Int { value, .. } => value.fmt(f),
+            Self::FixedBytes(bytes) => f.write_str(&hex::encode_prefixed(bytes)),
+            Self::Address(address) => address.fmt(f),
+            Self::Bool(b) => b.fmt(f),
+            Self::Str(bytes) => match std::str::from_utf8(bytes) {
+                Ok(s) => write!(f, "{s:?}"),
+                Err(_) => write!(f, "hex\"{}\"", hex::encode(bytes)),
+            },
+        }
+    }
+}
+
+fn rational_binop(l: &BigRational, r: &BigRational, op: BinOpKind) -> Result<ConstValue, EE> {
+    let integers = || match (l.is_integer(), r.is_integer()) {
+        (true, true) => Ok((l.to_integer(), r.to_integer())),
+        _ => Err(EE::NotAnInteger),
+    };
+    let value = match op {
+        BinOpKind::Lt
+        | BinOpKind::Le
+        | BinOpKind::Gt
+        | BinOpKind::Ge
+        | BinOpKind::Eq
+        | BinOpKind::Ne => return Ok(ConstValue::Bool(compare(l.cmp(r), op)?)),
+        BinOpKind::Or | BinOpKind::And => return Err(EE::UnsupportedBinaryOp),
+        BinOpKind::BitAnd => {
+            let (l, r) = integers()?;
+            BigRational::from_integer(l & r)
+        }
+        BinOpKind::BitOr => {
+            let (l, r) = integers()?;
+            BigRational::from_integer(l | r)
+        }
+        BinOpKind::BitXor => {
+            let (l, r) = integers()?;
+            BigRational::from_integer(l ^ r)
+        }
+        BinOpKind::Shl => {
+            let (l, r) = integers()?;
+            let shift = shift_amount(&r)?;
+            if l.bits() + shift > MAX_RATIONAL_BITS {
+                return Err(EE::IntTooBig);
+            }
+            BigRational::from_integer(l << shift)
+        }
+        BinOpKind::Shr | BinOpKind::Sar => {
+            let (l, r) = integers()?;
+            // Rounds towards negative infinity, like `sar`.
+            BigRational::from_integer(l >> shift_amount(&r)?.min(MAX_RATIONAL_BITS))
+        }
+        BinOpKind::Add => l + r,
+        BinOpKind::Sub => l - r,
+        BinOpKind::Mul => l * r,
+        BinOpKind::Div => {
+            if r.is_zero() {
+                return Err(EE::DivisionByZero);
+            }
+            l / r
+        }
+        BinOpKind::Rem => {
+            if r.is_zero() {
+                return Err(EE::DivisionByZero);
+            }
+            // The result has the sign of the dividend.
+            l - r * (l / r).trunc()
+        }
+        BinOpKind::Pow => {
+            if !r.is_integer() {
+                return Err(EE::NotAnInteger);
+            }
+            let exp = r.to_integer();
+            let abs_exp = exp.abs().to_u64().ok_or(EE::IntTooBig)?;
+            let max_bits = l.numer().bits().max(l.denom().bits());
+            if max_bits > 1
+                && max_bits.saturating_sub(1).saturating_mul(abs_exp) > MAX_RATIONAL_BITS
+            {
+                return Err(EE::IntTooBig);
+            }
+            if exp.is_negative() && l.is_zero() {
+                return Err(EE::DivisionByZero);
+            }
+            let pow = num_traits::pow(l.clone(), abs_exp as usize);
+            if exp.is_negative() {
+                pow.recip()
+            } else {
+                pow
+            }
+        }
+    };
+    if value.numer().bits() > MAX_RATIONAL_BITS || value.denom().bits() > MAX_RATIONAL_BITS {
+        return Err(EE::IntTooBig);
     }
+    Ok(ConstValue::Rational(value))
+}
+
+fn int_binop(
+    l: &BigInt,
+    r: &BigInt,
+    op: BinOpKind,
+    bits: u16,
+    signed: bool,
+) -> Result<ConstValue, EE> {
+    let value = match op {
+        BinOpKind::Lt
+        | BinOpKind::Le
+        | BinOpKind::Gt
+        | BinOpKind::Ge
+        | BinOpKind::Eq
+        | BinOpKind::Ne => return Ok(ConstValue::Bool(compare(l.cmp(r), op)?)),
+        BinOpKind::Or | BinOpKind::And => return Err(EE::UnsupportedBinaryOp),
+        BinOpKind::BitAnd => l & r,
+        BinOpKind::BitOr => l | r,
+        BinOpKind::BitXor => l ^ r,
+        // Shifts never overflow, the result is truncated.
+        BinOpKind::Shl => {
+            let shift = shift_amount(r)?.min(bits as u64);
+            return Ok(ConstValue::Int {
+                value: wrap_int(&(l << shift), bits, signed),
+                bits,
+                signed,
+            });
+        }
+        BinOpKind::Shr | BinOpKind::Sar => l >> shift_amount(r)?.min(bits as u64),
+        BinOpKind::Add => l + r,
+        BinOpKind::Sub => l - r,
+        BinOpKind::Mul => l * r,
+        BinOpKind::Div => {
+            if r.is_zero() {
+                return Err(EE::DivisionByZero);
+            }
+            l / r
+        }
+        BinOpKind::Rem => {
+            if r.is_zero() {
+                return Err(EE::DivisionByZero);
+            }
+            l % r
+        }
+        BinOpKind::Pow => {
+            if r.is_negative() {
+                return Err(EE::NegativeValue);
+            }
+            // Any exponent larger than the bit width overflows, unless the base is -1, 0 or 1.
+            let exp = if l.magnitude() <= &One::one() {
+                if r.is_zero() {
+                    0
+                } else if r.bit(0) {
+                    1
+                } else {
+                    2
+                }
+            } else {
+                r.to_u64().filter(|&exp| exp <= bits as u64).ok_or(EE::ArithmeticOverflow)?
+            };
+            num_traits::pow(l.clone(), exp as usize)
+        }
+    };
+    checked_int(value, bits, signed)
+}
+
+/// Returns the integer if it is in range of the type, or an overflow error otherwise.
+fn checked_int(value: BigInt, bits: u16, signed: bool) -> Result<ConstValue, EE> {
+    if !int_fits(&value, bits, signed) {
+        return Err(EE::ArithmeticOverflow);
+    }
+    Ok(ConstValue::Int { value, bits, signed })
+}
+
+fn compare(ordering: Ordering, op: BinOpKind) -> Result<bool, EE> {
+    Ok(match op {
+        BinOpKind::Lt => ordering.is_lt(),
+        BinOpKind::Le => ordering.is_le(),
+        BinOpKind::Gt => ordering.is_gt(),
+        BinOpKind::Ge => ordering.is_ge(),
+        BinOpKind::Eq => ordering.is_eq(),
+        BinOpKind::Ne => ordering.is_ne(),
+        _ => return Err(EE::UnsupportedBinaryOp),
+    })
+}
+
+fn shift_amount(r: &BigInt) -> Result<u64, EE> {
+    if r.is_negative() {
+        return Err(EE::NegativeValue);
+    }
+    Ok(r.to_u64().unwrap_or(u64::MAX))
+}
+
+/// Returns the minimum and maximum values of an integer type.
+fn int_bounds(bits: u16, signed: bool) -> (BigInt, BigInt) {
+    if signed {
+        let half = BigInt::one() << (bits - 1);
+        (-half.clone(), half - 1)
+    } else {
+        (BigInt::zero(), (BigInt::one() << bits) - 1)
+    }
+}
+
+fn int_fits(value: &BigInt, bits: u16, signed: bool) -> bool {
+    let (min, max) = int_bounds(bits, signed);
+    min <= *value && *value <= max
+}
+
+/// Truncates the integer to the given number of bits, interpreting the result as two's
+/// complement if `signed` is true.
+fn wrap_int(value: &BigInt, bits: u16, signed: bool) -> BigInt {
+    let modulus = BigInt::one() << bits;
+    let mut value = ((value % &modulus) + &modulus) % &modulus;
+    if signed && value.bit(bits as u64 - 1) {
+        value -= modulus;
+    }
+    value
+}
+
+/// Returns the two's complement big-endian representation of the integer, truncated or
+/// extended to `len` bytes.
+fn int_to_be_bytes(value: &BigInt, len: usize) -> Vec<u8> {
+    let value = wrap_int(value, len as u16 * 8, false);
+    let (_, bytes) = value.to_bytes_be();
+    let mut out = vec![0; len - bytes.len().min(len)];
+    out.extend_from_slice(&bytes[bytes.len().saturating_sub(len)..]);
+    out
 }
 
 #[derive(Debug)]
@@ -211,8 +768,12 @@ pub enum EvalErrorKind {
     ArithmeticOverflow,
     IntTooBig,
     DivisionByZero,
-    UnsupportedLiteral,
+    NotAnInteger,
+    NegativeValue,
+    TypeMismatch,
     UnsupportedUnaryOp,
+    UnsupportedBinaryOp,
+    UnsupportedConversion,
     UnsupportedExpr,
     NonConstantVar,
     AlreadyEmitted(ErrorGuaranteed),
@@ -230,8 +791,12 @@ impl EvalErrorKind {
             Self::ArithmeticOverflow => "arithmetic overflow",
             Self::IntTooBig => "integer value is too big",
             Self::DivisionByZero => "division by zero",
-            Self::UnsupportedLiteral => "unsupported literal",
+            Self::NotAnInteger => "value is not an integer",
+            Self::NegativeValue => "value is negative",
+            Self::TypeMismatch => "mismatched types",
             Self::UnsupportedUnaryOp => "unsupported unary operation",
+            Self::UnsupportedBinaryOp => "unsupported binary operation",
+            Self::UnsupportedConversion => "unsupported type conversion",
             Self::UnsupportedExpr => "unsupported expression",
             Self::NonConstantVar => "only constant variables are allowed",
             Self::AlreadyEmitted(_) => "error already emitted",
@@ -6,7 +6,7 @@ use either::Either;
 use rayon::prelude::*;
 use solar_ast as ast;
 use solar_data_structures::{index::IndexVec, newtype_index, BumpExt};
-use solar_interface::{diagnostics::ErrorGuaranteed, source_map::SourceFile, Ident, Span};
+use solar_interface::{diagnostics::ErrorGuaranteed, source_map::SourceFile, Ident, Span, Symbol};
 use std::{fmt, ops::ControlFlow, sync::Arc};
 use strum::EnumIs;
 
@@ -297,6 +297,12 @@ pub struct Source<'hir> {
     pub imports: &'hir [(ast::ItemId, SourceId)],
     /// The source items.
     pub items: &'hir [ItemId],
+    /// The file-level `using` directives.
+    pub usings: &'hir [UsingDirective<'hir>],
+    /// The minimum Solidity version required by the language features used in the source.
+    ///
+    /// `None` if the source could not be parsed.
+    pub min_version: Option<semver::Version>,
 }
 
 impl fmt::Debug for Source<'_> {
@@ -305,6 +311,8 @@ impl fmt::Debug for Source<'_> {
             .field("file", &self.file.name)
             .field("imports", &self.imports)
             .field("items", &self.items)
+            .field("usings", &self.usings)
+            .field("min_version", &self.min_version)
             .finish()
     }
 }
@@ -507,6 +515,15 @@ impl ItemId {
             None
         }
     }
+
+    /// Returns the struct ID if this is a struct.
+    pub fn as_struct(&self) -> Option<StructId> {
+        if let Self::Struct(v) = *self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 
 /// A contract, interface, or library.
@@ -522,6 +539,8 @@ pub struct Contract<'hir> {
     pub kind: ContractKind,
     /// The contract bases.
     pub bases: &'hir [ContractId],
+    /// The base constructor arguments given in the inheritance specifiers: `is B(1)`.
+    pub bases_args: &'hir [Modifier<'hir>],
     /// The linearized contract bases.
     pub linearized_bases: &'hir [ContractId],
     /// The constructor function.
@@ -535,6 +554,10 @@ pub struct Contract<'hir> {
     /// Note that this only includes items defined in the contract itself, not inherited items.
     /// For getting all items, use [`Hir::contract_items`].
     pub items: &'hir [ItemId],
+    /// The `using` directives declared in the contract.
+    ///
+    /// Like in solc, these are not inherited by derived contracts.
+    pub usings: &'hir [UsingDirective<'hir>],
 }
 
 impl Contract<'_> {
@@ -572,6 +595,18 @@ impl Contract<'_> {
     }
 }
 
+/// A modifier invocation, or a base constructor call in an inheritance specifier or constructor
+/// header.
+#[derive(Debug)]
+pub struct Modifier<'hir> {
+    /// The span of the invocation.
+    pub span: Span,
+    /// The modifier, or the base contract.
+    pub id: ItemId,
+    /// The arguments of the invocation.
+    pub args: CallArgs<'hir>,
+}
+
 /// A function.
 #[derive(Debug)]
 pub struct Function<'hir> {
@@ -591,7 +626,7 @@ pub struct Function<'hir> {
     /// The state mutability of the function.
     pub state_mutability: StateMutability,
     /// Modifiers, or base classes if this is a constructor.
-    pub modifiers: &'hir [ItemId],
+    pub modifiers: &'hir [Modifier<'hir>],
     /// Whether this function is marked with the `virtual` keyword.
     pub marked_virtual: bool,
     /// Whether this function is marked with the `virtual` keyword or is defined in an interface.
@@ -657,6 +692,8 @@ pub struct Struct<'hir> {
     /// The struct name.
     pub name: Ident,
     pub fields: &'hir [VariableId],
+    /// The ERC-7201 namespace ID, from a `@custom:storage-location erc7201:<id>` annotation.
+    pub namespace: Option<Symbol>,
 }
 
 /// An enum.
@@ -674,6 +711,40 @@ pub struct Enum<'hir> {
     pub variants: &'hir [Ident],
 }
 
+/// A `using` directive: `using { A, B.add as + } for uint256 global;`.
+#[derive(Debug)]
+pub struct UsingDirective<'hir> {
+    /// The directive span.
+    pub span: Span,
+    /// The attached functions.
+    pub list: UsingList<'hir>,
+    /// The type to which the functions are attached. This is `*` if the value is `None`.
+    pub ty: Option<Type<'hir>>,
+    /// Whether the directive applies to the type everywhere it is used, rather than only in the
+    /// current scope.
+    pub global: bool,
+}
+
+/// The functions attached by a [`UsingDirective`].
+#[derive(Clone, Copy, Debug)]
+pub enum UsingList<'hir> {
+    /// `using L for T`: all the non-private functions of the library `L`.
+    Library(ContractId),
+    /// `using { f, L.g as + } for T`: the given free or library functions.
+    Functions(&'hir [UsingFunction]),
+}
+
+/// A function in a [`UsingList::Functions`] list.
+#[derive(Clone, Copy, Debug)]
+pub struct UsingFunction {
+    /// The span of the function path.
+    pub span: Span,
+    /// The attached function.
+    pub id: FunctionId,
+    /// The operator that the function implements, if any.
+    pub operator: Option<ast::UserDefinableOperator>,
+}
+
 /// A user-defined value type.
 #[derive(Debug)]
 pub struct Udvt<'hir> {
@@ -808,6 +879,12 @@ impl<'hir> Variable<'hir> {
         self.mutability == Some(VarMut::Immutable)
     }
 
+    /// Returns `true` if the variable is stored in [`transient`](DataLocation::Transient)
+    /// storage.
+    pub fn is_transient(&self) -> bool {
+        self.data_location == Some(DataLocation::Transient)
+    }
+
     pub fn is_l_value(&self) -> bool {
         !self.is_constant()
     }
@@ -932,9 +1009,9 @@ pub struct Stmt<'hir> {
 /// A kind of statement.
 #[derive(Debug)]
 pub enum StmtKind<'hir> {
-    // TODO: Yul to HIR.
-    // /// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
-    // Assembly(StmtAssembly<'hir>),
+    /// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
+    Assembly(&'hir StmtAssembly<'hir>),
+
     /// A single-variable declaration statement: `uint256 foo = 42;`.
     DeclSingle(VariableId),
 
@@ -986,6 +1063,17 @@ pub enum StmtKind<'hir> {
     Err(ErrorGuaranteed),
 }
 
+/// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
+///
+/// The Yul code is not lowered yet; only the calls to the EVM builtins which access the state are
+/// recorded.
+#[derive(Debug)]
+pub struct StmtAssembly<'hir> {
+    /// The calls to the builtins which read or modify the state, with the state mutability that
+    /// they require.
+    pub state_accesses: &'hir [(StateMutability, Span)],
+}
+
 /// A try statement: `try fooBar(42) returns (...) { ... } catch (...) { ... }`.
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.tryStatement>
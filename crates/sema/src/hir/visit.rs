@@ -48,7 +48,11 @@ pub trait Visit<'hir> {
     }
 
     fn visit_contract(&mut self, contract: &'hir Contract<'hir>) -> ControlFlow<Self::BreakValue> {
-        // TODO: base initializers
+        for base in contract.bases_args {
+            for arg in base.args.exprs() {
+                self.visit_expr(arg)?;
+            }
+        }
         visit_nested_items(self, contract.items)
     }
 
@@ -57,7 +61,11 @@ pub trait Visit<'hir> {
     }
 
     fn visit_function(&mut self, func: &'hir Function<'hir>) -> ControlFlow<Self::BreakValue> {
-        // TODO: modifiers
+        for modifier in func.modifiers {
+            for arg in modifier.args.exprs() {
+                self.visit_expr(arg)?;
+            }
+        }
         if let Some(body) = func.body {
             for stmt in body {
                 self.visit_stmt(stmt)?;
@@ -80,8 +88,16 @@ pub trait Visit<'hir> {
 
     fn visit_expr(&mut self, expr: &'hir Expr<'hir>) -> ControlFlow<Self::BreakValue> {
         match expr.kind {
-            ExprKind::Call(expr, _, _)
-            | ExprKind::Delete(expr)
+            ExprKind::Call(callee, ref args, opts) => {
+                self.visit_expr(callee)?;
+                for opt in opts.into_iter().flatten() {
+                    self.visit_expr(&opt.value)?;
+                }
+                for arg in args.exprs() {
+                    self.visit_expr(arg)?;
+                }
+            }
+            ExprKind::Delete(expr)
             | ExprKind::Member(expr, _)
             | ExprKind::Payable(expr)
             | ExprKind::Unary(_, expr) => self.visit_expr(expr)?,
@@ -129,6 +145,7 @@ pub trait Visit<'hir> {
 
     fn visit_stmt(&mut self, stmt: &'hir Stmt<'hir>) -> ControlFlow<Self::BreakValue> {
         match stmt.kind {
+            StmtKind::Assembly(_) => {}
             StmtKind::DeclSingle(var) => self.visit_nested_var(var)?,
             StmtKind::DeclMulti(vars, expr) => {
                 for &var in vars {
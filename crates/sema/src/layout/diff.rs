@@ -0,0 +1,514 @@
+//! Comparison of storage layouts, to check that a deployed contract can be upgraded.
+
+use super::json::{StorageEntry, StorageLayout};
+use crate::{hir, ty::Gcx};
+use alloy_primitives::U512;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A storage layout change that is not compatible with upgrading a deployed contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutChange {
+    /// The kind of change.
+    pub kind: LayoutChangeKind,
+    /// The ERC-7201 namespace the variable is stored in, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// The struct member whose layout changed, for [`LayoutChangeKind::MemberChanged`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    /// The variable in the old layout. `None` for insertions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<StorageEntry>,
+    /// The variable in the new layout. `None` for removals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<StorageEntry>,
+}
+
+/// The kind of a [`LayoutChange`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LayoutChangeKind {
+    /// A variable was removed.
+    Removed,
+    /// A variable was moved to a different slot or offset, e.g. because it was reordered.
+    Moved,
+    /// A variable was changed to a type that is stored differently.
+    Retyped,
+    /// A static array was shrunk.
+    Shrunk,
+    /// The layout of a member of a struct was changed.
+    MemberChanged,
+    /// A variable was inserted before existing variables, outside of a storage gap.
+    Inserted,
+}
+
+/// Compares two storage layouts of a contract, returning the changes that are not compatible with
+/// upgrading from `old` to `new`.
+///
+/// Variables are matched by name and declaring contract, or by position and type if they were
+/// renamed.
+/// Variables whose name starts with `__gap` are storage gaps: they may be shrunk or removed to
+/// make room for new variables, as long as the variables after them do not move.
+pub fn diff(old: &StorageLayout, new: &StorageLayout) -> Vec<LayoutChange> {
+    let mut differ = Differ { old, new, changes: Vec::new(), compared: HashMap::new() };
+    differ.diff_entries(None, &old.storage, &new.storage);
+    for (namespace, old_entries) in &old.namespaces {
+        let new_entries = new.namespaces.get(namespace).map(Vec::as_slice).unwrap_or_default();
+        differ.diff_entries(Some(namespace), old_entries, new_entries);
+    }
+    differ.changes
+}
+
+/// The kind of an incompatible type change, and the struct member that changed, if any.
+type TypeChange = (LayoutChangeKind, Option<String>);
+
+struct Differ<'a> {
+    old: &'a StorageLayout,
+    new: &'a StorageLayout,
+    changes: Vec<LayoutChange>,
+    /// The results of comparing pairs of types. Also used to stop at recursive structs.
+    compared: HashMap<(&'a str, &'a str), Option<TypeChange>>,
+}
+
+impl<'a> Differ<'a> {
+    fn diff_entries(
+        &mut self,
+        namespace: Option<&str>,
+        old: &'a [StorageEntry],
+        new: &'a [StorageEntry],
+    ) {
+        // Match variables by name first, then renamed variables by position and type.
+        let mut matches = vec![None; old.len()];
+        let mut matched = vec![false; new.len()];
+        for (o, m) in old.iter().zip(&mut matches) {
+            let found = (0..new.len()).find(|&i| {
+                !matched[i]
+                    && new[i].label == o.label
+                    && contract_name(&new[i].contract) == contract_name(&o.contract)
+            });
+            if let Some(i) = found {
+                matched[i] = true;
+                *m = Some(i);
+            }
+        }
+        for (o, m) in old.iter().zip(&mut matches) {
+            if m.is_some() || is_gap(o) {
+                continue;
+            }
+            let start = range(self.old, o).0;
+            for (i, n) in new.iter().enumerate() {
+                if !matched[i]
+                    && range(self.new, n).0 == start
+                    && contract_name(&n.contract) == contract_name(&o.contract)
+                    && self.compare_types(&o.ty, &n.ty).is_none()
+                {
+                    matched[i] = true;
+                    *m = Some(i);
+                    break;
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        for (o, &m) in old.iter().zip(&matches) {
+            let Some(n) = m.map(|i| &new[i]) else {
+                if !is_gap(o) {
+                    changes.push((LayoutChangeKind::Removed, None, Some(o), None));
+                }
+                continue;
+            };
+            let (old_start, old_end) = range(self.old, o);
+            let (new_start, new_end) = range(self.new, n);
+            if is_gap(o) {
+                // Gaps shrink from the start to make room for the variables inserted before them.
+                if new_end != old_end || new_start < old_start {
+                    changes.push((LayoutChangeKind::Moved, None, Some(o), Some(n)));
+                }
+                continue;
+            }
+            if new_start != old_start {
+                changes.push((LayoutChangeKind::Moved, None, Some(o), Some(n)));
+                continue;
+            }
+            if let Some((kind, member)) = self.compare_types(&o.ty, &n.ty) {
+                changes.push((kind, member, Some(o), Some(n)));
+            }
+        }
+
+        let end = old.iter().map(|o| range(self.old, o).1).max().unwrap_or_default();
+        let gaps = old.iter().filter(|o| is_gap(o)).map(|o| range(self.old, o)).collect::<Vec<_>>();
+        for (n, _) in new.iter().zip(&matched).filter(|&(_, &matched)| !matched) {
+            let (start, new_end) = range(self.new, n);
+            if start < end
+                && !gaps
+                    .iter()
+                    .any(|&(gap_start, gap_end)| gap_start <= start && new_end <= gap_end)
+            {
+                changes.push((LayoutChangeKind::Inserted, None, None, Some(n)));
+            }
+        }
+
+        self.changes.extend(changes.into_iter().map(|(kind, member, old, new)| LayoutChange {
+            kind,
+            namespace: namespace.map(ToString::to_string),
+            member,
+            old: old.cloned(),
+            new: new.cloned(),
+        }));
+    }
+
+    /// Compares the types with the given identifiers, returning how the new type is incompatible
+    /// with the old one, and the struct member that changed, if any.
+    fn compare_types(&mut self, old_id: &'a str, new_id: &'a str) -> Option<TypeChange> {
+        if let Some(result) = self.compared.get(&(old_id, new_id)) {
+            return result.clone();
+        }
+        self.compared.insert((old_id, new_id), None);
+        let result = self.compare_types_uncached(old_id, new_id);
+        self.compared.insert((old_id, new_id), result.clone());
+        result
+    }
+
+    fn compare_types_uncached(&mut self, old_id: &'a str, new_id: &'a str) -> Option<TypeChange> {
+        let retyped = Some((LayoutChangeKind::Retyped, None));
+        let (Some(old), Some(new)) = (self.old.ty(old_id), self.new.ty(new_id)) else {
+            return if old_id == new_id { None } else { retyped };
+        };
+        if old.encoding != new.encoding {
+            return retyped;
+        }
+
+        // Arrays.
+        if let (Some(old_base), Some(new_base)) = (&old.base, &new.base) {
+            if let Some(change) = self.compare_types(old_base, new_base) {
+                return Some(change);
+            }
+            // Changing the size of the elements moves all but the first one.
+            if size(self.old, old_base) != size(self.new, new_base) {
+                return retyped;
+            }
+            if size(self.new, new_id) < size(self.old, old_id) {
+                return Some((LayoutChangeKind::Shrunk, None));
+            }
+            return None;
+        }
+
+        // Mappings.
+        if let (Some(old_key), Some(new_key)) = (&old.key, &new.key) {
+            let label = |layout: &'a StorageLayout, id: &'a str| layout.ty(id).map(|ty| &ty.label);
+            if label(self.old, old_key) != label(self.new, new_key) {
+                return retyped;
+            }
+            let (Some(old_value), Some(new_value)) = (&old.value, &new.value) else {
+                return retyped;
+            };
+            return self.compare_types(old_value, new_value);
+        }
+
+        // Structs. Members may be appended.
+        if let (Some(old_members), Some(new_members)) = (&old.members, &new.members) {
+            for (i, o) in old_members.iter().enumerate() {
+                let member_changed = Some((LayoutChangeKind::MemberChanged, Some(o.label.clone())));
+                let Some(n) = new_members.get(i) else { return member_changed };
+                if (&n.label, &n.slot, n.offset) != (&o.label, &o.slot, o.offset)
+                    || self.compare_types(&o.ty, &n.ty).is_some()
+                {
+                    return member_changed;
+                }
+            }
+            return None;
+        }
+
+        // Value types. Enums and contracts may be renamed.
+        let same_kind = |prefix| old.label.starts_with(prefix) && new.label.starts_with(prefix);
+        if old.label == new.label
+            || ((same_kind("enum ") || same_kind("contract "))
+                && old.number_of_bytes == new.number_of_bytes)
+        {
+            None
+        } else {
+            retyped
+        }
+    }
+}
+
+/// Returns the range of bytes occupied by the given entry, counted from the start of slot 0.
+///
+/// This does not fit in 256 bits for the slots of ERC-7201 namespaces.
+fn range(layout: &StorageLayout, entry: &StorageEntry) -> (U512, U512) {
+    let slot = entry.slot.parse::<U512>().unwrap_or_default();
+    let start = slot * U512::from(32) + U512::from(entry.offset);
+    (start, start + size(layout, &entry.ty))
+}
+
+/// Returns the number of bytes occupied by the given type.
+fn size(layout: &StorageLayout, id: &str) -> U512 {
+    layout.ty(id).and_then(|ty| ty.number_of_bytes.parse().ok()).unwrap_or(U512::from(32))
+}
+
+/// Returns whether the given entry is a storage gap reserved for future variables.
+fn is_gap(entry: &StorageEntry) -> bool {
+    entry.label.starts_with("__gap")
+}
+
+/// Returns the name of a contract from its fully qualified name.
+fn contract_name(fully_qualified: &str) -> &str {
+    fully_qualified.rsplit(':').next().unwrap_or(fully_qualified)
+}
+
+/// The contents of a `--storage-layout-base` file, the JSON output of `--emit storage-layout`.
+#[derive(Deserialize)]
+struct BaseLayouts {
+    #[serde(default)]
+    contracts: BTreeMap<String, BaseContract>,
+}
+
+#[derive(Deserialize)]
+struct BaseContract {
+    #[serde(rename = "storage-layout")]
+    storage_layout: Option<StorageLayout>,
+}
+
+impl BaseLayouts {
+    /// Returns the layout of the contract with the given fully qualified name, or with the given
+    /// name if it is unique.
+    fn find(&self, fully_qualified: &str, name: &str) -> Option<&StorageLayout> {
+        if let Some(contract) = self.contracts.get(fully_qualified) {
+            return contract.storage_layout.as_ref();
+        }
+        let mut candidates = self.contracts.iter().filter(|(k, _)| contract_name(k) == name);
+        match (candidates.next(), candidates.next()) {
+            (Some((_, contract)), None) => contract.storage_layout.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Compares the storage layouts of the compiled contracts against `--storage-layout-base`,
+/// reporting the incompatible changes as errors.
+///
+/// Returns the changes of each compared contract, by fully qualified name. Non-abstract contracts
+/// that are not in the base are included without changes, and reported with a warning.
+pub(crate) fn check(gcx: Gcx<'_>) -> BTreeMap<String, Option<Vec<LayoutChange>>> {
+    let mut changes = BTreeMap::new();
+    let Some(path) = &gcx.sess.opts.storage_layout_base else { return changes };
+    let base = std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_slice::<BaseLayouts>(&s).map_err(|e| e.to_string()));
+    let base = match base {
+        Ok(base) => base,
+        Err(e) => {
+            let msg = format!("failed to read storage layout base {}: {e}", path.display());
+            gcx.dcx().err(msg).emit();
+            return changes;
+        }
+    };
+
+    for id in gcx.hir.contract_ids() {
+        let contract = gcx.hir.contract(id);
+        let name = gcx.contract_fully_qualified_name(id).to_string();
+        let Some(old) = base.find(&name, contract.name.as_str()) else {
+            // Libraries can't have state variables.
+            if contract.kind.is_contract() {
+                let msg = format!("contract `{}` is not in the storage layout base", contract.name);
+                gcx.dcx()
+                    .warn(msg)
+                    .span(contract.name.span)
+                    .note(format!("its storage layout was not compared to {}", path.display()))
+                    .emit();
+                changes.insert(name, None);
+            }
+            continue;
+        };
+        let new = StorageLayout::new(gcx, id);
+        let contract_changes = diff(old, &new);
+        for change in &contract_changes {
+            report(gcx, contract, old, &new, change);
+        }
+        changes.insert(name, Some(contract_changes));
+    }
+    changes
+}
+
+fn report(
+    gcx: Gcx<'_>,
+    contract: &hir::Contract<'_>,
+    old_layout: &StorageLayout,
+    new_layout: &StorageLayout,
+    change: &LayoutChange,
+) {
+    let ty = |layout: &StorageLayout, entry: &StorageEntry| {
+        layout.ty(&entry.ty).map_or(&entry.ty, |ty| &ty.label).clone()
+    };
+    let location = |entry: &StorageEntry| format!("slot {}, offset {}", entry.slot, entry.offset);
+    let (old, new) = (change.old.as_ref(), change.new.as_ref());
+    let name = new.or(old).unwrap().label.as_str();
+
+    // Removed variables do not exist anymore, so point at the contract instead.
+    let span = new.map_or(contract.name.span, |new| {
+        gcx.hir.variable(hir::VariableId::from_usize(new.ast_id)).span
+    });
+    let mut diag = match change.kind {
+        LayoutChangeKind::Removed => {
+            let old = old.unwrap();
+            gcx.dcx()
+                .err(format!("storage variable `{name}` was removed"))
+                .note(format!("it was stored at {}", location(old)))
+        }
+        LayoutChangeKind::Moved => {
+            let (old, new) = (old.unwrap(), new.unwrap());
+            gcx.dcx().err(format!("storage variable `{name}` was moved")).note(format!(
+                "it was stored at {}, and is now at {}",
+                location(old),
+                location(new)
+            ))
+        }
+        LayoutChangeKind::Retyped => {
+            let (old, new) = (old.unwrap(), new.unwrap());
+            gcx.dcx().err(format!(
+                "type of storage variable `{name}` changed from `{}` to `{}`",
+                ty(old_layout, old),
+                ty(new_layout, new),
+            ))
+        }
+        LayoutChangeKind::Shrunk => {
+            let (old, new) = (old.unwrap(), new.unwrap());
+            gcx.dcx().err(format!(
+                "storage variable `{name}` was shrunk from `{}` to `{}`",
+                ty(old_layout, old),
+                ty(new_layout, new),
+            ))
+        }
+        LayoutChangeKind::MemberChanged => {
+            let member = change.member.as_deref().unwrap_or_default();
+            gcx.dcx()
+                .err(format!("layout of member `{member}` of storage variable `{name}` changed"))
+                .help("struct members can only be appended")
+        }
+        LayoutChangeKind::Inserted => gcx
+            .dcx()
+            .err(format!("storage variable `{name}` was inserted before existing variables"))
+            .help("declare new variables after the existing ones, or in place of a storage gap"),
+    };
+    diag = diag.span(span);
+    if let Some(namespace) = &change.namespace {
+        diag = diag.note(format!("in namespace `{namespace}`"));
+    }
+    diag.emit();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::json::StorageType;
+
+    fn ty(label: &str, bytes: u64, base: Option<&str>) -> StorageType {
+        StorageType {
+            base: base.map(ToString::to_string),
+            encoding: "inplace".to_string(),
+            key: None,
+            label: label.to_string(),
+            members: None,
+            number_of_bytes: bytes.to_string(),
+            value: None,
+        }
+    }
+
+    fn layout(vars: &[(&str, u64, u8, &str)]) -> StorageLayout {
+        let types = [
+            ("t_uint128", ty("uint128", 16, None)),
+            ("t_uint256", ty("uint256", 32, None)),
+            ("t_array(t_uint256)8_storage", ty("uint256[8]", 256, Some("t_uint256"))),
+            ("t_array(t_uint256)10_storage", ty("uint256[10]", 320, Some("t_uint256"))),
+        ];
+        StorageLayout {
+            namespaces: Default::default(),
+            storage: vars
+                .iter()
+                .enumerate()
+                .map(|(i, &(label, slot, offset, ty))| StorageEntry {
+                    ast_id: i,
+                    contract: "a.sol:C".to_string(),
+                    label: label.to_string(),
+                    offset,
+                    slot: slot.to_string(),
+                    ty: ty.to_string(),
+                })
+                .collect(),
+            types: Some(types.into_iter().map(|(id, ty)| (id.to_string(), ty)).collect()),
+        }
+    }
+
+    fn kinds(
+        old: &[(&str, u64, u8, &str)],
+        new: &[(&str, u64, u8, &str)],
+    ) -> Vec<LayoutChangeKind> {
+        diff(&layout(old), &layout(new)).into_iter().map(|change| change.kind).collect()
+    }
+
+    #[test]
+    fn compatible() {
+        let old = [("a", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128")];
+        assert_eq!(kinds(&old, &old), []);
+        let appended =
+            [("a", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128"), ("c", 1, 0, "t_uint256")];
+        assert_eq!(kinds(&old, &appended), []);
+        let renamed = [("x", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128")];
+        assert_eq!(kinds(&old, &renamed), []);
+    }
+
+    #[test]
+    fn incompatible() {
+        use LayoutChangeKind::*;
+
+        let old = [("a", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128"), ("c", 1, 0, "t_uint256")];
+        let reordered =
+            [("b", 0, 0, "t_uint128"), ("a", 0, 16, "t_uint128"), ("c", 1, 0, "t_uint256")];
+        assert_eq!(kinds(&old, &reordered), [Moved, Moved]);
+        let retyped =
+            [("a", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128"), ("c", 1, 0, "t_uint128")];
+        assert_eq!(kinds(&old, &retyped), [Retyped]);
+        let removed = [("a", 0, 0, "t_uint128"), ("b", 0, 16, "t_uint128")];
+        assert_eq!(kinds(&old, &removed), [Removed]);
+        let inserted = [
+            ("a", 0, 0, "t_uint128"),
+            ("b", 0, 16, "t_uint128"),
+            ("x", 1, 0, "t_uint256"),
+            ("c", 2, 0, "t_uint256"),
+        ];
+        assert_eq!(kinds(&old, &inserted), [Moved, Inserted]);
+
+        let old = [("a", 0, 0, "t_array(t_uint256)10_storage")];
+        let shrunk = [("a", 0, 0, "t_array(t_uint256)8_storage")];
+        assert_eq!(kinds(&old, &shrunk), [Shrunk]);
+    }
+
+    #[test]
+    fn gaps() {
+        use LayoutChangeKind::*;
+
+        let old = [
+            ("a", 0, 0, "t_uint256"),
+            ("__gap", 1, 0, "t_array(t_uint256)10_storage"),
+            ("b", 11, 0, "t_uint256"),
+        ];
+        let used = [
+            ("a", 0, 0, "t_uint256"),
+            ("x", 1, 0, "t_uint256"),
+            ("y", 2, 0, "t_uint256"),
+            ("__gap", 3, 0, "t_array(t_uint256)8_storage"),
+            ("b", 11, 0, "t_uint256"),
+        ];
+        assert_eq!(kinds(&old, &used), []);
+
+        let not_shrunk = [
+            ("a", 0, 0, "t_uint256"),
+            ("x", 1, 0, "t_uint256"),
+            ("__gap", 2, 0, "t_array(t_uint256)10_storage"),
+            ("b", 12, 0, "t_uint256"),
+        ];
+        assert_eq!(kinds(&old, &not_shrunk), [Moved, Moved]);
+    }
+}
@@ -0,0 +1,189 @@
+//! JSON representation of storage layouts, in the same format as solc's `storageLayout` output.
+
+use super::{Namespace, StorageItem};
+use crate::{
+    hir,
+    ty::{Gcx, Ty, TyKind},
+};
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+use solar_ast::ElementaryType;
+use std::collections::BTreeMap;
+
+/// The storage layout of a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// The members of the ERC-7201 namespaces, with absolute slots.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub namespaces: BTreeMap<String, Vec<StorageEntry>>,
+    /// The state variables, in storage order.
+    pub storage: Vec<StorageEntry>,
+    /// The types of the state variables and the types they contain, by identifier.
+    pub types: Option<BTreeMap<String, StorageType>>,
+}
+
+/// The location of a state variable or struct member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+    pub ast_id: usize,
+    pub contract: String,
+    pub label: String,
+    pub offset: u8,
+    pub slot: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A type in [`StorageLayout::types`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    pub encoding: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageEntry>>,
+    pub number_of_bytes: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl StorageLayout {
+    /// Returns the persistent storage layout of the given contract.
+    pub fn new(gcx: Gcx<'_>, id: hir::ContractId) -> Self {
+        let layout = gcx.storage_layout(id);
+        StorageLayoutBuilder::new(gcx, id).build(layout.storage, layout.namespaces)
+    }
+
+    /// Returns the transient storage layout of the given contract, if it has any transient state
+    /// variables.
+    pub fn transient(gcx: Gcx<'_>, id: hir::ContractId) -> Option<Self> {
+        let layout = gcx.storage_layout(id);
+        if layout.transient.is_empty() {
+            return None;
+        }
+        Some(StorageLayoutBuilder::new(gcx, id).build(layout.transient, &[]))
+    }
+
+    /// Returns the type with the given identifier.
+    pub fn ty(&self, id: &str) -> Option<&StorageType> {
+        self.types.as_ref()?.get(id)
+    }
+}
+
+/// Builds the JSON storage layout of a contract, collecting the types of the stored variables.
+struct StorageLayoutBuilder<'gcx> {
+    gcx: Gcx<'gcx>,
+    contract: String,
+    types: BTreeMap<String, StorageType>,
+}
+
+impl<'gcx> StorageLayoutBuilder<'gcx> {
+    fn new(gcx: Gcx<'gcx>, id: hir::ContractId) -> Self {
+        Self {
+            gcx,
+            contract: gcx.contract_fully_qualified_name(id).to_string(),
+            types: Default::default(),
+        }
+    }
+
+    fn build(mut self, items: &[StorageItem], namespaces: &[Namespace]) -> StorageLayout {
+        let storage = self.entries(items, U256::ZERO);
+        let namespaces = namespaces
+            .iter()
+            .map(|ns| {
+                let members = self.gcx.struct_storage_layout(ns.strukt).members;
+                (format!("erc7201:{}", ns.id), self.entries(members, ns.slot))
+            })
+            .collect();
+        StorageLayout { namespaces, storage, types: (!self.types.is_empty()).then_some(self.types) }
+    }
+
+    /// Returns the entries of the given items, with slots relative to `base`.
+    fn entries(&mut self, items: &[StorageItem], base: U256) -> Vec<StorageEntry> {
+        items.iter().map(|item| self.entry(item, base)).collect()
+    }
+
+    fn entry(&mut self, item: &StorageItem, base: U256) -> StorageEntry {
+        let var = self.gcx.hir.variable(item.var);
+        StorageEntry {
+            ast_id: item.var.index(),
+            contract: self.contract.clone(),
+            label: var.name.map(|name| name.to_string()).unwrap_or_default(),
+            offset: item.offset,
+            slot: base.saturating_add(item.slot).to_string(),
+            ty: self.add_type(self.gcx.type_of_item(item.var.into())),
+        }
+    }
+
+    /// Adds the given type and the types it contains to `types`, returning its identifier.
+    fn add_type(&mut self, ty: Ty<'gcx>) -> String {
+        let gcx = self.gcx;
+        let id = super::type_identifier(gcx, ty);
+        if self.types.contains_key(&id) {
+            return id;
+        }
+        let ty = ty.peel_refs();
+        let encoding = match ty.kind {
+            TyKind::Elementary(ElementaryType::String | ElementaryType::Bytes) => "bytes",
+            TyKind::Mapping(..) => "mapping",
+            TyKind::DynArray(_) => "dynamic_array",
+            _ => "inplace",
+        };
+        // Insert the type before its inner types so that recursive structs terminate.
+        self.types.insert(
+            id.clone(),
+            StorageType {
+                base: None,
+                encoding: encoding.to_string(),
+                key: None,
+                label: super::type_label(gcx, ty),
+                members: None,
+                number_of_bytes: super::storage_size(gcx, ty).number_of_bytes().to_string(),
+                value: None,
+            },
+        );
+        let (mut base, mut key, mut value, mut members) = (None, None, None, None);
+        match ty.kind {
+            TyKind::Array(element, _) | TyKind::DynArray(element) => {
+                base = Some(self.add_type(element))
+            }
+            TyKind::Mapping(k, v) => {
+                key = Some(self.add_mapping_key(k));
+                value = Some(self.add_type(v));
+            }
+            TyKind::Struct(struct_id) => {
+                let layout = gcx.struct_storage_layout(struct_id);
+                members = Some(self.entries(layout.members, U256::ZERO))
+            }
+            _ => {}
+        }
+        let entry = self.types.get_mut(&id).unwrap();
+        (entry.base, entry.key, entry.value, entry.members) = (base, key, value, members);
+        id
+    }
+
+    /// Mapping keys are not stored, so `string` and `bytes` keys are memory pointers in solc.
+    fn add_mapping_key(&mut self, key: Ty<'gcx>) -> String {
+        let TyKind::Elementary(ty @ (ElementaryType::String | ElementaryType::Bytes)) =
+            key.peel_refs().kind
+        else {
+            return self.add_type(key);
+        };
+        let id = format!("t_{}_memory_ptr", ty.to_abi_str());
+        self.types.entry(id.clone()).or_insert_with(|| StorageType {
+            base: None,
+            encoding: "bytes".to_string(),
+            key: None,
+            label: ty.to_abi_str().into_owned(),
+            members: None,
+            number_of_bytes: "32".to_string(),
+            value: None,
+        });
+        id
+    }
+}
@@ -0,0 +1,344 @@
+//! Storage layout computation.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html>
+
+use crate::{
+    hir,
+    ty::{Gcx, Recursiveness, Ty, TyKind},
+};
+use alloy_primitives::{keccak256, U256};
+use solar_ast::{self as ast, ElementaryType, Visibility};
+use solar_data_structures::BumpExt;
+use solar_interface::{Span, Symbol};
+use std::fmt::Write;
+
+pub mod diff;
+pub mod json;
+
+/// The storage layout of a contract.
+///
+/// Return type of [`Gcx::storage_layout`].
+#[derive(Clone, Copy, Debug)]
+pub struct StorageLayout<'gcx> {
+    /// The state variables stored in persistent storage, including the inherited ones, in
+    /// declaration order.
+    pub storage: &'gcx [StorageItem],
+    /// The state variables stored in transient storage, including the inherited ones, in
+    /// declaration order.
+    ///
+    /// Transient storage is numbered independently from persistent storage.
+    pub transient: &'gcx [StorageItem],
+    /// The [ERC-7201] namespaces declared by the contract and its bases, in declaration order.
+    ///
+    /// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+    pub namespaces: &'gcx [Namespace],
+}
+
+/// An [ERC-7201] storage namespace, declared with a `@custom:storage-location erc7201:<id>`
+/// annotation on a struct.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Namespace {
+    /// The namespace ID.
+    pub id: Symbol,
+    /// The struct stored in the namespace.
+    pub strukt: hir::StructId,
+    /// The slot the struct starts at.
+    pub slot: U256,
+}
+
+/// The storage layout of a struct.
+///
+/// Return type of [`Gcx::struct_storage_layout`].
+#[derive(Clone, Copy, Debug)]
+pub struct StructLayout<'gcx> {
+    /// The members of the struct, with slots relative to the start of the struct.
+    pub members: &'gcx [StorageItem],
+    /// The number of slots occupied by the struct.
+    pub slots: U256,
+}
+
+/// The location of a variable in storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageItem {
+    /// The variable.
+    pub var: hir::VariableId,
+    /// The slot the variable starts at.
+    pub slot: U256,
+    /// The offset in bytes of the variable in its slot.
+    pub offset: u8,
+}
+
+/// The amount of storage occupied by a type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageSize {
+    /// The number of bytes occupied in a slot. Types that are not packed always occupy 32 bytes.
+    pub bytes: u8,
+    /// The number of slots occupied.
+    pub slots: U256,
+}
+
+impl StorageSize {
+    fn bytes(bytes: u8) -> Self {
+        Self { bytes, slots: U256::from(1) }
+    }
+
+    /// Returns the total number of bytes occupied by the type, as reported in `numberOfBytes` by
+    /// solc.
+    pub fn number_of_bytes(&self) -> U256 {
+        if self.bytes < 32 {
+            U256::from(self.bytes)
+        } else {
+            self.slots.saturating_mul(U256::from(32))
+        }
+    }
+}
+
+/// Returns the amount of storage occupied by the given type.
+pub fn storage_size<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>) -> StorageSize {
+    match ty.kind {
+        TyKind::Elementary(ty) => match ty {
+            ElementaryType::Address(_) => StorageSize::bytes(20),
+            ElementaryType::Bool => StorageSize::bytes(1),
+            ElementaryType::String | ElementaryType::Bytes => StorageSize::bytes(32),
+            ElementaryType::Fixed(size, _)
+            | ElementaryType::UFixed(size, _)
+            | ElementaryType::Int(size)
+            | ElementaryType::UInt(size)
+            | ElementaryType::FixedBytes(size) => StorageSize::bytes(size.bytes()),
+        },
+        TyKind::Contract(_) => StorageSize::bytes(20),
+        TyKind::Enum(_) => StorageSize::bytes(1),
+        TyKind::Udvt(ty, _) | TyKind::Ref(ty, _) => storage_size(gcx, ty),
+        TyKind::FnPtr(f) => {
+            StorageSize::bytes(if f.visibility == Visibility::External { 24 } else { 8 })
+        }
+        TyKind::Struct(id) => StorageSize { bytes: 32, slots: gcx.struct_storage_layout(id).slots },
+        TyKind::Array(element, len) => {
+            let element = storage_size(gcx, element);
+            // Small elements are packed, all others start a new slot.
+            let slots = if element.bytes <= 16 {
+                len.div_ceil(U256::from(32 / element.bytes))
+            } else {
+                len.saturating_mul(element.slots)
+            };
+            StorageSize { bytes: 32, slots }
+        }
+        _ => StorageSize::bytes(32),
+    }
+}
+
+/// Assigns slots and offsets to consecutive variables.
+#[derive(Default)]
+struct SlotAllocator {
+    slot: U256,
+    offset: u8,
+    overflowed: bool,
+}
+
+impl SlotAllocator {
+    fn alloc(&mut self, size: StorageSize) -> (U256, u8) {
+        if self.offset + size.bytes > 32 {
+            self.advance(U256::from(1));
+        }
+        let position = (self.slot, self.offset);
+        if size.slots == U256::from(1) && self.offset + size.bytes <= 32 {
+            self.offset += size.bytes;
+        } else {
+            self.advance(size.slots);
+        }
+        position
+    }
+
+    fn advance(&mut self, slots: U256) {
+        self.slot = self.slot.checked_add(slots).unwrap_or_else(|| {
+            self.overflowed = true;
+            U256::MAX
+        });
+        self.offset = 0;
+    }
+
+    /// Returns the number of slots used.
+    fn finish(mut self) -> (U256, bool) {
+        if self.offset > 0 {
+            self.advance(U256::from(1));
+        }
+        (self.slot, self.overflowed)
+    }
+}
+
+fn layout_variables<'gcx>(
+    gcx: Gcx<'gcx>,
+    vars: impl IntoIterator<Item = hir::VariableId>,
+) -> (Vec<StorageItem>, U256, bool) {
+    let mut allocator = SlotAllocator::default();
+    let items = vars
+        .into_iter()
+        .map(|var| {
+            let size = storage_size(gcx, gcx.type_of_item(var.into()));
+            let (slot, offset) = allocator.alloc(size);
+            StorageItem { var, slot, offset }
+        })
+        .collect();
+    let (slots, overflowed) = allocator.finish();
+    (items, slots, overflowed)
+}
+
+pub(crate) fn storage_layout(gcx: Gcx<'_>, id: hir::ContractId) -> StorageLayout<'_> {
+    let c = gcx.hir.contract(id);
+    let vars = c.linearized_bases.iter().rev().flat_map(|&base| gcx.hir.contract(base).variables());
+    let vars = vars.filter(|&var| gcx.hir.variable(var).mutability.is_none());
+    let (storage, _, overflowed) =
+        layout_variables(gcx, vars.clone().filter(|&var| !gcx.hir.variable(var).is_transient()));
+    let (transient, _, transient_overflowed) =
+        layout_variables(gcx, vars.filter(|&var| gcx.hir.variable(var).is_transient()));
+    if overflowed || transient_overflowed {
+        gcx.dcx().err("contract requires too much storage").span(c.name.span).emit();
+    }
+    let mut namespaces = Vec::<Namespace>::new();
+    for &base in c.linearized_bases.iter().rev() {
+        for strukt in gcx.hir.contract(base).items.iter().filter_map(hir::ItemId::as_struct) {
+            let Some(id) = gcx.hir.strukt(strukt).namespace else { continue };
+            if namespaces.iter().all(|ns| ns.id != id) {
+                namespaces.push(Namespace { id, strukt, slot: erc7201_slot(id.as_str()) });
+            }
+        }
+    }
+    StorageLayout {
+        storage: gcx.bump().alloc_vec(storage),
+        transient: gcx.bump().alloc_vec(transient),
+        namespaces: gcx.bump().alloc_vec(namespaces),
+    }
+}
+
+/// Returns the base slot of the [ERC-7201] namespace with the given ID:
+/// `keccak256(abi.encode(uint256(keccak256(id)) - 1)) & ~bytes32(uint256(0xff))`.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+pub fn erc7201_slot(id: &str) -> U256 {
+    let location = U256::from_be_bytes(keccak256(id).0) - U256::from(1);
+    let slot = U256::from_be_bytes(keccak256(location.to_be_bytes::<32>()).0);
+    slot & !U256::from(0xff)
+}
+
+/// Returns the value of the `@custom:storage-location` NatSpec tag in the given doc-comments,
+/// along with the span of the comment that contains it.
+pub(crate) fn find_storage_location<'a>(docs: &'a ast::DocComments<'_>) -> Option<(Span, &'a str)> {
+    docs.iter().find_map(|doc| {
+        doc.symbol.as_str().lines().find_map(|line| {
+            let line = line.trim_start().trim_start_matches('*').trim_start();
+            let value = line.strip_prefix("@custom:storage-location")?;
+            (value.is_empty() || value.starts_with(char::is_whitespace))
+                .then(|| (doc.span, value.trim()))
+        })
+    })
+}
+
+/// Parses the value of a `@custom:storage-location` tag, returning the ERC-7201 namespace ID.
+pub(crate) fn parse_storage_location(value: &str) -> Option<&str> {
+    value.strip_prefix("erc7201:").filter(|id| !id.is_empty())
+}
+
+pub(crate) fn struct_storage_layout(gcx: Gcx<'_>, id: hir::StructId) -> StructLayout<'_> {
+    // Infinitely recursive structs have already been reported.
+    if let Recursiveness::Infinite(_) = gcx.struct_recursiveness(id) {
+        return StructLayout { members: &[], slots: U256::from(1) };
+    }
+    let (members, slots, _) = layout_variables(gcx, gcx.hir.strukt(id).fields.iter().copied());
+    StructLayout { members: gcx.bump().alloc_vec(members), slots }
+}
+
+/// Returns the identifier of the given storage type, as used in the `types` of the solc storage
+/// layout output.
+///
+/// For example: `t_uint256`, `t_mapping(t_address,t_uint256)` or
+/// `t_array(t_struct(S)0_storage)dyn_storage`.
+pub fn type_identifier<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>) -> String {
+    let mut s = String::new();
+    write_type_identifier(gcx, ty, &mut s);
+    s
+}
+
+fn write_type_identifier<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>, s: &mut String) {
+    match ty.kind {
+        TyKind::Elementary(ElementaryType::Address(true)) => s.push_str("t_address_payable"),
+        TyKind::Elementary(ty @ (ElementaryType::String | ElementaryType::Bytes)) => {
+            write!(s, "t_{}_storage", ty.to_abi_str()).unwrap()
+        }
+        TyKind::Elementary(ty) => write!(s, "t_{}", ty.to_abi_str()).unwrap(),
+        TyKind::Ref(ty, _) => write_type_identifier(gcx, ty, s),
+        TyKind::Contract(id) => {
+            write!(s, "t_contract({}){}", gcx.item_name(id), id.index()).unwrap()
+        }
+        TyKind::Struct(id) => {
+            write!(s, "t_struct({}){}_storage", gcx.item_name(id), id.index()).unwrap()
+        }
+        TyKind::Enum(id) => write!(s, "t_enum({}){}", gcx.item_name(id), id.index()).unwrap(),
+        TyKind::Udvt(_, id) => {
+            write!(s, "t_userDefinedValueType({}){}", gcx.item_name(id), id.index()).unwrap()
+        }
+        TyKind::Array(element, len) => {
+            s.push_str("t_array(");
+            write_type_identifier(gcx, element, s);
+            write!(s, "){len}_storage").unwrap();
+        }
+        TyKind::DynArray(element) => {
+            s.push_str("t_array(");
+            write_type_identifier(gcx, element, s);
+            s.push_str(")dyn_storage");
+        }
+        TyKind::Mapping(key, value) => {
+            s.push_str("t_mapping(");
+            // Mapping keys are never stored, so they are not in storage.
+            match key.peel_refs().kind {
+                TyKind::Elementary(ty @ (ElementaryType::String | ElementaryType::Bytes)) => {
+                    write!(s, "t_{}_memory_ptr", ty.to_abi_str()).unwrap()
+                }
+                _ => write_type_identifier(gcx, key, s),
+            }
+            s.push(',');
+            write_type_identifier(gcx, value, s);
+            s.push(')');
+        }
+        TyKind::FnPtr(f) => {
+            let visibility =
+                if f.visibility == Visibility::External { "external" } else { "internal" };
+            write!(s, "t_function_{visibility}_{}(", f.state_mutability).unwrap();
+            for (i, &ty) in f.parameters.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                write_type_identifier(gcx, ty, s);
+            }
+            s.push_str(")returns(");
+            for (i, &ty) in f.returns.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                write_type_identifier(gcx, ty, s);
+            }
+            s.push(')');
+        }
+        _ => s.push_str("t_unknown"),
+    }
+}
+
+/// Returns the human-readable label of the given storage type, as used in the solc storage layout
+/// output.
+///
+/// For example: `uint256`, `mapping(address => uint256)` or `struct C.S[]`.
+pub fn type_label<'gcx>(gcx: Gcx<'gcx>, ty: Ty<'gcx>) -> String {
+    match ty.kind {
+        TyKind::Ref(ty, _) => type_label(gcx, ty),
+        TyKind::Struct(id) => format!("struct {}", gcx.item_canonical_name(id)),
+        TyKind::Enum(id) => format!("enum {}", gcx.item_canonical_name(id)),
+        TyKind::Udvt(_, id) => gcx.item_canonical_name(id).to_string(),
+        TyKind::Array(element, len) => format!("{}[{len}]", type_label(gcx, element)),
+        TyKind::DynArray(element) => format!("{}[]", type_label(gcx, element)),
+        TyKind::Mapping(key, value) => {
+            format!("mapping({} => {})", type_label(gcx, key), type_label(gcx, value))
+        }
+        _ => ty.display(gcx).to_string(),
+    }
+}
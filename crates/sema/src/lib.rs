@@ -11,7 +11,10 @@ extern crate tracing;
 
 use rayon::prelude::*;
 use solar_data_structures::{trustme, OnDrop};
-use solar_interface::{config::CompilerStage, Result, Session};
+use solar_interface::{
+    config::{CompilerStage, DumpKind},
+    Result, Session,
+};
 use thread_local::ThreadLocal;
 use ty::Gcx;
 
@@ -23,13 +26,16 @@ pub use solar_interface as interface;
 
 mod ast_lowering;
 mod ast_passes;
+mod min_version;
 
 mod parse;
 pub use parse::{ParsedSource, ParsedSources, ParsingContext};
 
 pub mod builtins;
+pub mod cfg;
 pub mod eval;
 pub mod hir;
+pub mod layout;
 pub mod ty;
 
 mod typeck;
@@ -106,25 +112,36 @@ fn lower<'sess, 'hir>(
     sources: &ParsedSources<'_>,
     arena: &'hir hir::Arena,
 ) -> Result<(hir::Hir<'hir>, ast_lowering::SymbolResolver<'sess>)> {
-    debug_span!("all_ast_passes").in_scope(|| {
-        sources.par_asts().for_each(|ast| {
-            ast_passes::run(sess, ast);
-        });
+    let min_versions = debug_span!("all_ast_passes").in_scope(|| {
+        sources
+            .as_raw_slice()
+            .par_iter()
+            .map(|source| source.ast.as_ref().map(|ast| ast_passes::run(sess, ast)))
+            .collect::<Vec<_>>()
     });
 
     sess.dcx.has_errors()?;
 
-    Ok(ast_lowering::lower(sess, sources, arena))
+    let (mut hir, symbol_resolver) = ast_lowering::lower(sess, sources, arena);
+    for (source, min_version) in hir.sources.iter_mut().zip(min_versions) {
+        source.min_version = min_version.map(|min| min.version);
+    }
+    Ok((hir, symbol_resolver))
 }
 
 #[instrument(level = "debug", skip_all)]
 fn analysis(gcx: Gcx<'_>) -> Result<()> {
     if let Some(dump) = &gcx.sess.opts.unstable.dump {
-        if dump.kind.is_hir() {
-            dump_hir(gcx, dump.paths.as_deref())?;
+        match dump.kind {
+            DumpKind::Hir => dump_hir(gcx, dump.paths.as_deref())?,
+            DumpKind::Cfg => dump_cfg(gcx, dump.paths.as_deref())?,
+            DumpKind::Ast => {}
         }
     }
 
+    // Report cyclic initializers before they are evaluated as part of array types.
+    let _ = gcx.initializer_cycles(());
+
     // Lower HIR types.
     gcx.hir.par_item_ids().for_each(|id| {
         let _ = gcx.type_of_item(id);
@@ -139,10 +156,13 @@ fn analysis(gcx: Gcx<'_>) -> Result<()> {
     typeck::check(gcx);
     gcx.sess.dcx.has_errors()?;
 
+    // Layout changes are reported as errors, but are still emitted so that they can be inspected.
+    let layout_changes = layout::diff::check(gcx);
+
     if !gcx.sess.opts.emit.is_empty() {
-        emit::emit(gcx);
-        gcx.sess.dcx.has_errors()?;
+        emit::emit(gcx, &layout_changes);
     }
+    gcx.sess.dcx.has_errors()?;
 
     Ok(())
 }
@@ -180,6 +200,48 @@ fn dump_hir(gcx: Gcx<'_>, paths: Option<&[String]>) -> Result<()> {
     Ok(())
 }
 
+fn dump_cfg(gcx: Gcx<'_>, paths: Option<&[String]>) -> Result<()> {
+    let name_of = |id: hir::FunctionId| {
+        let f = gcx.hir.function(id);
+        let name = f.name.map_or_else(|| f.kind.to_str().to_string(), |name| name.to_string());
+        match f.contract {
+            Some(c) => format!("{}.{name}", gcx.hir.contract(c).name),
+            None => name,
+        }
+    };
+    let mut matched = vec![false; paths.map_or(0, |paths| paths.len())];
+    for id in gcx.hir.function_ids() {
+        let f = gcx.hir.function(id);
+        if f.is_getter() {
+            continue;
+        }
+        let Some(cfg) = gcx.cfg(id) else { continue };
+        let name = name_of(id);
+        if let Some(paths) = paths {
+            // Match either the canonical name, `Contract.function`, or just the function name.
+            let short_name = name.rsplit('.').next().unwrap();
+            let mut any = false;
+            for (path, matched) in paths.iter().zip(&mut matched) {
+                if path == &name || path == short_name {
+                    *matched = true;
+                    any = true;
+                }
+            }
+            if !any {
+                continue;
+            }
+        }
+        println!("{}", cfg.dot(gcx.sess, &name));
+    }
+    if let Some(paths) = paths {
+        if let Some(i) = matched.iter().position(|&m| !m) {
+            let msg = format!("`-Zdump=cfg={:?}` did not match any function", paths[i]);
+            return Err(gcx.dcx().err(msg).emit());
+        }
+    }
+    Ok(())
+}
+
 fn match_file_name(name: &solar_interface::source_map::FileName, path: &str) -> bool {
     match name {
         solar_interface::source_map::FileName::Real(path_buf) => {
@@ -0,0 +1,272 @@
+//! Inference of the minimum Solidity version required by the language features used in a source.
+
+use solar_ast::{self as ast, visit::Visit, ElementaryType, StrKind};
+use solar_data_structures::Never;
+use solar_interface::{config::SOLC_VERSION, sym, Session, Span};
+use std::ops::ControlFlow;
+
+/// The oldest version that is considered when no feature requires a newer one.
+const MIN_SUPPORTED_VERSION: semver::Version = semver::Version::new(0, 4, 0);
+
+/// The last patch version of each released minor version before the current one, used to find
+/// the oldest compiler version that satisfies a version requirement.
+const RELEASES: &[(u64, u64)] = &[(4, 26), (5, 17), (6, 12), (7, 6)];
+
+/// A language feature that is only available since a certain Solidity version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Feature {
+    Emit,
+    ConstructorKeyword,
+    FileLevelStructOrEnum,
+    AbstractContract,
+    TryCatch,
+    ReceiveOrFallback,
+    Immutable,
+    UnicodeString,
+    FileLevelFunction,
+    FileLevelConstant,
+    Unchecked,
+    CustomError,
+    BytesConcat,
+    Udvt,
+    StringConcat,
+    FileLevelUsing,
+    NamedMappingParameter,
+    UserDefinedOperator,
+    FileLevelEvent,
+    Transient,
+}
+
+impl Feature {
+    /// Returns the first Solidity version that supports the feature.
+    pub(crate) fn version(self) -> semver::Version {
+        let (minor, patch) = match self {
+            Self::Emit => (4, 21),
+            Self::ConstructorKeyword => (4, 22),
+            Self::FileLevelStructOrEnum
+            | Self::AbstractContract
+            | Self::TryCatch
+            | Self::ReceiveOrFallback => (6, 0),
+            Self::Immutable => (6, 5),
+            Self::UnicodeString => (7, 0),
+            Self::FileLevelFunction => (7, 1),
+            Self::FileLevelConstant => (7, 4),
+            Self::Unchecked => (8, 0),
+            Self::CustomError | Self::BytesConcat => (8, 4),
+            Self::Udvt => (8, 8),
+            Self::StringConcat => (8, 12),
+            Self::FileLevelUsing => (8, 13),
+            Self::NamedMappingParameter => (8, 18),
+            Self::UserDefinedOperator => (8, 19),
+            Self::FileLevelEvent => (8, 22),
+            Self::Transient => (8, 27),
+        };
+        semver::Version::new(0, minor, patch)
+    }
+
+    /// Returns a description of the feature.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::Emit => "`emit` statements",
+            Self::ConstructorKeyword => "`constructor` definitions",
+            Self::FileLevelStructOrEnum => "file-level structs and enums",
+            Self::AbstractContract => "abstract contracts",
+            Self::TryCatch => "`try` statements",
+            Self::ReceiveOrFallback => "`receive` and `fallback` functions",
+            Self::Immutable => "immutable variables",
+            Self::UnicodeString => "unicode string literals",
+            Self::FileLevelFunction => "file-level functions",
+            Self::FileLevelConstant => "file-level constants",
+            Self::Unchecked => "`unchecked` blocks",
+            Self::CustomError => "custom errors",
+            Self::BytesConcat => "`bytes.concat`",
+            Self::Udvt => "user-defined value types",
+            Self::StringConcat => "`string.concat`",
+            Self::FileLevelUsing => "file-level and global `using` directives",
+            Self::NamedMappingParameter => "named mapping parameters",
+            Self::UserDefinedOperator => "user-defined operators",
+            Self::FileLevelEvent => "file-level events",
+            Self::Transient => "transient storage",
+        }
+    }
+}
+
+/// The minimum Solidity version required by a source.
+#[derive(Clone, Debug)]
+pub(crate) struct MinVersion {
+    /// The minimum version.
+    pub(crate) version: semver::Version,
+    /// The first use of the feature that requires `version`, if any.
+    pub(crate) feature: Option<(Feature, Span)>,
+}
+
+/// Infers the minimum Solidity version required by the given source, and warns about
+/// `pragma solidity` requirements that allow older versions.
+#[instrument(name = "min_version", level = "debug", skip_all)]
+pub(crate) fn check(sess: &Session, ast: &ast::SourceUnit<'_>) -> MinVersion {
+    let mut visitor = FeatureVisitor { feature: None, in_contract: false };
+    let _ = visitor.visit_source_unit(ast);
+    let min = MinVersion {
+        version: visitor.feature.map_or(MIN_SUPPORTED_VERSION, |(f, _)| f.version()),
+        feature: visitor.feature,
+    };
+
+    let Some((feature, feature_span)) = min.feature else { return min };
+    for item in ast.items.iter() {
+        let ast::ItemKind::Pragma(pragma) = &item.kind else { continue };
+        let ast::PragmaTokens::Version(name, req) = &pragma.tokens else { continue };
+        if name.name != sym::solidity {
+            continue;
+        }
+        let Some(oldest) = oldest_release(req) else { continue };
+        if oldest >= min.version {
+            continue;
+        }
+        let msg = "version requirement allows compiler versions that are too old for this file";
+        let note = format!("using {} requires Solidity {}", feature.description(), min.version);
+        sess.dcx
+            .warn(msg)
+            .span(item.span)
+            .span_note(feature_span, note)
+            .note(format!("the oldest allowed version is {oldest}"))
+            .help(format!(
+                "require at least the minimum version: `pragma solidity ^{}`",
+                min.version
+            ))
+            .emit();
+    }
+    min
+}
+
+/// Returns the oldest released compiler version that satisfies the requirement.
+fn oldest_release(req: &ast::SemverReq<'_>) -> Option<semver::Version> {
+    let current = (SOLC_VERSION.minor, SOLC_VERSION.patch);
+    let releases = RELEASES.iter().copied().chain(std::iter::once(current));
+    releases.flat_map(|(minor, last)| (0..=last).map(move |patch| (minor, patch))).find_map(
+        |(minor, patch)| {
+            let version = semver::Version::new(0, minor, patch);
+            req.matches(&version.clone().into()).then_some(version)
+        },
+    )
+}
+
+/// Finds the feature that requires the newest version.
+struct FeatureVisitor {
+    feature: Option<(Feature, Span)>,
+    in_contract: bool,
+}
+
+impl FeatureVisitor {
+    fn use_feature(&mut self, feature: Feature, span: Span) {
+        if self.feature.is_none_or(|(f, _)| feature.version() > f.version()) {
+            self.feature = Some((feature, span));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FeatureVisitor {
+    type BreakValue = Never;
+
+    fn visit_item(&mut self, item: &'ast ast::Item<'ast>) -> ControlFlow<Self::BreakValue> {
+        let file_level = !self.in_contract;
+        match &item.kind {
+            ast::ItemKind::Contract(contract) => {
+                if contract.kind.is_abstract_contract() {
+                    self.use_feature(Feature::AbstractContract, item.span);
+                }
+                self.in_contract = true;
+                let r = self.walk_item(item);
+                self.in_contract = false;
+                return r;
+            }
+            ast::ItemKind::Function(f) => match f.kind {
+                ast::FunctionKind::Constructor => {
+                    self.use_feature(Feature::ConstructorKeyword, item.span)
+                }
+                ast::FunctionKind::Receive | ast::FunctionKind::Fallback => {
+                    self.use_feature(Feature::ReceiveOrFallback, item.span)
+                }
+                _ if file_level => self.use_feature(Feature::FileLevelFunction, item.span),
+                _ => {}
+            },
+            ast::ItemKind::Variable(var) => {
+                match var.mutability {
+                    Some(ast::VarMut::Immutable) => self.use_feature(Feature::Immutable, item.span),
+                    Some(ast::VarMut::Constant) if file_level => {
+                        self.use_feature(Feature::FileLevelConstant, item.span)
+                    }
+                    _ => {}
+                }
+                if var.data_location == Some(ast::DataLocation::Transient) {
+                    self.use_feature(Feature::Transient, item.span);
+                }
+            }
+            ast::ItemKind::Struct(_) | ast::ItemKind::Enum(_) if file_level => {
+                self.use_feature(Feature::FileLevelStructOrEnum, item.span)
+            }
+            ast::ItemKind::Udvt(_) => self.use_feature(Feature::Udvt, item.span),
+            ast::ItemKind::Error(_) => self.use_feature(Feature::CustomError, item.span),
+            ast::ItemKind::Event(_) if file_level => {
+                self.use_feature(Feature::FileLevelEvent, item.span)
+            }
+            ast::ItemKind::Using(using) => {
+                if using.global || file_level {
+                    self.use_feature(Feature::FileLevelUsing, item.span);
+                }
+                if let ast::UsingList::Multiple(list) = &using.list {
+                    if list.iter().any(|(_, op)| op.is_some()) {
+                        self.use_feature(Feature::UserDefinedOperator, item.span);
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_item(item)
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast ast::Stmt<'ast>) -> ControlFlow<Self::BreakValue> {
+        match stmt.kind {
+            ast::StmtKind::Emit(..) => self.use_feature(Feature::Emit, stmt.span),
+            ast::StmtKind::Revert(..) => self.use_feature(Feature::CustomError, stmt.span),
+            ast::StmtKind::Try(_) => self.use_feature(Feature::TryCatch, stmt.span),
+            ast::StmtKind::UncheckedBlock(_) => self.use_feature(Feature::Unchecked, stmt.span),
+            _ => {}
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'ast ast::Expr<'ast>) -> ControlFlow<Self::BreakValue> {
+        if let ast::ExprKind::Member(base, member) = &expr.kind {
+            if let ast::ExprKind::Type(ty) = &base.kind {
+                if member.name == sym::concat {
+                    match ty.kind {
+                        ast::TypeKind::Elementary(ElementaryType::Bytes) => {
+                            self.use_feature(Feature::BytesConcat, expr.span)
+                        }
+                        ast::TypeKind::Elementary(ElementaryType::String) => {
+                            self.use_feature(Feature::StringConcat, expr.span)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        self.walk_expr(expr)
+    }
+
+    fn visit_ty(&mut self, ty: &'ast ast::Type<'ast>) -> ControlFlow<Self::BreakValue> {
+        if let ast::TypeKind::Mapping(mapping) = &ty.kind {
+            if mapping.key_name.is_some() || mapping.value_name.is_some() {
+                self.use_feature(Feature::NamedMappingParameter, ty.span);
+            }
+        }
+        self.walk_ty(ty)
+    }
+
+    fn visit_lit(&mut self, lit: &'ast ast::Lit) -> ControlFlow<Self::BreakValue> {
+        if let ast::LitKind::Str(StrKind::Unicode, _) = lit.kind {
+            self.use_feature(Feature::UnicodeString, lit.span);
+        }
+        self.walk_lit(lit)
+    }
+}
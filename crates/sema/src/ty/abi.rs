@@ -1,7 +1,8 @@
-use super::{Gcx, Ty, TyKind};
-use crate::hir;
+use super::{Gcx, Ty, TyKind, TypeckResults};
+use crate::hir::{self, Res, Visit};
 use alloy_json_abi as json;
-use solar_ast::ElementaryType;
+use solar_ast::{ElementaryType, StateMutability, Visibility};
+use solar_data_structures::{map::FxIndexSet, Never};
 use std::{fmt, ops::ControlFlow};
 
 impl<'gcx> Gcx<'gcx> {
@@ -41,15 +42,9 @@ impl<'gcx> Gcx<'gcx> {
         for f in self.interface_functions(id) {
             items.push(self.function_abi(f.id).into());
         }
-        // TODO: Does not include referenced items.
-        // See solc `interfaceEvents` and `interfaceErrors`.
-        for item in self.hir.contract_item_ids(id) {
-            match item {
-                hir::ItemId::Event(id) => items.push(self.event_abi(id).into()),
-                hir::ItemId::Error(id) => items.push(self.error_abi(id).into()),
-                _ => {}
-            }
-        }
+        let (events, errors) = self.interface_events_and_errors(id);
+        items.extend(events.into_iter().map(|id| self.event_abi(id).into()));
+        items.extend(errors.into_iter().map(|id| self.error_abi(id).into()));
 
         // https://github.com/ethereum/solidity/blob/87d86bfba64d8b88537a4a85c1d71f521986b614/libsolidity/interface/ABI.cpp#L43-L47
         fn cmp_key<'a>(item: &'a json::AbiItem<'_>) -> impl Ord + use<'a> {
@@ -61,6 +56,42 @@ impl<'gcx> Gcx<'gcx> {
         items
     }
 
+    /// Returns the events and errors that are part of the interface of the given contract.
+    ///
+    /// These are the ones declared in the contract and its bases, and the ones that are emitted or
+    /// reverted with in the code reachable from the contract, including library and free
+    /// functions.
+    ///
+    /// See solc `interfaceEvents` and `interfaceErrors`.
+    pub fn interface_events_and_errors(
+        self,
+        id: hir::ContractId,
+    ) -> (FxIndexSet<hir::EventId>, FxIndexSet<hir::ErrorId>) {
+        let mut collector = InterfaceCollector {
+            gcx: self,
+            results: TypeckResults::default(),
+            functions: FxIndexSet::default(),
+            events: FxIndexSet::default(),
+            errors: FxIndexSet::default(),
+        };
+        for item in self.hir.contract_item_ids(id) {
+            match item {
+                hir::ItemId::Event(id) => _ = collector.events.insert(id),
+                hir::ItemId::Error(id) => _ = collector.errors.insert(id),
+                hir::ItemId::Function(id) => _ = collector.functions.insert(id),
+                _ => {}
+            }
+        }
+        // Called functions are appended to `functions` while visiting.
+        let mut i = 0;
+        while let Some(&f) = collector.functions.get_index(i) {
+            collector.results = self.typeck(f.into());
+            let _ = collector.visit_nested_function(f);
+            i += 1;
+        }
+        (collector.events, collector.errors)
+    }
+
     fn function_abi(self, id: hir::FunctionId) -> json::Function {
         let f = self.hir.function(id);
         json::Function {
@@ -133,6 +164,77 @@ impl<'gcx> Gcx<'gcx> {
     }
 }
 
+/// Collects the events and errors used in the functions reachable from a contract.
+struct InterfaceCollector<'gcx> {
+    gcx: Gcx<'gcx>,
+    /// The type checking results of the visited function.
+    results: TypeckResults<'gcx>,
+    functions: FxIndexSet<hir::FunctionId>,
+    events: FxIndexSet<hir::EventId>,
+    errors: FxIndexSet<hir::ErrorId>,
+}
+
+impl InterfaceCollector<'_> {
+    fn res(&self, expr: &hir::Expr<'_>) -> Option<Res> {
+        let expr = expr.peel_parens();
+        match self.results.expr_res(expr.id) {
+            Some(res) => Some(res),
+            None => match expr.kind {
+                hir::ExprKind::Ident(&[res]) => Some(res),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for InterfaceCollector<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        if let hir::StmtKind::Emit(expr) = stmt.kind {
+            if let hir::ExprKind::Call(callee, ..) = expr.kind {
+                if let Some(Res::Item(hir::ItemId::Event(id))) = self.res(callee) {
+                    self.events.insert(id);
+                }
+            }
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            // `revert E()` and `require(c, E())`.
+            hir::ExprKind::Call(callee, ..) => {
+                if let Some(Res::Item(hir::ItemId::Error(id))) = self.res(callee) {
+                    self.errors.insert(id);
+                }
+            }
+            // Library and free functions are not part of the contract, so they have to be followed
+            // through internal calls. Public library functions are called with `delegatecall`, so
+            // their events and errors are not part of the contract.
+            hir::ExprKind::Ident(_) | hir::ExprKind::Member(..) => {
+                if let Some(Res::Item(hir::ItemId::Function(id))) = self.res(expr) {
+                    let f = self.gcx.hir.function(id);
+                    let is_internal = self.results.expr_ty(expr.id).is_some_and(|ty| {
+                        matches!(ty.kind, TyKind::FnPtr(f) if f.visibility <= Visibility::Internal)
+                    });
+                    if is_internal
+                        && f.contract.is_none_or(|c| self.gcx.hir.contract(c).kind.is_library())
+                    {
+                        self.functions.insert(id);
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
 fn json_state_mutability(s: hir::StateMutability) -> json::StateMutability {
     match s {
         hir::StateMutability::Pure => json::StateMutability::Pure,
@@ -246,26 +348,26 @@ impl<'gcx, W: fmt::Write> TyAbiPrinter<'gcx, W> {
 /// This is mainly used in the `internalType` field of the ABI.
 ///
 /// Example: https://github.com/ethereum/solidity/blob/9d7cc42bc1c12bb43e9dccf8c6c36833fdfcbbca/libsolidity/ast/Types.cpp#L2352-L2358
-struct TySolcPrinter<'gcx, W> {
+pub(crate) struct TySolcPrinter<'gcx, W> {
     gcx: Gcx<'gcx>,
     buf: W,
     data_locations: bool,
 }
 
 impl<'gcx, W: fmt::Write> TySolcPrinter<'gcx, W> {
-    fn new(gcx: Gcx<'gcx>, buf: W) -> Self {
+    pub(crate) fn new(gcx: Gcx<'gcx>, buf: W) -> Self {
         Self { gcx, buf, data_locations: false }
     }
 
     /// Whether to print data locations for reference types.
     ///
     /// Default: `false`.
-    fn data_locations(mut self, yes: bool) -> Self {
+    pub(crate) fn data_locations(mut self, yes: bool) -> Self {
         self.data_locations = yes;
         self
     }
 
-    fn print(&mut self, ty: Ty<'gcx>) -> fmt::Result {
+    pub(crate) fn print(&mut self, ty: Ty<'gcx>) -> fmt::Result {
         match ty.kind {
             TyKind::Elementary(ty) => {
                 ty.write_abi_str(&mut self.buf)?;
@@ -279,7 +381,21 @@ impl<'gcx, W: fmt::Write> TySolcPrinter<'gcx, W> {
                 self.buf.write_str(if c.kind.is_library() { "library" } else { "contract" })?;
                 write!(self.buf, " {}", c.name)
             }
-            TyKind::FnPtr(_) => self.buf.write_str("function"),
+            TyKind::FnPtr(f) => {
+                self.buf.write_str("function ")?;
+                self.print_tuple(f.parameters)?;
+                if f.visibility == Visibility::External {
+                    self.buf.write_str(" external")?;
+                }
+                if f.state_mutability != StateMutability::NonPayable {
+                    write!(self.buf, " {}", f.state_mutability)?;
+                }
+                if !f.returns.is_empty() {
+                    self.buf.write_str(" returns ")?;
+                    self.print_tuple(f.returns)?;
+                }
+                Ok(())
+            }
             TyKind::Struct(id) => {
                 write!(self.buf, "struct {}", self.gcx.item_canonical_name(id))
             }
@@ -301,17 +417,43 @@ impl<'gcx, W: fmt::Write> TySolcPrinter<'gcx, W> {
                 write!(self.buf, "[{len}]")
             }
 
-            TyKind::StringLiteral(..)
-            | TyKind::IntLiteral(_)
-            | TyKind::Tuple(_)
-            | TyKind::Mapping(..)
-            | TyKind::Error(..)
-            | TyKind::Event(..)
-            | TyKind::Module(_)
-            | TyKind::BuiltinModule(_)
-            | TyKind::Type(_)
-            | TyKind::Meta(_)
-            | TyKind::Err(_) => panic!("printing unsupported type as solc: {ty:?}"),
+            TyKind::StringLiteral(..) => self.buf.write_str("string literal"),
+            TyKind::IntLiteral(_) => self.buf.write_str("integer literal"),
+            TyKind::Tuple(tys) => {
+                self.buf.write_str("tuple")?;
+                self.print_tuple(tys)
+            }
+            TyKind::Mapping(key, value) => {
+                self.buf.write_str("mapping(")?;
+                self.print(key)?;
+                self.buf.write_str(" => ")?;
+                self.print(value)?;
+                self.buf.write_str(")")
+            }
+            TyKind::Error(_, id) => write!(self.buf, "error {}", self.gcx.item_name(id)),
+            TyKind::Event(_, id) => write!(self.buf, "event {}", self.gcx.item_name(id)),
+            TyKind::Module(id) => {
+                let file = &self.gcx.hir.source(id).file;
+                write!(self.buf, "module \"{}\"", file.name.display())
+            }
+            TyKind::BuiltinModule(builtin) => write!(self.buf, "{}", builtin.name()),
+            TyKind::Type(ty) | TyKind::Meta(ty) => {
+                self.buf.write_str("type(")?;
+                self.print(ty)?;
+                self.buf.write_str(")")
+            }
+            TyKind::Err(_) => self.buf.write_str("<error>"),
         }
     }
+
+    fn print_tuple(&mut self, tys: &[Ty<'gcx>]) -> fmt::Result {
+        self.buf.write_str("(")?;
+        for (i, &ty) in tys.iter().enumerate() {
+            if i > 0 {
+                self.buf.write_str(",")?;
+            }
+            self.print(ty)?;
+        }
+        self.buf.write_str(")")
+    }
 }
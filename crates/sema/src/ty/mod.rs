@@ -5,7 +5,7 @@ use crate::{
 };
 use alloy_primitives::{keccak256, Selector, B256, U256};
 use either::Either;
-use solar_ast::{DataLocation, StateMutability, TypeSize, Visibility};
+use solar_ast::{DataLocation, ElementaryType, StateMutability, TypeSize, Visibility};
 use solar_data_structures::{
     fmt::{from_fn, or_list},
     map::{FxBuildHasher, FxHashMap, FxHashSet},
@@ -13,6 +13,7 @@ use solar_data_structures::{
     BumpExt,
 };
 use solar_interface::{
+    config::EvmVersion,
     diagnostics::{DiagCtxt, ErrorGuaranteed},
     Ident, Session, Span,
 };
@@ -96,6 +97,67 @@ impl<'gcx> IntoIterator for InterfaceFunctions<'gcx> {
     }
 }
 
+/// The type and resolution of a type checked expression.
+#[derive(Clone, Copy, Debug)]
+pub struct TypedExpr<'gcx> {
+    /// The expression ID.
+    pub id: hir::ExprId,
+    /// The span of the expression.
+    pub span: Span,
+    /// The type of the expression.
+    pub ty: Ty<'gcx>,
+    /// The declaration that the expression refers to, after overload resolution.
+    ///
+    /// This is only set for identifiers and member accesses that refer to a declaration or
+    /// builtin, and for operations on user-defined value types, which refer to the function that
+    /// is bound to the operator.
+    pub res: Option<hir::Res>,
+}
+
+/// The types of all the expressions in a function body or variable initializer.
+///
+/// Return type of [`Gcx::typeck`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TypeckResults<'gcx> {
+    /// The checked expressions, sorted by ID.
+    exprs: &'gcx [TypedExpr<'gcx>],
+}
+
+impl<'gcx> TypeckResults<'gcx> {
+    pub(crate) fn new(exprs: &'gcx [TypedExpr<'gcx>]) -> Self {
+        debug_assert!(exprs.is_sorted_by_key(|expr| expr.id));
+        Self { exprs }
+    }
+
+    /// Returns all the checked expressions, sorted by ID.
+    pub fn exprs(&self) -> &'gcx [TypedExpr<'gcx>] {
+        self.exprs
+    }
+
+    /// Returns the checked expression with the given ID.
+    pub fn expr(&self, id: hir::ExprId) -> Option<&'gcx TypedExpr<'gcx>> {
+        self.exprs.binary_search_by_key(&id, |expr| expr.id).ok().map(|i| &self.exprs[i])
+    }
+
+    /// Returns the type of the given expression.
+    pub fn expr_ty(&self, id: hir::ExprId) -> Option<Ty<'gcx>> {
+        self.expr(id).map(|expr| expr.ty)
+    }
+
+    /// Returns the resolution of the given expression.
+    pub fn expr_res(&self, id: hir::ExprId) -> Option<hir::Res> {
+        self.expr(id).and_then(|expr| expr.res)
+    }
+
+    /// Returns the innermost expression that contains the given span.
+    pub fn expr_at(&self, span: Span) -> Option<&'gcx TypedExpr<'gcx>> {
+        self.exprs
+            .iter()
+            .filter(|expr| expr.span.contains(span))
+            .min_by_key(|expr| expr.span.hi() - expr.span.lo())
+    }
+}
+
 /// Recursiveness of a type.
 #[derive(Clone, Copy, Debug)]
 pub enum Recursiveness {
@@ -393,18 +455,24 @@ impl<'gcx> Gcx<'gcx> {
     /// Computes the [`Ty`] of the given [`hir::Type`]. Not cached.
     pub fn type_of_hir_ty(self, ty: &hir::Type<'_>) -> Ty<'gcx> {
         let kind = match ty.kind {
-            hir::TypeKind::Elementary(ty) => TyKind::Elementary(ty),
+            hir::TypeKind::Elementary(ty) => match ty {
+                // `int` and `uint` are aliases for `int256` and `uint256`.
+                ElementaryType::Int(size) => return self.types.int_(size),
+                ElementaryType::UInt(size) => return self.types.uint_(size),
+                ElementaryType::FixedBytes(size) => return self.types.fixed_bytes_(size),
+                _ => TyKind::Elementary(ty),
+            },
             hir::TypeKind::Array(array) => {
                 let ty = self.type_of_hir_ty(&array.element);
                 match array.size {
-                    Some(size) => match crate::eval::ConstantEvaluator::new(self).eval(size) {
-                        Ok(int) => {
-                            if int.data.is_zero() {
+                    Some(size) => match crate::eval::ConstantEvaluator::new(self).eval_u256(size) {
+                        Ok(len) => {
+                            if len.is_zero() {
                                 let msg = "array length must be greater than zero";
                                 let guar = self.dcx().err(msg).span(size.span).emit();
-                                TyKind::Array(self.mk_ty_err(guar), int.data)
+                                TyKind::Array(self.mk_ty_err(guar), len)
                             } else {
-                                TyKind::Array(ty, int.data)
+                                TyKind::Array(ty, len)
                             }
                         }
                         Err(guar) => TyKind::Array(self.mk_ty_err(guar), U256::from(1)),
@@ -442,6 +510,42 @@ impl<'gcx> Gcx<'gcx> {
         }
     }
 
+    /// Returns the innermost type checked expression that contains the given span.
+    ///
+    /// This type checks the enclosing contract, function or variable if it hasn't been already.
+    /// The bodies of getters are not considered since they are not part of the source.
+    pub fn expr_at(self, span: Span) -> Option<&'gcx TypedExpr<'gcx>> {
+        let contracts = self
+            .hir
+            .contracts_enumerated()
+            .filter(|(_, c)| !c.bases_args.is_empty() && c.span.contains(span))
+            .map(|(id, _)| hir::ItemId::from(id));
+        let functions = self
+            .hir
+            .functions_enumerated()
+            .filter(|(_, f)| {
+                (f.body.is_some() || !f.modifiers.is_empty())
+                    && !f.is_getter()
+                    && f.span.contains(span)
+            })
+            .map(|(id, _)| hir::ItemId::from(id));
+        let variables = self
+            .hir
+            .variables_enumerated()
+            .filter(|(_, v)| v.initializer.is_some() && v.span.contains(span))
+            .map(|(id, _)| hir::ItemId::from(id));
+        contracts
+            .chain(functions)
+            .chain(variables)
+            .filter_map(|id| self.typeck(id).expr_at(span))
+            .min_by_key(|expr| expr.span.hi() - expr.span.lo())
+    }
+
+    /// Returns the type of the innermost type checked expression that contains the given span.
+    pub fn type_at(self, span: Span) -> Option<Ty<'gcx>> {
+        self.expr_at(span).map(|expr| expr.ty)
+    }
+
     /// Returns the type of the given [`hir::Res`].
     pub fn type_of_res(self, res: hir::Res) -> Ty<'gcx> {
         match res {
@@ -589,6 +693,26 @@ fn item_selector(gcx: _, id: hir::ItemId) -> B256 {
     keccak256(gcx.item_signature(id))
 }
 
+/// Returns the compile-time value of the given constant variable, converted to its declared type.
+///
+/// Returns `None` if the variable is not constant, or if the initializer cannot be evaluated at
+/// compile time, for example because it calls a function, which is allowed by solc.
+pub fn constant_value(gcx: _, id: hir::VariableId) -> Option<&'gcx crate::eval::ConstValue> {
+    if !gcx.hir.variable(id).is_constant() {
+        return None;
+    }
+    let value = crate::eval::ConstantEvaluator::new(gcx).try_eval_variable(id).ok()?;
+    Some(gcx.bump().alloc(value))
+}
+
+/// Detects cyclic dependencies between the initializers of constant and immutable variables,
+/// emitting an error for each cycle.
+///
+/// Returns the variables that are part of a cycle.
+pub fn initializer_cycles(gcx: _, _unit: ()) -> &'gcx FxHashMap<hir::VariableId, ErrorGuaranteed> {
+    gcx.bump().alloc(crate::eval::initializer_cycles(gcx))
+}
+
 /// Returns the type of the given item.
 pub fn type_of_item(gcx: _, id: hir::ItemId) -> Ty<'gcx> {
     let kind = match id {
@@ -633,6 +757,21 @@ pub fn type_of_item(gcx: _, id: hir::ItemId) -> Ty<'gcx> {
     gcx.mk_ty(kind)
 }
 
+/// Type checks the given function body or variable initializer, returning the types of all of
+/// its expressions.
+///
+/// Items other than functions and state or file-level variables have empty results.
+pub fn typeck(gcx: _, id: hir::ItemId) -> TypeckResults<'gcx> {
+    crate::typeck::typeck(gcx, id)
+}
+
+/// Returns the control-flow graph of the given function or modifier body.
+///
+/// Returns `None` if the function has no body.
+pub fn cfg(gcx: _, id: hir::FunctionId) -> Option<&'gcx crate::cfg::Cfg<'gcx>> {
+    crate::cfg::build(gcx, id)
+}
+
 /// Returns the types of the fields of the given struct.
 pub fn struct_field_types(gcx: _, id: hir::StructId) -> &'gcx [Ty<'gcx>] {
     gcx.mk_ty_iter(gcx.hir.strukt(id).fields.iter().map(|&f| gcx.type_of_item(f.into())))
@@ -690,10 +829,31 @@ pub fn struct_recursiveness(gcx: _, id: hir::StructId) -> Recursiveness {
     }
 }
 
+/// Returns the storage layout of the given contract, including inherited state variables.
+///
+/// Constants and immutables are not stored in storage and are not included.
+pub fn storage_layout(gcx: _, id: hir::ContractId) -> crate::layout::StorageLayout<'gcx> {
+    crate::layout::storage_layout(gcx, id)
+}
+
+/// Returns the storage layout of the given struct.
+pub fn struct_storage_layout(gcx: _, id: hir::StructId) -> crate::layout::StructLayout<'gcx> {
+    crate::layout::struct_storage_layout(gcx, id)
+}
+
 /// Returns the members of the given type.
 pub fn members_of(gcx: _, ty: Ty<'gcx>) -> members::MemberList<'gcx> {
     members::members_of(gcx, ty)
 }
+
+/// Returns the functions attached to a type by the `using for` directives that are in effect in
+/// the given source and contract.
+///
+/// The first parameter of the returned function types is bound to the value of the type.
+pub fn attached_functions(gcx: _, key: (hir::SourceId, Option<hir::ContractId>, Ty<'gcx>)) -> members::MemberList<'gcx> {
+    let (source, contract, ty) = key;
+    gcx.bump().alloc_vec(members::attached_functions(gcx, ty, source, contract))
+}
 }
 
 fn var_type<'gcx>(gcx: Gcx<'gcx>, var: &'gcx hir::Variable<'gcx>, ty: Ty<'gcx>) -> Ty<'gcx> {
@@ -782,12 +942,26 @@ fn var_type<'gcx>(gcx: Gcx<'gcx>, var: &'gcx hir::Variable<'gcx>, ty: Ty<'gcx>)
                 if mut_specified {
                     let msg = "transient cannot be used as data location for constant or immutable variables";
                     gcx.dcx().err(msg).span(var.span).emit();
-                }
-                if var.initializer.is_some() {
+                } else if var.initializer.is_some() {
                     let msg =
                         "initialization of transient storage state variables is not supported";
                     gcx.dcx().err(msg).span(var.span).emit();
                 }
+                let evm_version = gcx.sess.opts.evm_version;
+                if evm_version < EvmVersion::Cancun {
+                    crate::builtins::unsupported_evm_version(
+                        gcx.dcx(),
+                        var.span,
+                        "transient",
+                        EvmVersion::Cancun,
+                        evm_version,
+                    );
+                }
+                if has_reference_or_mapping_type && ty.has_error().is_ok() {
+                    let msg = "transient storage state variables must have a value type";
+                    let note = format!("`{}` is not a value type", ty.display(gcx));
+                    gcx.dcx().err(msg).span(var.span).note(note).emit();
+                }
                 Transient
             }
             Some(_) => unreachable!(),
@@ -797,7 +971,8 @@ fn var_type<'gcx>(gcx: Gcx<'gcx>, var: &'gcx hir::Variable<'gcx>, ty: Ty<'gcx>)
     } else {
         match var_loc {
             Some(loc @ (Memory | Storage | Calldata)) => loc,
-            Some(Transient) => unimplemented!(),
+            // Only allowed for state variables, rejected above otherwise.
+            Some(Transient) => unreachable!(),
             None => {
                 assert!(!has_reference_or_mapping_type, "data location not properly set");
                 Memory
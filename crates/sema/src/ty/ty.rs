@@ -30,7 +30,16 @@ impl<'gcx> Ty<'gcx> {
         gcx.mk_ty(kind)
     }
 
-    // TODO: with_loc_if_ref ?
+    /// Returns the type with the given data location if it is a reference type, or `self`
+    /// otherwise.
+    pub fn with_loc_if_ref(self, gcx: Gcx<'gcx>, loc: DataLocation) -> Self {
+        if self.peel_refs().is_reference_type() {
+            self.with_loc(gcx, loc)
+        } else {
+            self
+        }
+    }
+
     pub fn with_loc(self, gcx: Gcx<'gcx>, loc: DataLocation) -> Self {
         let mut ty = self;
         if let TyKind::Ref(inner, l2) = self.kind {
@@ -143,6 +152,267 @@ impl<'gcx> Ty<'gcx> {
         }
     }
 
+    /// Returns the data location of the type, if it is a reference.
+    #[inline]
+    pub fn loc(self) -> Option<DataLocation> {
+        match self.kind {
+            TyKind::Ref(_, loc) => Some(loc),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the type is a signed or unsigned integer type.
+    ///
+    /// Does not include integer literals.
+    #[inline]
+    pub fn is_integer(self) -> bool {
+        matches!(self.kind, TyKind::Elementary(ElementaryType::Int(_) | ElementaryType::UInt(_)))
+    }
+
+    /// Returns `true` if the type is a signed integer type.
+    #[inline]
+    pub fn is_signed_integer(self) -> bool {
+        matches!(self.kind, TyKind::Elementary(ElementaryType::Int(_)))
+    }
+
+    /// Returns `true` if the type is an unsigned integer type.
+    #[inline]
+    pub fn is_unsigned_integer(self) -> bool {
+        matches!(self.kind, TyKind::Elementary(ElementaryType::UInt(_)))
+    }
+
+    /// Returns `true` if the type is a signed or unsigned fixed-point number type.
+    #[inline]
+    pub fn is_fixed_point(self) -> bool {
+        matches!(
+            self.kind,
+            TyKind::Elementary(ElementaryType::Fixed(..) | ElementaryType::UFixed(..))
+        )
+    }
+
+    /// Returns `true` if the type is a fixed-size byte array type.
+    #[inline]
+    pub fn is_fixed_bytes(self) -> bool {
+        matches!(self.kind, TyKind::Elementary(ElementaryType::FixedBytes(_)))
+    }
+
+    /// Returns `true` if the type is an integer or string literal.
+    #[inline]
+    pub fn is_literal(self) -> bool {
+        matches!(self.kind, TyKind::IntLiteral(_) | TyKind::StringLiteral(..))
+    }
+
+    /// Returns `true` if the type is `bool`.
+    #[inline]
+    pub fn is_bool(self) -> bool {
+        matches!(self.kind, TyKind::Elementary(ElementaryType::Bool))
+    }
+
+    /// Returns `true` if the type is a number: an integer, a fixed-point number, or an integer
+    /// literal.
+    #[inline]
+    pub fn is_numeric(self) -> bool {
+        self.is_integer() || self.is_fixed_point() || matches!(self.kind, TyKind::IntLiteral(_))
+    }
+
+    /// Returns `true` if the type is `()`.
+    #[inline]
+    pub fn is_unit(self) -> bool {
+        matches!(self.kind, TyKind::Tuple([]))
+    }
+
+    /// Returns the type that a value of this type has when it is stored in a variable.
+    ///
+    /// Literals get converted to the smallest type that can hold them, and other types are
+    /// returned as-is.
+    ///
+    /// Corresponds to `mobileType` in solc.
+    pub fn mobile(self, gcx: Gcx<'gcx>) -> Self {
+        match self.kind {
+            TyKind::IntLiteral(size) => gcx.types.uint_(size),
+            TyKind::StringLiteral(..) => gcx.types.string_ref.memory,
+            _ => self,
+        }
+    }
+
+    /// Returns `true` if a value of this type can be implicitly converted to `other`.
+    ///
+    /// Types that contain errors are convertible to and from any other type, to avoid emitting
+    /// errors that are caused by previous ones.
+    ///
+    /// Data locations are only checked for `calldata` references, which can't be created from
+    /// other locations. The other restrictions are checked in the data location pass.
+    pub fn convert_implicit_to(self, other: Self, gcx: Gcx<'gcx>) -> bool {
+        use ElementaryType::*;
+
+        if self == other || self.has_error().is_err() || other.has_error().is_err() {
+            return true;
+        }
+        match (&self.kind, &other.kind) {
+            (&TyKind::Elementary(from), &TyKind::Elementary(to)) => match (from, to) {
+                (Address(true), Address(false)) => true,
+                (UInt(a), UInt(b)) | (Int(a), Int(b)) => a.bits() <= b.bits(),
+                (UInt(a), Int(b)) => a.bits() < b.bits(),
+                (FixedBytes(a), FixedBytes(b)) => a.bytes() <= b.bytes(),
+                // TODO: Check the range of fixed-point numbers.
+                (UInt(_) | Int(_) | Fixed(..) | UFixed(..), Fixed(..) | UFixed(..)) => true,
+                _ => false,
+            },
+            (&TyKind::IntLiteral(size), &TyKind::Elementary(to)) => match to {
+                UInt(b) | Int(b) => size.bytes() <= b.bytes(),
+                Fixed(..) | UFixed(..) => true,
+                // TODO: Only `0` and hex literals of the same length are allowed.
+                FixedBytes(_) => true,
+                _ => false,
+            },
+            (&TyKind::IntLiteral(_), &TyKind::IntLiteral(_)) => true,
+            (&TyKind::StringLiteral(utf8, size), _) => {
+                if other.is_ref_at(DataLocation::Calldata) {
+                    return false;
+                }
+                match other.peel_refs().kind {
+                    TyKind::Elementary(String) => utf8,
+                    TyKind::Elementary(Bytes) => true,
+                    TyKind::Elementary(FixedBytes(n)) => size.bytes_raw() <= n.bytes(),
+                    _ => false,
+                }
+            }
+            (&TyKind::Ref(from, from_loc), &TyKind::Ref(to, to_loc)) => {
+                if to_loc == DataLocation::Calldata && from_loc != DataLocation::Calldata {
+                    return false;
+                }
+                from.convert_implicit_to_ignoring_loc(to)
+            }
+            // Unspecified data locations are only found in builtins.
+            (&TyKind::Ref(from, _), _) => from.convert_implicit_to_ignoring_loc(other),
+            (_, &TyKind::Ref(to, _)) => self.convert_implicit_to_ignoring_loc(to),
+            (&TyKind::Contract(from), &TyKind::Contract(to)) => {
+                gcx.hir.contract(from).linearized_bases.contains(&to)
+            }
+            (&TyKind::FnPtr(from), &TyKind::FnPtr(to)) => {
+                use StateMutability::*;
+
+                let mutability_ok = match (from.state_mutability, to.state_mutability) {
+                    (a, b) if a == b => true,
+                    (_, NonPayable) => true,
+                    (Pure, View) => true,
+                    _ => false,
+                };
+                mutability_ok
+                    && (from.visibility == Visibility::External)
+                        == (to.visibility == Visibility::External)
+                    && from.parameters == to.parameters
+                    && from.returns == to.returns
+            }
+            (&TyKind::Tuple(from), &TyKind::Tuple(to)) => {
+                from.len() == to.len()
+                    && std::iter::zip(from, to).all(|(&a, &b)| a.convert_implicit_to(b, gcx))
+            }
+            _ => false,
+        }
+    }
+
+    /// Same as [`convert_implicit_to`](Self::convert_implicit_to), but `self` and `other` are
+    /// both the inner types of references.
+    fn convert_implicit_to_ignoring_loc(self, other: Self) -> bool {
+        let (from, to) = (self.peel_refs(), other.peel_refs());
+        from == to
+            || from.has_error().is_err()
+            || to.has_error().is_err()
+            || match (&from.kind, &to.kind) {
+                (&TyKind::Array(a, n), &TyKind::Array(b, m)) => {
+                    n == m && a.convert_implicit_to_ignoring_loc(b)
+                }
+                (&TyKind::DynArray(a), &TyKind::DynArray(b)) => {
+                    a.convert_implicit_to_ignoring_loc(b)
+                }
+                _ => false,
+            }
+    }
+
+    /// Returns `true` if a value of this type can be explicitly converted to `other`, as in
+    /// `uint8(x)`.
+    ///
+    /// The values of literals are not checked, only their types.
+    ///
+    /// Corresponds to `isExplicitlyConvertibleTo` in solc.
+    pub fn convert_explicit_to(self, other: Self, gcx: Gcx<'gcx>) -> bool {
+        use ElementaryType::*;
+
+        if self.convert_implicit_to(other, gcx) {
+            return true;
+        }
+        match (&self.kind, &other.kind) {
+            (&TyKind::Elementary(from), &TyKind::Elementary(to)) => match (from, to) {
+                // Either the size or the sign can change, but not both at the same time.
+                (UInt(a), UInt(b)) | (Int(a), Int(b)) | (UInt(a), Int(b)) | (Int(a), UInt(b)) => {
+                    a == b || matches!(from, UInt(_)) == matches!(to, UInt(_))
+                }
+                (Address(_), Address(_)) => true,
+                (UInt(a), Address(false)) | (Address(false), UInt(a)) => a.bits() == 160,
+                (FixedBytes(a), Address(false)) | (Address(false), FixedBytes(a)) => {
+                    a.bytes() == 20
+                }
+                (UInt(a), FixedBytes(b)) => a.bits() == b.bits(),
+                (FixedBytes(a), UInt(b) | Int(b)) => a.bits() == b.bits(),
+                (FixedBytes(_), FixedBytes(_)) => true,
+                (Fixed(..) | UFixed(..), UInt(_) | Int(_)) => true,
+                _ => false,
+            },
+            (TyKind::IntLiteral(_), &TyKind::Elementary(Address(false))) => true,
+            (TyKind::IntLiteral(_) | TyKind::Elementary(UInt(_) | Int(_)), TyKind::Enum(_)) => true,
+            (TyKind::Enum(_), TyKind::Elementary(UInt(_) | Int(_))) => true,
+            (&TyKind::Contract(id), &TyKind::Elementary(Address(payable))) => {
+                !payable || contract_is_payable(gcx, id)
+            }
+            (&TyKind::Elementary(Address(payable)), &TyKind::Contract(id)) => {
+                payable || !contract_is_payable(gcx, id)
+            }
+            // `bytes` and `string` can be converted to each other in the same data location, and
+            // `bytes` can be converted to fixed bytes.
+            (&TyKind::Ref(from, from_loc), &TyKind::Ref(to, to_loc)) => {
+                from_loc == to_loc
+                    && matches!(from.kind, TyKind::Elementary(Bytes | String))
+                    && matches!(to.kind, TyKind::Elementary(Bytes | String))
+            }
+            (&TyKind::Ref(from, _), &TyKind::Elementary(FixedBytes(_))) => {
+                matches!(from.kind, TyKind::Elementary(Bytes))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the common type of `self` and `other`, that is the type that both can be
+    /// implicitly converted to, if any.
+    ///
+    /// Literals are only kept if both types are literals, otherwise their mobile type is used.
+    pub fn common_type(self, other: Self, gcx: Gcx<'gcx>) -> Option<Self> {
+        match (&self.kind, &other.kind) {
+            (&TyKind::IntLiteral(a), &TyKind::IntLiteral(b)) => {
+                return Some(gcx.mk_ty_int_literal(a.max(b)));
+            }
+            (&TyKind::StringLiteral(a_utf8, a), &TyKind::StringLiteral(b_utf8, b)) => {
+                return Some(gcx.mk_ty(TyKind::StringLiteral(a_utf8 && b_utf8, a.max(b))));
+            }
+            _ => {}
+        }
+        let (a, b) = (self.mobile(gcx), other.mobile(gcx));
+        if other.convert_implicit_to(a, gcx) {
+            Some(a)
+        } else if self.convert_implicit_to(b, gcx) {
+            Some(b)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a value that formats the type for diagnostics.
+    pub fn display(self, gcx: Gcx<'gcx>) -> impl fmt::Display + 'gcx {
+        solar_data_structures::fmt::from_fn(move |f| {
+            super::abi::TySolcPrinter::new(gcx, f).data_locations(true).print(self)
+        })
+    }
+
     /// Returns `true` if the type is recursive.
     pub fn is_recursive(self) -> bool {
         self.flags.contains(TyFlags::IS_RECURSIVE)
@@ -423,3 +693,12 @@ impl TyFlags {
         }
     }
 }
+
+/// Returns `true` if the contract can receive Ether with a plain transfer, that is if it has a
+/// `receive` function or a `payable` fallback function.
+fn contract_is_payable(gcx: Gcx<'_>, id: hir::ContractId) -> bool {
+    let c = gcx.hir.contract(id);
+    c.receive.is_some()
+        || c.fallback
+            .is_some_and(|f| gcx.hir.function(f).state_mutability == StateMutability::Payable)
+}
@@ -0,0 +1,1880 @@
+//! Type checking of function bodies and variable initializers.
+
+use crate::{
+    builtins::Builtin,
+    eval::{ConstValue, ConstantEvaluator, EvalErrorKind},
+    hir::{self, BinOp, BinOpKind, Res, UnOp, UnOpKind},
+    ty::{Gcx, Ty, TyFnPtr, TyKind, TypeckResults, TypedExpr},
+};
+use num_bigint::Sign;
+use num_traits::Zero;
+use solar_ast::{
+    DataLocation, ElementaryType, LitKind, TypeSize, UserDefinableOperator, Visibility,
+};
+use solar_data_structures::{map::FxHashMap, smallvec::SmallVec, BumpExt};
+use solar_interface::{
+    diagnostics::{DiagBuilder, ErrorGuaranteed},
+    sym, Ident, Span, Symbol,
+};
+
+/// Type checks the modifier invocations and body of the given function, the base constructor
+/// arguments of the given contract, or the initializer of the given state or file-level variable.
+///
+/// Other items don't contain any expressions that are type checked, and have empty results.
+pub(crate) fn typeck(gcx: Gcx<'_>, id: hir::ItemId) -> TypeckResults<'_> {
+    match id {
+        hir::ItemId::Contract(id) => {
+            let contract = gcx.hir.contract(id);
+            if contract.bases_args.is_empty() {
+                return TypeckResults::default();
+            }
+            let mut checker = TypeChecker::new(gcx, contract.source, Some(id), None);
+            for base in contract.bases_args {
+                checker.check_modifier(base);
+            }
+            checker.into_results()
+        }
+        hir::ItemId::Function(id) => {
+            let f = gcx.hir.function(id);
+            if f.body.is_none() && f.modifiers.is_empty() {
+                return TypeckResults::default();
+            }
+            let mut checker = TypeChecker::new(gcx, f.source, f.contract, Some(id));
+            for modifier in f.modifiers {
+                checker.check_modifier(modifier);
+            }
+            if let Some(body) = f.body {
+                checker.check_block(body);
+            }
+            checker.into_results()
+        }
+        hir::ItemId::Variable(id) => {
+            let var = gcx.hir.variable(id);
+            if !(var.is_state_variable() || var.is_file_level_variable()) {
+                return TypeckResults::default();
+            }
+            let Some(init) = var.initializer else { return TypeckResults::default() };
+            let mut checker = TypeChecker::new(gcx, var.source, var.contract, None);
+            checker.check_expr_coerce(init, gcx.type_of_item(id.into()));
+            checker.into_results()
+        }
+        _ => TypeckResults::default(),
+    }
+}
+
+/// A possible resolution of an expression that may refer to overloaded declarations.
+#[derive(Clone, Copy, Debug)]
+struct Candidate<'gcx> {
+    ty: Ty<'gcx>,
+    res: Option<Res>,
+}
+
+type Candidates<'gcx> = SmallVec<[Candidate<'gcx>; 2]>;
+
+struct TypeChecker<'gcx> {
+    gcx: Gcx<'gcx>,
+    /// The source in which the checked code is defined.
+    source: hir::SourceId,
+    /// The contract in which the checked code is defined, if any.
+    contract: Option<hir::ContractId>,
+    /// The function whose body is being checked, if any.
+    function: Option<hir::FunctionId>,
+    /// The types of the checked expressions.
+    types: FxHashMap<hir::ExprId, Ty<'gcx>>,
+    /// The resolutions of identifiers, member accesses and user-defined operators, after overload
+    /// resolution.
+    resolutions: FxHashMap<hir::ExprId, Res>,
+    /// The spans of the checked expressions.
+    spans: FxHashMap<hir::ExprId, Span>,
+    /// Whether the checked code is inside of an `unchecked` block.
+    unchecked: bool,
+}
+
+impl<'gcx> TypeChecker<'gcx> {
+    fn new(
+        gcx: Gcx<'gcx>,
+        source: hir::SourceId,
+        contract: Option<hir::ContractId>,
+        function: Option<hir::FunctionId>,
+    ) -> Self {
+        Self {
+            gcx,
+            source,
+            contract,
+            function,
+            types: FxHashMap::default(),
+            resolutions: FxHashMap::default(),
+            spans: FxHashMap::default(),
+            unchecked: false,
+        }
+    }
+
+    fn into_results(self) -> TypeckResults<'gcx> {
+        let mut exprs = self
+            .types
+            .iter()
+            .map(|(&id, &ty)| TypedExpr {
+                id,
+                span: self.spans[&id],
+                ty,
+                res: self.resolutions.get(&id).copied(),
+            })
+            .collect::<Vec<_>>();
+        exprs.sort_unstable_by_key(|expr| expr.id);
+        TypeckResults::new(self.gcx.bump().alloc_vec(exprs))
+    }
+
+    // --- Modifiers ---
+
+    /// Checks the arguments of a modifier invocation or base constructor call against the
+    /// parameters of the modifier or constructor.
+    fn check_modifier(&mut self, modifier: &'gcx hir::Modifier<'gcx>) {
+        let gcx = self.gcx;
+        let arg_tys =
+            modifier.args.exprs().map(|arg| self.check_expr(arg)).collect::<SmallVec<[_; 8]>>();
+        let (descr, id) = match modifier.id {
+            hir::ItemId::Contract(base) => {
+                // The arguments can also be given in another inheritance specifier or constructor.
+                if modifier.args.is_empty() {
+                    return;
+                }
+                ("constructor call", gcx.hir.contract(base).ctor.map(hir::ItemId::from))
+            }
+            id => ("modifier invocation", Some(id)),
+        };
+        let parameters = id.map(|id| gcx.item_parameter_types(id)).unwrap_or_default();
+        let names = self.parameter_names(id.map(Res::Item), parameters.len());
+        self.check_args(modifier.span, descr, parameters, names, &modifier.args, &arg_tys);
+    }
+
+    // --- Statements ---
+
+    fn check_block(&mut self, block: hir::Block<'gcx>) {
+        for stmt in block {
+            self.check_stmt(stmt);
+        }
+    }
+
+    fn check_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) {
+        match stmt.kind {
+            hir::StmtKind::DeclSingle(id) => {
+                if let Some(init) = self.gcx.hir.variable(id).initializer {
+                    self.check_expr_coerce(init, self.gcx.type_of_item(id.into()));
+                }
+            }
+            hir::StmtKind::DeclMulti(vars, expr) => {
+                let ty = self.check_expr(expr);
+                let expected = vars.iter().map(|var| {
+                    var.map(|id| (self.gcx.type_of_item(id.into()), self.gcx.hir.variable(id).span))
+                });
+                self.check_destructuring(expr, ty, expected.collect());
+            }
+            hir::StmtKind::Block(block) => self.check_block(block),
+            hir::StmtKind::UncheckedBlock(block) => {
+                let prev = std::mem::replace(&mut self.unchecked, true);
+                self.check_block(block);
+                self.unchecked = prev;
+            }
+            hir::StmtKind::Emit(expr) => {
+                if let Some(callee_ty) = self.check_emit_or_revert(expr) {
+                    if !matches!(callee_ty.kind, TyKind::Event(..)) {
+                        let msg = "expression has to be an event invocation";
+                        self.gcx.dcx().err(msg).span(expr.span).emit();
+                    }
+                }
+            }
+            hir::StmtKind::Revert(expr) => {
+                if let Some(callee_ty) = self.check_emit_or_revert(expr) {
+                    if !matches!(callee_ty.kind, TyKind::Error(..)) {
+                        let msg = "expression has to be an error";
+                        self.gcx.dcx().err(msg).span(expr.span).emit();
+                    }
+                }
+            }
+            hir::StmtKind::Return(expr) => self.check_return(stmt.span, expr),
+            hir::StmtKind::Loop(block, _) => self.check_block(block),
+            hir::StmtKind::If(cond, then, else_) => {
+                self.check_expr_coerce(cond, self.gcx.types.bool);
+                self.check_stmt(then);
+                if let Some(else_) = else_ {
+                    self.check_stmt(else_);
+                }
+            }
+            hir::StmtKind::Try(try_) => self.check_try(try_),
+            hir::StmtKind::Expr(expr) => {
+                self.check_expr(expr);
+            }
+            hir::StmtKind::Assembly(_)
+            | hir::StmtKind::Break
+            | hir::StmtKind::Continue
+            | hir::StmtKind::Placeholder
+            | hir::StmtKind::Err(_) => {}
+        }
+    }
+
+    /// Checks an `emit` or `revert` statement, returning the type of the callee if it has no
+    /// errors.
+    fn check_emit_or_revert(&mut self, expr: &'gcx hir::Expr<'gcx>) -> Option<Ty<'gcx>> {
+        self.check_expr(expr);
+        let hir::ExprKind::Call(callee, ..) = expr.kind else { unreachable!() };
+        let callee_ty = self.types[&callee.id];
+        callee_ty.has_error().ok().map(|()| callee_ty)
+    }
+
+    fn check_try(&mut self, try_: &'gcx hir::StmtTry<'gcx>) {
+        let gcx = self.gcx;
+        let ty = self.check_expr(&try_.expr);
+        let returns = self.try_call_returns(&try_.expr);
+        if returns.is_none() && ty.has_error().is_ok() {
+            let msg =
+                "try can only be used with external function calls and contract creation calls";
+            gcx.dcx().err(msg).span(try_.expr.span).emit();
+        }
+
+        let (success, catches) = try_.clauses.split_first().expect("no try clauses");
+        if let (Some(returns), [first, .., last] | [first @ last]) = (returns, success.args) {
+            if returns.len() != success.args.len() {
+                let msg = format!(
+                    "function returns {} values, but the returns clause has {} variables",
+                    returns.len(),
+                    success.args.len(),
+                );
+                let span = gcx.hir.variable(*first).span.to(gcx.hir.variable(*last).span);
+                gcx.dcx().err(msg).span(span).emit();
+            } else {
+                for (&var, &ret) in success.args.iter().zip(returns) {
+                    // The returned values are decoded into memory.
+                    let ret = if ret.is_reference_type() {
+                        ret.with_loc(gcx, DataLocation::Memory)
+                    } else {
+                        ret
+                    };
+                    self.check_clause_parameter(var, ret, None);
+                }
+            }
+        }
+
+        for clause in catches {
+            let (expected, form) = match clause.name.map(|name| name.name) {
+                None if clause.args.is_empty() => continue,
+                None => (gcx.types.bytes_ref.memory, "`catch (bytes memory ...) { ... }`"),
+                Some(sym::Error) => {
+                    (gcx.types.string_ref.memory, "`catch Error(string memory ...) { ... }`")
+                }
+                Some(sym::Panic) => (gcx.types.uint(256), "`catch Panic(uint256 ...) { ... }`"),
+                Some(_) => {
+                    let msg = "invalid catch clause name";
+                    let help = "expected `catch (...)`, `catch Error(...)` or `catch Panic(...)`";
+                    gcx.dcx().err(msg).span(clause.name.unwrap().span).help(help).emit();
+                    continue;
+                }
+            };
+            match clause.args {
+                &[var] => self.check_clause_parameter(var, expected, Some(form)),
+                _ => {
+                    let first = gcx.hir.variable(clause.args[0]).span;
+                    let last = gcx.hir.variable(clause.args[clause.args.len() - 1]).span;
+                    let msg = "wrong number of catch clause parameters";
+                    gcx.dcx().err(msg).span(first.to(last)).help(format!("expected {form}")).emit();
+                }
+            }
+        }
+
+        for clause in try_.clauses {
+            self.check_block(clause.block);
+        }
+    }
+
+    /// Returns the types returned by the call in a `try` statement, if it is an external function
+    /// call or a contract creation.
+    fn try_call_returns(&self, expr: &hir::Expr<'_>) -> Option<&'gcx [Ty<'gcx>]> {
+        let hir::ExprKind::Call(callee, ..) = expr.kind else { return None };
+        let callee = callee.peel_parens();
+        let TyKind::FnPtr(f) = self.types.get(&callee.id)?.kind else { return None };
+        let res = self.resolutions.get(&callee.id).copied();
+        let is_external =
+            f.visibility == Visibility::External && !matches!(res, Some(Res::Builtin(_)));
+        // Public library functions are called with `delegatecall`.
+        let is_library_call = matches!(callee.kind, hir::ExprKind::Member(..))
+            && match res {
+                Some(Res::Item(hir::ItemId::Function(id))) => {
+                    let f = self.gcx.hir.function(id);
+                    f.visibility >= Visibility::Public
+                        && f.contract.is_some_and(|c| self.gcx.hir.contract(c).kind.is_library())
+                }
+                _ => false,
+            };
+        let is_creation = matches!(callee.kind, hir::ExprKind::New(_));
+        (is_external || is_library_call || is_creation).then_some(f.returns)
+    }
+
+    /// Checks that the declared type of a `try` statement clause parameter is `expected`.
+    fn check_clause_parameter(&self, var: hir::VariableId, expected: Ty<'gcx>, form: Option<&str>) {
+        let ty = self.gcx.type_of_item(var.into());
+        if ty == expected || ty.has_error().is_err() {
+            return;
+        }
+        let mut err = self.mismatched_types(self.gcx.hir.variable(var).span, ty, expected);
+        if let Some(form) = form {
+            err = err.help(format!("expected {form}"));
+        }
+        err.emit();
+    }
+
+    fn check_return(&mut self, span: Span, expr: Option<&'gcx hir::Expr<'gcx>>) {
+        let Some(expr) = expr else { return };
+        let returns = match self.function {
+            Some(id) => self.gcx.type_of_item(id.into()).returns().unwrap_or_default(),
+            None => &[],
+        };
+        match returns {
+            [] => {
+                self.check_expr(expr);
+                let msg = "return arguments not allowed";
+                self.gcx.dcx().err(msg).span(span).emit();
+            }
+            &[ret] => {
+                self.check_expr_coerce(expr, ret);
+            }
+            returns => {
+                let ty = self.check_expr(expr);
+                let len = match ty.kind {
+                    TyKind::Tuple(tys) => tys.len(),
+                    _ => 1,
+                };
+                if ty.has_error().is_ok() && len != returns.len() {
+                    let msg =
+                        "different number of arguments in return statement than in returns declaration";
+                    self.gcx.dcx().err(msg).span(span).emit();
+                    return;
+                }
+                let expected = returns.iter().map(|&ty| Some((ty, expr.span))).collect();
+                self.check_destructuring(expr, ty, expected);
+            }
+        }
+    }
+
+    /// Checks that the tuple `expr` of type `ty` can be assigned to the given components.
+    ///
+    /// Components that are `None` are ignored.
+    fn check_destructuring(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        ty: Ty<'gcx>,
+        expected: Vec<Option<(Ty<'gcx>, Span)>>,
+    ) {
+        if ty.has_error().is_err() {
+            return;
+        }
+        let tys = match ty.kind {
+            TyKind::Tuple(tys) => tys,
+            _ => std::slice::from_ref(&ty),
+        };
+        if tys.len() != expected.len() {
+            let msg = format!(
+                "different number of components on the left hand side ({}) than on the right hand side ({})",
+                expected.len(),
+                tys.len(),
+            );
+            self.gcx.dcx().err(msg).span(expr.span).emit();
+            return;
+        }
+        let component_exprs = match expr.peel_parens().kind {
+            hir::ExprKind::Tuple(exprs) if exprs.len() == tys.len() => Some(exprs),
+            _ => None,
+        };
+        for (i, (&ty, expected)) in tys.iter().zip(expected).enumerate() {
+            let Some((expected, span)) = expected else { continue };
+            if ty.convert_implicit_to(expected, self.gcx) {
+                continue;
+            }
+            let span = component_exprs.and_then(|exprs| exprs[i]).map_or(span, |e| e.span);
+            self.emit_mismatched_types(span, ty, expected);
+        }
+    }
+
+    // --- Expressions ---
+
+    /// Checks the expression and that its type can be implicitly converted to `expected`.
+    fn check_expr_coerce(&mut self, expr: &'gcx hir::Expr<'gcx>, expected: Ty<'gcx>) -> Ty<'gcx> {
+        let ty = self.check_expr_with_expectation(expr, Some(expected));
+        self.check_coercion(expr, ty, expected);
+        ty
+    }
+
+    /// Checks that `ty`, the type of `expr`, can be implicitly converted to `expected`.
+    fn check_coercion(&self, expr: &hir::Expr<'_>, ty: Ty<'gcx>, expected: Ty<'gcx>) -> bool {
+        // The type of integer literals only tracks their size, so also check that the value is in
+        // range of the expected type.
+        // Literals with a fractional part can't be converted to integers.
+        let literal = matches!(ty.kind, TyKind::IntLiteral(_)) && expected.is_integer();
+        let value = literal.then(|| ConstantEvaluator::new(self.gcx).try_eval(expr).ok()).flatten();
+        let integer = value.as_ref().map(ConstValue::as_integer);
+        let in_range = integer.as_ref().is_none_or(|integer| {
+            integer.as_ref().is_some_and(|integer| {
+                ConstValue::Rational(integer.clone().into()).convert(expected, false).is_ok()
+            })
+        });
+        if in_range && ty.convert_implicit_to(expected, self.gcx) {
+            return true;
+        }
+        let mut err = self.mismatched_types(expr.span, ty, expected);
+        if let Some(value) = value {
+            let note = if integer.flatten().is_some() {
+                format!("the value `{value}` does not fit in `{}`", expected.display(self.gcx))
+            } else {
+                format!("the value `{value}` is not an integer")
+            };
+            err = err.note(note);
+        }
+        err.emit();
+        false
+    }
+
+    fn emit_mismatched_types(&self, span: Span, found: Ty<'gcx>, expected: Ty<'gcx>) {
+        self.mismatched_types(span, found, expected).emit();
+    }
+
+    fn mismatched_types(
+        &self,
+        span: Span,
+        found: Ty<'gcx>,
+        expected: Ty<'gcx>,
+    ) -> DiagBuilder<'gcx, ErrorGuaranteed> {
+        let label = format!(
+            "expected `{}`, found `{}`",
+            expected.display(self.gcx),
+            found.display(self.gcx),
+        );
+        self.gcx.dcx().err("mismatched types").span(span).span_label(span, label)
+    }
+
+    fn check_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> Ty<'gcx> {
+        self.check_expr_with_expectation(expr, None)
+    }
+
+    /// Checks the expression, using the type that it is expected to have to resolve references
+    /// to overloaded declarations.
+    fn check_expr_with_expectation(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        expected: Option<Ty<'gcx>>,
+    ) -> Ty<'gcx> {
+        let ty = self.check_expr_kind(expr, expected);
+        self.record_ty(expr, ty);
+        ty
+    }
+
+    fn record_ty(&mut self, expr: &hir::Expr<'_>, ty: Ty<'gcx>) {
+        self.types.insert(expr.id, ty);
+        self.spans.insert(expr.id, expr.span);
+    }
+
+    fn check_expr_kind(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        expected: Option<Ty<'gcx>>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        match expr.kind {
+            hir::ExprKind::Array(exprs) => self.check_array(expr, exprs),
+            hir::ExprKind::Assign(lhs, op, rhs) => self.check_assign(expr, lhs, op, rhs),
+            hir::ExprKind::Binary(lhs, op, rhs) => {
+                let lhs_ty = self.check_expr(lhs);
+                let rhs_ty = self.check_expr(rhs);
+                self.check_binop(expr, lhs_ty, op, rhs_ty, false)
+            }
+            hir::ExprKind::Call(callee, ref args, opts) => {
+                self.check_call(expr, callee, args, opts)
+            }
+            hir::ExprKind::Delete(operand) => {
+                self.check_expr(operand);
+                let _ = self.check_lvalue(operand);
+                gcx.types.unit
+            }
+            hir::ExprKind::Ident(res) => {
+                let candidates = self.ident_candidates(expr, res);
+                self.resolve_single(expr, &candidates, expected)
+            }
+            hir::ExprKind::Index(base, index) => self.check_index(expr, base, index),
+            hir::ExprKind::Slice(base, start, end) => self.check_slice(expr, base, start, end),
+            hir::ExprKind::Lit(lit) => self.check_lit(lit),
+            hir::ExprKind::Member(base, name) => {
+                let candidates = self.member_candidates(base, name);
+                self.resolve_single(expr, &candidates, expected)
+            }
+            hir::ExprKind::New(ref ty) => self.check_new(expr, ty),
+            hir::ExprKind::Payable(operand) => {
+                self.check_expr(operand);
+                gcx.types.address_payable
+            }
+            hir::ExprKind::Ternary(cond, true_, false_) => {
+                self.check_expr_coerce(cond, gcx.types.bool);
+                let true_ty = self.check_expr(true_);
+                let false_ty = self.check_expr(false_);
+                let common = true_ty.common_type(false_ty, gcx).or_else(|| {
+                    self.mobile_type(true_, true_ty)
+                        .common_type(self.mobile_type(false_, false_ty), gcx)
+                });
+                common.unwrap_or_else(|| {
+                    let msg = format!(
+                        "true expression's type `{}` does not match false expression's type `{}`",
+                        true_ty.display(gcx),
+                        false_ty.display(gcx),
+                    );
+                    gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit())
+                })
+            }
+            hir::ExprKind::Tuple(exprs) => match exprs {
+                [Some(inner)] => self.check_expr_with_expectation(inner, expected),
+                _ => {
+                    let tys = exprs.iter().map(|e| match e {
+                        Some(e) => self.check_expr(e),
+                        // Empty components are only allowed on the left-hand side of
+                        // assignments, which is checked in `check_assign_lhs`.
+                        None => {
+                            let msg = "tuple component cannot be empty";
+                            gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit())
+                        }
+                    });
+                    gcx.mk_ty(TyKind::Tuple(gcx.mk_ty_iter(tys)))
+                }
+            },
+            hir::ExprKind::TypeCall(ref ty) => gcx.type_of_hir_ty(ty).make_meta(gcx),
+            hir::ExprKind::Type(ref ty) => gcx.type_of_hir_ty(ty).make_type_type(gcx),
+            hir::ExprKind::Unary(op, operand) => self.check_unop(expr, op, operand),
+            hir::ExprKind::Err(guar) => gcx.mk_ty_err(guar),
+        }
+    }
+
+    fn check_lit(&self, lit: &hir::Lit) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        match lit.kind {
+            LitKind::Str(_, ref value) => gcx.mk_ty_string_literal(value),
+            LitKind::Number(ref n) => gcx.mk_ty_int_literal(int_literal_size(n.bits())),
+            // TODO: Rational number literal types.
+            LitKind::Rational(_) => gcx.mk_ty_int_literal(int_literal_size(0)),
+            LitKind::Address(_) => gcx.types.address,
+            LitKind::Bool(_) => gcx.types.bool,
+            LitKind::Err(guar) => gcx.mk_ty_err(guar),
+        }
+    }
+
+    fn check_array(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        exprs: &'gcx [hir::Expr<'gcx>],
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let mut common = None;
+        let mut ok = true;
+        for element in exprs {
+            let ty = self.check_expr(element);
+            let ty = self.mobile_type(element, ty);
+            common = match common {
+                None => Some(ty),
+                Some(common) => {
+                    let new = common.common_type(ty, gcx);
+                    ok &= new.is_some();
+                    new.or(Some(common))
+                }
+            };
+        }
+        let element = match common {
+            Some(ty) if ok => ty.peel_refs(),
+            _ => {
+                let msg = "unable to deduce common type for array elements";
+                return gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit());
+            }
+        };
+        let len = alloy_primitives::U256::from(exprs.len());
+        gcx.mk_ty(TyKind::Array(element, len)).with_loc(gcx, DataLocation::Memory)
+    }
+
+    fn check_assign(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        lhs: &'gcx hir::Expr<'gcx>,
+        op: Option<BinOp>,
+        rhs: &'gcx hir::Expr<'gcx>,
+    ) -> Ty<'gcx> {
+        let lhs_ty = self.check_assign_lhs(lhs);
+        let is_lvalue = self.check_lvalue(lhs);
+        let is_tuple = matches!(lhs.peel_parens().kind, hir::ExprKind::Tuple([_, _, ..]));
+        let expected = (op.is_none() && !is_tuple).then_some(lhs_ty);
+        let rhs_ty = self.check_expr_with_expectation(rhs, expected);
+        if !is_lvalue {
+            return lhs_ty;
+        }
+        match op {
+            None => {
+                if let hir::ExprKind::Tuple(components @ [_, _, ..]) = lhs.peel_parens().kind {
+                    let expected =
+                        components.iter().map(|c| c.map(|c| (self.types[&c.id], c.span))).collect();
+                    self.check_destructuring(rhs, rhs_ty, expected);
+                } else {
+                    self.check_coercion(rhs, rhs_ty, lhs_ty);
+                }
+            }
+            Some(op) => {
+                let result = self.check_binop(expr, lhs_ty, op, rhs_ty, true);
+                self.check_coercion(expr, result, lhs_ty);
+            }
+        }
+        lhs_ty
+    }
+
+    /// Checks the left-hand side of an assignment, in which tuple components may be empty.
+    ///
+    /// Empty components have the type `()`.
+    fn check_assign_lhs(&mut self, lhs: &'gcx hir::Expr<'gcx>) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let ty = match lhs.kind {
+            hir::ExprKind::Tuple([Some(inner)]) => self.check_assign_lhs(inner),
+            hir::ExprKind::Tuple(components) => {
+                let tys = components.iter().map(|c| match c {
+                    Some(c) => self.check_assign_lhs(c),
+                    None => gcx.types.unit,
+                });
+                gcx.mk_ty(TyKind::Tuple(gcx.mk_ty_iter(tys)))
+            }
+            _ => return self.check_expr(lhs),
+        };
+        self.record_ty(lhs, ty);
+        ty
+    }
+
+    /// Checks that the expression can be assigned to, returning `false` if an error was emitted.
+    fn check_lvalue(&self, expr: &'gcx hir::Expr<'gcx>) -> bool {
+        let expr = expr.peel_parens();
+        if self.types.get(&expr.id).is_some_and(|ty| ty.has_error().is_err()) {
+            return true;
+        }
+        let is_lvalue = match expr.kind {
+            hir::ExprKind::Ident(_) | hir::ExprKind::Member(..) => {
+                match self.resolutions.get(&expr.id) {
+                    Some(&Res::Item(hir::ItemId::Variable(id))) => {
+                        if self.gcx.hir.variable(id).is_constant() {
+                            let msg = "cannot assign to a constant variable";
+                            self.gcx.dcx().err(msg).span(expr.span).emit();
+                            return false;
+                        }
+                        true
+                    }
+                    Some(Res::Err(_)) => true,
+                    Some(_) => false,
+                    None => match expr.kind {
+                        // Struct fields.
+                        hir::ExprKind::Member(base, _)
+                            if self.types.get(&base.id).is_some_and(|ty| {
+                                matches!(ty.peel_refs().kind, TyKind::Struct(_))
+                            }) =>
+                        {
+                            true
+                        }
+                        hir::ExprKind::Member(_, name) if name.name == sym::length => {
+                            let msg =
+                                "member `length` is read-only and cannot be used to resize arrays";
+                            self.gcx.dcx().err(msg).span(expr.span).emit();
+                            return false;
+                        }
+                        _ => false,
+                    },
+                }
+            }
+            hir::ExprKind::Index(..) => true,
+            hir::ExprKind::Tuple(components) => {
+                let mut ok = true;
+                for component in components.iter().flatten() {
+                    ok &= self.check_lvalue(component);
+                }
+                return ok;
+            }
+            // `array.push() = value;`
+            hir::ExprKind::Call(callee, ..) => {
+                matches!(callee.peel_parens().kind, hir::ExprKind::Member(_, name) if name.name == sym::push)
+            }
+            hir::ExprKind::Err(_) => true,
+            _ => false,
+        };
+        if !is_lvalue {
+            self.gcx.dcx().err("expression has to be an lvalue").span(expr.span).emit();
+        }
+        is_lvalue
+    }
+
+    fn check_unop(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        op: UnOp,
+        operand: &'gcx hir::Expr<'gcx>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let ty = self.check_expr(operand);
+        if let Err(guar) = ty.has_error() {
+            return gcx.mk_ty_err(guar);
+        }
+        let is_udvt = matches!(ty.kind, TyKind::Udvt(..));
+        if is_udvt {
+            let operator = UserDefinableOperator::from_un_op(op.kind);
+            if let Some(ty) = self.check_user_defined_op(expr, operator, &[ty]) {
+                return ty;
+            }
+        }
+        let valid = match op.kind {
+            _ if is_udvt => false,
+            UnOpKind::Not => ty.is_bool(),
+            UnOpKind::BitNot => {
+                ty.is_integer() || ty.is_fixed_bytes() || matches!(ty.kind, TyKind::IntLiteral(_))
+            }
+            UnOpKind::Neg => {
+                if ty.is_unsigned_integer() {
+                    let msg = "unary negation is only allowed for signed integers";
+                    return gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit());
+                }
+                ty.is_numeric()
+            }
+            UnOpKind::PreInc | UnOpKind::PreDec | UnOpKind::PostInc | UnOpKind::PostDec => {
+                if !self.check_lvalue(operand) {
+                    return ty;
+                }
+                ty.is_integer() || ty.is_fixed_point()
+            }
+        };
+        if !valid {
+            let msg = format!(
+                "built-in unary operator `{op}` cannot be applied to type `{}`",
+                ty.display(gcx),
+            );
+            let mut err = gcx.dcx().err(msg).span(expr.span);
+            if is_udvt {
+                err = err.note("no matching user-defined operator found");
+            }
+            return gcx.mk_ty_err(err.emit());
+        }
+        ty
+    }
+
+    /// Checks a binary operation, returning the type of the result.
+    ///
+    /// `expr` is either the binary expression, or the compound assignment if `is_assign` is
+    /// `true`.
+    fn check_binop(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        lhs: Ty<'gcx>,
+        op: BinOp,
+        rhs: Ty<'gcx>,
+        is_assign: bool,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        if let Err(guar) = lhs.has_error().and(rhs.has_error()) {
+            return gcx.mk_ty_err(guar);
+        }
+        // User-defined value types only support the operators that are bound to them.
+        let is_udvt = matches!(lhs.kind, TyKind::Udvt(..)) || matches!(rhs.kind, TyKind::Udvt(..));
+        if is_udvt && !is_assign {
+            let operator = UserDefinableOperator::from_bin_op(op.kind);
+            if let Some(ty) = self.check_user_defined_op(expr, operator, &[lhs, rhs]) {
+                return ty;
+            }
+        }
+        let both_literals = !is_assign
+            && matches!((&lhs.kind, &rhs.kind), (TyKind::IntLiteral(_), TyKind::IntLiteral(_)));
+        let result = match op.kind {
+            _ if is_udvt => None,
+            BinOpKind::And | BinOpKind::Or => {
+                (lhs.is_bool() && rhs.is_bool()).then_some(gcx.types.bool)
+            }
+            BinOpKind::Eq | BinOpKind::Ne => lhs
+                .common_type(rhs, gcx)
+                .filter(|ty| ty.is_value_type() || matches!(ty.kind, TyKind::IntLiteral(_)))
+                .map(|_| gcx.types.bool),
+            BinOpKind::Lt | BinOpKind::Le | BinOpKind::Gt | BinOpKind::Ge => lhs
+                .common_type(rhs, gcx)
+                .filter(|ty| {
+                    ty.is_numeric()
+                        || ty.is_fixed_bytes()
+                        || matches!(
+                            ty.kind,
+                            TyKind::Elementary(ElementaryType::Address(_))
+                                | TyKind::Enum(_)
+                                | TyKind::Contract(_)
+                        )
+                })
+                .map(|_| gcx.types.bool),
+            BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul | BinOpKind::Div | BinOpKind::Rem => {
+                lhs.common_type(rhs, gcx).filter(|ty| ty.is_numeric())
+            }
+            BinOpKind::BitAnd | BinOpKind::BitOr | BinOpKind::BitXor => {
+                lhs.common_type(rhs, gcx).filter(|ty| {
+                    ty.is_integer()
+                        || ty.is_fixed_bytes()
+                        || matches!(ty.kind, TyKind::IntLiteral(_))
+                })
+            }
+            BinOpKind::Shl | BinOpKind::Shr | BinOpKind::Sar | BinOpKind::Pow => {
+                let lhs_ok = if op.kind == BinOpKind::Pow {
+                    lhs.is_numeric()
+                } else {
+                    lhs.is_integer()
+                        || lhs.is_fixed_bytes()
+                        || matches!(lhs.kind, TyKind::IntLiteral(_))
+                };
+                let rhs_ok = rhs.is_unsigned_integer() || matches!(rhs.kind, TyKind::IntLiteral(_));
+                (lhs_ok && rhs_ok).then(|| match lhs.kind {
+                    // Literals raised to or shifted by a non-literal value are `uint256`.
+                    TyKind::IntLiteral(_) if !both_literals => gcx.types.uint(256),
+                    _ => lhs,
+                })
+            }
+        };
+        let Some(result) = result else {
+            let msg = format!(
+                "built-in binary operator `{op}` cannot be applied to types `{}` and `{}`",
+                lhs.display(gcx),
+                rhs.display(gcx),
+            );
+            let mut err = gcx.dcx().err(msg).span(expr.span);
+            if is_udvt {
+                err = err.note("no matching user-defined operator found");
+            }
+            return gcx.mk_ty_err(err.emit());
+        };
+        // Literals with a fractional part can't be converted to the integer type of the other
+        // operand.
+        if result.is_integer() {
+            if let hir::ExprKind::Binary(lhs_expr, _, rhs_expr)
+            | hir::ExprKind::Assign(lhs_expr, _, rhs_expr) = expr.kind
+            {
+                for (operand, ty) in [(lhs_expr, lhs), (rhs_expr, rhs)] {
+                    if let Some(value) = self.fractional_literal(operand, ty) {
+                        let msg = format!(
+                            "built-in binary operator `{op}` cannot be applied to types `{}` and `{}`",
+                            lhs.display(gcx),
+                            rhs.display(gcx),
+                        );
+                        let note = format!("the value `{value}` is not an integer");
+                        let err = gcx.dcx().err(msg).span(expr.span).span_note(operand.span, note);
+                        return gcx.mk_ty_err(err.emit());
+                    }
+                }
+            }
+        }
+        self.check_const_binop(expr, op, lhs, result);
+        match (&result.kind, &lhs.kind, &rhs.kind) {
+            (TyKind::IntLiteral(_), &TyKind::IntLiteral(l), &TyKind::IntLiteral(r))
+                if both_literals =>
+            {
+                // Compute the actual size of the result if possible.
+                let value = ConstantEvaluator::new(gcx).try_eval(expr).ok();
+                let size = match value.as_ref().and_then(|value| value.to_u256().ok()) {
+                    Some(value) if !value.bit(255) => int_literal_size(value.bit_len() as u64),
+                    _ => l.max(r),
+                };
+                gcx.mk_ty_int_literal(size)
+            }
+            _ => result,
+        }
+    }
+
+    /// Checks a binary operation with operands that are known at compile time for division by
+    /// zero, shifts by at least the number of bits of the shifted type and, outside of `unchecked`
+    /// blocks, arithmetic overflow.
+    fn check_const_binop(
+        &self,
+        expr: &'gcx hir::Expr<'gcx>,
+        op: BinOp,
+        lhs: Ty<'gcx>,
+        result: Ty<'gcx>,
+    ) {
+        let gcx = self.gcx;
+        let (hir::ExprKind::Binary(_, _, rhs) | hir::ExprKind::Assign(_, _, rhs)) = expr.kind
+        else {
+            return;
+        };
+        let rhs_value = || ConstantEvaluator::new(gcx).try_eval(rhs).ok()?.as_integer();
+        match op.kind {
+            BinOpKind::Div | BinOpKind::Rem if rhs_value().is_some_and(|value| value.is_zero()) => {
+                let msg =
+                    if op.kind == BinOpKind::Div { "division by zero" } else { "modulo by zero" };
+                gcx.dcx().err(msg).span(expr.span).span_label(rhs.span, "this is zero").emit();
+                return;
+            }
+            BinOpKind::Shl | BinOpKind::Shr | BinOpKind::Sar => {
+                let bits = match lhs.kind {
+                    TyKind::Elementary(
+                        ElementaryType::Int(size)
+                        | ElementaryType::UInt(size)
+                        | ElementaryType::FixedBytes(size),
+                    ) => size.bits(),
+                    _ => return,
+                };
+                if rhs_value().is_some_and(|value| value >= bits.into()) {
+                    let label = format!("`{}` only has {bits} bits", lhs.display(gcx));
+                    let msg = "shift amount is too large";
+                    gcx.dcx().err(msg).span(expr.span).span_label(rhs.span, label).emit();
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        // Only fully evaluated operations on integer types can overflow; literal arithmetic is
+        // exact and is checked when it is converted to the target type.
+        if self.unchecked || matches!(expr.kind, hir::ExprKind::Assign(..)) || !result.is_integer()
+        {
+            return;
+        }
+        match ConstantEvaluator::new(gcx).try_eval(expr) {
+            // Report the error only on the innermost expression that overflows.
+            Err(err)
+                if matches!(err.kind, EvalErrorKind::ArithmeticOverflow)
+                    && err.span == expr.span =>
+            {
+                let label = format!("the result does not fit in `{}`", result.display(gcx));
+                let msg = "this arithmetic operation will overflow";
+                gcx.dcx().err(msg).span(expr.span).span_label(expr.span, label).emit();
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves an operation on a user-defined value type to the function that is bound to the
+    /// operator, and returns the type of the result.
+    ///
+    /// Returns `None` if there is no definition of the operator for the operand types.
+    fn check_user_defined_op(
+        &mut self,
+        expr: &hir::Expr<'_>,
+        operator: Option<UserDefinableOperator>,
+        operands: &[Ty<'gcx>],
+    ) -> Option<Ty<'gcx>> {
+        let gcx = self.gcx;
+        let operator = operator?;
+        let ty = operands[0];
+        if operands.iter().any(|&operand| operand != ty) {
+            return None;
+        }
+        let definitions = operator_definitions(gcx, ty, operator, operands.len());
+        match definitions[..] {
+            [] => None,
+            [id] => {
+                self.resolutions.insert(expr.id, Res::Item(id.into()));
+                let TyKind::FnPtr(f) = gcx.type_of_item(id.into()).kind else { unreachable!() };
+                Some(self.returns_ty(f.returns))
+            }
+            _ => {
+                let kind = if operands.len() == 1 { "unary" } else { "binary" };
+                let msg = format!(
+                    "user-defined {kind} operator `{operator}` has more than one definition \
+                     matching the operand type visible in the current scope"
+                );
+                let mut err = gcx.dcx().err(msg).span(expr.span);
+                for &id in &definitions {
+                    err = err.span_note(gcx.item_name(id).span, "candidate");
+                }
+                Some(gcx.mk_ty_err(err.emit()))
+            }
+        }
+    }
+
+    fn check_index(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        base: &'gcx hir::Expr<'gcx>,
+        index: Option<&'gcx hir::Expr<'gcx>>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let base_ty = self.check_expr(base);
+        if let Err(guar) = base_ty.has_error() {
+            if let Some(index) = index {
+                self.check_expr(index);
+            }
+            return gcx.mk_ty_err(guar);
+        }
+
+        // Array type: `T[]`, `T[N]`.
+        if let TyKind::Type(element) = base_ty.kind {
+            let kind = match index {
+                None => TyKind::DynArray(element),
+                Some(index) => {
+                    self.check_expr(index);
+                    match ConstantEvaluator::new(gcx).eval_u256(index) {
+                        Ok(len) if len.is_zero() => {
+                            let msg = "array length must be greater than zero";
+                            return gcx.mk_ty_err(gcx.dcx().err(msg).span(index.span).emit());
+                        }
+                        Ok(len) => TyKind::Array(element, len),
+                        Err(guar) => return gcx.mk_ty_err(guar),
+                    }
+                }
+            };
+            return gcx.mk_ty(kind).make_type_type(gcx);
+        }
+
+        let Some(index) = index else {
+            let msg = "index expression cannot be omitted";
+            return gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit());
+        };
+        let (inner, loc) = match base_ty.kind {
+            TyKind::Ref(inner, loc) => (inner, loc),
+            _ => (base_ty, DataLocation::Memory),
+        };
+        let uint256 = gcx.types.uint(256);
+        match inner.kind {
+            TyKind::Array(element, _) | TyKind::DynArray(element) => {
+                self.check_expr_coerce(index, uint256);
+                element.with_loc_if_ref(gcx, loc)
+            }
+            TyKind::Elementary(ElementaryType::Bytes | ElementaryType::FixedBytes(_)) => {
+                self.check_expr_coerce(index, uint256);
+                gcx.types.fixed_bytes(1)
+            }
+            TyKind::Mapping(key, value) => {
+                self.check_expr_coerce(index, key);
+                value.with_loc_if_ref(gcx, DataLocation::Storage)
+            }
+            TyKind::Elementary(ElementaryType::String) => {
+                self.check_expr(index);
+                let msg = "index access for `string` is not possible";
+                gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit())
+            }
+            _ => {
+                self.check_expr(index);
+                let msg = format!(
+                    "indexed expression has to be a type, mapping or array (is `{}`)",
+                    base_ty.display(gcx),
+                );
+                gcx.mk_ty_err(gcx.dcx().err(msg).span(base.span).emit())
+            }
+        }
+    }
+
+    fn check_slice(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        base: &'gcx hir::Expr<'gcx>,
+        start: Option<&'gcx hir::Expr<'gcx>>,
+        end: Option<&'gcx hir::Expr<'gcx>>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let base_ty = self.check_expr(base);
+        for bound in [start, end].into_iter().flatten() {
+            self.check_expr_coerce(bound, gcx.types.uint(256));
+        }
+        if let Err(guar) = base_ty.has_error() {
+            return gcx.mk_ty_err(guar);
+        }
+        match base_ty.kind {
+            TyKind::Ref(inner, DataLocation::Calldata)
+                if matches!(
+                    inner.kind,
+                    TyKind::DynArray(_)
+                        | TyKind::Elementary(ElementaryType::Bytes | ElementaryType::String)
+                ) =>
+            {
+                base_ty
+            }
+            _ => {
+                let msg = "index range access is only supported for dynamic calldata arrays";
+                gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit())
+            }
+        }
+    }
+
+    fn check_new(&mut self, expr: &hir::Expr<'_>, ty: &hir::Type<'_>) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let ty = gcx.type_of_hir_ty(ty);
+        if let Err(guar) = ty.has_error() {
+            return gcx.mk_ty_err(guar);
+        }
+        match ty.kind {
+            TyKind::Contract(id) => {
+                let c = gcx.hir.contract(id);
+                let (parameters, state_mutability) = match c.ctor {
+                    Some(ctor) => {
+                        let f = gcx.hir.function(ctor);
+                        (gcx.item_parameter_types(ctor), f.state_mutability)
+                    }
+                    None => (&[][..], hir::StateMutability::NonPayable),
+                };
+                gcx.mk_ty_fn(parameters, state_mutability, Visibility::External, &[ty])
+            }
+            TyKind::DynArray(_)
+            | TyKind::Elementary(ElementaryType::Bytes | ElementaryType::String) => {
+                let ret = ty.with_loc(gcx, DataLocation::Memory);
+                gcx.mk_builtin_fn(&[gcx.types.uint(256)], hir::StateMutability::Pure, &[ret])
+            }
+            _ => {
+                let msg = "contract or dynamic array type expected";
+                gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit())
+            }
+        }
+    }
+
+    // --- Calls ---
+
+    fn check_call(
+        &mut self,
+        expr: &'gcx hir::Expr<'gcx>,
+        callee: &'gcx hir::Expr<'gcx>,
+        args: &'gcx hir::CallArgs<'gcx>,
+        opts: Option<&'gcx [hir::NamedArg<'gcx>]>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+
+        if let Some(opts) = opts {
+            self.check_call_options(opts);
+        }
+
+        let inner_callee = callee.peel_parens();
+        let candidates = match inner_callee.kind {
+            hir::ExprKind::Ident(res) => self.ident_candidates(inner_callee, res),
+            hir::ExprKind::Member(base, name) => self.member_candidates(base, name),
+            _ => {
+                let ty = self.check_expr(inner_callee);
+                let mut candidates = Candidates::new();
+                candidates.push(Candidate { ty, res: None });
+                candidates
+            }
+        };
+        let arg_tys = args.exprs().map(|arg| self.check_expr(arg)).collect::<SmallVec<[_; 8]>>();
+
+        // `require(condition, CustomError(...))`
+        if candidates
+            .iter()
+            .all(|c| matches!(c.res, Some(Res::Builtin(Builtin::Require | Builtin::RequireMsg))))
+            && !candidates.is_empty()
+        {
+            if let (hir::CallArgs::Unnamed([cond, _]), [_, error_ty]) = (args, &arg_tys[..]) {
+                if matches!(error_ty.kind, TyKind::Error(..)) {
+                    let candidate = candidates[0];
+                    self.record_callee(callee, inner_callee, candidate);
+                    self.check_coercion(cond, arg_tys[0], gcx.types.bool);
+                    return gcx.types.unit;
+                }
+            }
+        }
+
+        let candidate = match self.resolve_overload(inner_callee, &candidates, args, &arg_tys) {
+            Ok(candidate) => candidate,
+            Err(guar) => {
+                let ty = gcx.mk_ty_err(guar);
+                self.record_callee(callee, inner_callee, Candidate { ty, res: None });
+                return ty;
+            }
+        };
+        self.record_callee(callee, inner_callee, candidate);
+
+        let callee_ty = candidate.ty;
+        match callee_ty.kind {
+            TyKind::FnPtr(f) => {
+                if let Some(Res::Builtin(builtin)) = candidate.res {
+                    if builtin == Builtin::AbiDecode {
+                        return self.abi_decode_returns(args, &arg_tys);
+                    }
+                    if builtin.is_variadic() {
+                        return self.returns_ty(f.returns);
+                    }
+                }
+                let names = self.parameter_names(candidate.res, f.parameters.len());
+                self.check_args(expr.span, "function call", f.parameters, names, args, &arg_tys);
+                self.returns_ty(f.returns)
+            }
+            TyKind::Event(parameters, id) => {
+                let names = self.parameter_names(Some(Res::Item(id.into())), parameters.len());
+                self.check_args(expr.span, "event invocation", parameters, names, args, &arg_tys);
+                gcx.types.unit
+            }
+            TyKind::Error(parameters, id) => {
+                let names = self.parameter_names(Some(Res::Item(id.into())), parameters.len());
+                self.check_args(expr.span, "error", parameters, names, args, &arg_tys);
+                callee_ty
+            }
+            TyKind::Type(to) => self.check_type_call(expr, to, args, &arg_tys),
+            TyKind::Err(guar) => gcx.mk_ty_err(guar),
+            _ => {
+                let msg =
+                    format!("expression of type `{}` is not callable", callee_ty.display(gcx));
+                gcx.mk_ty_err(gcx.dcx().err(msg).span(callee.span).emit())
+            }
+        }
+    }
+
+    fn check_call_options(&mut self, opts: &'gcx [hir::NamedArg<'gcx>]) {
+        let gcx = self.gcx;
+        for opt in opts {
+            let expected = match opt.name.name {
+                sym::value | solar_interface::kw::Gas => gcx.types.uint(256),
+                sym::salt => gcx.types.fixed_bytes(32),
+                _ => {
+                    self.check_expr(&opt.value);
+                    let msg = format!(
+                        "unknown call option `{}`; valid options are `salt`, `value` and `gas`",
+                        opt.name
+                    );
+                    gcx.dcx().err(msg).span(opt.name.span).emit();
+                    continue;
+                }
+            };
+            self.check_expr_coerce(&opt.value, expected);
+        }
+    }
+
+    fn record_callee(
+        &mut self,
+        callee: &'gcx hir::Expr<'gcx>,
+        inner_callee: &'gcx hir::Expr<'gcx>,
+        candidate: Candidate<'gcx>,
+    ) {
+        self.record_ty(inner_callee, candidate.ty);
+        self.record_ty(callee, candidate.ty);
+        if let Some(res) = candidate.res {
+            self.record_res(inner_callee, res);
+        }
+    }
+
+    /// Selects the candidate that is called with the given arguments.
+    ///
+    /// If there is only one candidate, it is always returned so that argument mismatches can be
+    /// reported against it. Otherwise, exactly one of the candidates must accept the arguments.
+    fn resolve_overload(
+        &self,
+        callee: &hir::Expr<'_>,
+        candidates: &[Candidate<'gcx>],
+        args: &hir::CallArgs<'_>,
+        arg_tys: &[Ty<'gcx>],
+    ) -> Result<Candidate<'gcx>, ErrorGuaranteed> {
+        if let [candidate] = candidates {
+            return Ok(*candidate);
+        }
+        let matching = candidates
+            .iter()
+            .filter(|c| self.args_match(**c, args, arg_tys))
+            .copied()
+            .collect::<Candidates<'gcx>>();
+        if let [candidate] = matching[..] {
+            return Ok(candidate);
+        }
+        // Argument errors may cause any number of candidates to match.
+        for ty in arg_tys {
+            ty.has_error()?;
+        }
+
+        let gcx = self.gcx;
+        let name = self.overloaded_name(callee, candidates);
+        let (msg, notes) = if matching.is_empty() {
+            (format!("no matching overload for call to{name}"), candidates)
+        } else {
+            (format!("ambiguous call to overloaded function{name}"), &matching[..])
+        };
+        let mut err = self.note_candidates(gcx.dcx().err(msg).span(callee.span), notes);
+        if matching.is_empty() {
+            let arg_tys = arg_tys.iter().map(|ty| ty.display(gcx).to_string()).collect::<Vec<_>>();
+            err = err.note(format!("the call has argument types ({})", arg_tys.join(", ")));
+        }
+        Err(err.emit())
+    }
+
+    /// Returns the name of the overloaded declarations referred to by `expr`, with a leading space
+    /// so that it can be appended to diagnostic messages, or an empty string if it is unknown.
+    fn overloaded_name(&self, expr: &hir::Expr<'_>, candidates: &[Candidate<'gcx>]) -> String {
+        let gcx = self.gcx;
+        let name = match expr.peel_parens().kind {
+            hir::ExprKind::Member(_, name) => Some(name.name),
+            _ => candidates.iter().find_map(|c| match c.res? {
+                Res::Item(id) => gcx.item_name_opt(id).map(|name| name.name),
+                Res::Builtin(builtin) => Some(builtin.name()),
+                _ => None,
+            }),
+        };
+        name.map(|name| format!(" `{name}`")).unwrap_or_default()
+    }
+
+    /// Adds a note for each of the given candidates.
+    fn note_candidates(
+        &self,
+        mut err: DiagBuilder<'gcx, ErrorGuaranteed>,
+        candidates: &[Candidate<'gcx>],
+    ) -> DiagBuilder<'gcx, ErrorGuaranteed> {
+        let gcx = self.gcx;
+        for candidate in candidates {
+            match candidate.res {
+                Some(Res::Item(id)) => {
+                    let span = gcx.item_name_opt(id).map_or_else(|| gcx.item_span(id), |n| n.span);
+                    err = err.span_note(span, "candidate");
+                }
+                _ => {
+                    err = err.note(format!("candidate: `{}`", candidate.ty.display(gcx)));
+                }
+            }
+        }
+        err
+    }
+
+    /// Returns `true` if the candidate can be called with the given arguments.
+    fn args_match(
+        &self,
+        candidate: Candidate<'gcx>,
+        args: &hir::CallArgs<'_>,
+        arg_tys: &[Ty<'gcx>],
+    ) -> bool {
+        let parameters = match candidate.ty.kind {
+            TyKind::FnPtr(f) => {
+                if let Some(Res::Builtin(builtin)) = candidate.res {
+                    if builtin.is_variadic() {
+                        return true;
+                    }
+                }
+                f.parameters
+            }
+            TyKind::Event(parameters, _) | TyKind::Error(parameters, _) => parameters,
+            _ => return false,
+        };
+        if parameters.len() != arg_tys.len() {
+            return false;
+        }
+        match args {
+            hir::CallArgs::Unnamed(_) => std::iter::zip(arg_tys, parameters)
+                .all(|(&arg, &param)| arg.convert_implicit_to(param, self.gcx)),
+            hir::CallArgs::Named(named) => {
+                let Some(names) = self.parameter_names(candidate.res, parameters.len()) else {
+                    return false;
+                };
+                std::iter::zip(*named, arg_tys).all(|(arg, &ty)| {
+                    names
+                        .iter()
+                        .position(|&name| name == Some(arg.name.name))
+                        .is_some_and(|i| ty.convert_implicit_to(parameters[i], self.gcx))
+                })
+            }
+        }
+    }
+
+    /// Returns the names of the last `len` parameters of the declaration referred to by `res`, if
+    /// any.
+    ///
+    /// Functions attached with `using for` have fewer parameters than their declaration, since the
+    /// first one is bound to the member access base.
+    fn parameter_names(
+        &self,
+        res: Option<Res>,
+        len: usize,
+    ) -> Option<SmallVec<[Option<Symbol>; 8]>> {
+        let Some(Res::Item(id)) = res else { return None };
+        let parameters = self.gcx.item_parameters_opt(id)?;
+        Some(
+            parameters[parameters.len().saturating_sub(len)..]
+                .iter()
+                .map(|&p| self.gcx.hir.variable(p).name.map(|name| name.name))
+                .collect(),
+        )
+    }
+
+    /// Checks the arguments of a call against the given parameters.
+    fn check_args(
+        &self,
+        span: Span,
+        descr: &str,
+        parameters: &[Ty<'gcx>],
+        names: Option<SmallVec<[Option<Symbol>; 8]>>,
+        args: &hir::CallArgs<'_>,
+        arg_tys: &[Ty<'gcx>],
+    ) {
+        let gcx = self.gcx;
+        if parameters.len() != arg_tys.len() {
+            let msg = format!(
+                "wrong argument count for {descr}: {} arguments given but expected {}",
+                arg_tys.len(),
+                parameters.len(),
+            );
+            gcx.dcx().err(msg).span(span).emit();
+            return;
+        }
+        match args {
+            hir::CallArgs::Unnamed(exprs) => {
+                for ((arg, &ty), &param) in exprs.iter().zip(arg_tys).zip(parameters) {
+                    self.check_coercion(arg, ty, param);
+                }
+            }
+            hir::CallArgs::Named(named) => {
+                let Some(names) = names else { return };
+                for (i, (arg, &ty)) in named.iter().zip(arg_tys).enumerate() {
+                    if named[..i].iter().any(|prev| prev.name.name == arg.name.name) {
+                        let msg = format!("duplicate named argument `{}`", arg.name);
+                        gcx.dcx().err(msg).span(arg.name.span).emit();
+                        continue;
+                    }
+                    let Some(param) = names.iter().position(|&name| name == Some(arg.name.name))
+                    else {
+                        let msg = format!(
+                            "named argument `{}` does not match function declaration",
+                            arg.name,
+                        );
+                        gcx.dcx().err(msg).span(arg.name.span).emit();
+                        continue;
+                    };
+                    self.check_coercion(&arg.value, ty, parameters[param]);
+                }
+            }
+        }
+    }
+
+    /// Checks a call to a type: a struct constructor or an explicit type conversion.
+    fn check_type_call(
+        &self,
+        expr: &hir::Expr<'_>,
+        to: Ty<'gcx>,
+        args: &hir::CallArgs<'_>,
+        arg_tys: &[Ty<'gcx>],
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        if let TyKind::Struct(id) = to.kind {
+            let fields = gcx.struct_field_types(id);
+            let names = self.parameter_names(Some(Res::Item(id.into())), fields.len());
+            self.check_args(expr.span, "struct constructor", fields, names, args, arg_tys);
+            return to.with_loc(gcx, DataLocation::Memory);
+        }
+
+        let (hir::CallArgs::Unnamed([arg]), &[from]) = (args, arg_tys) else {
+            let msg = "exactly one unnamed argument expected for explicit type conversion";
+            return gcx.mk_ty_err(gcx.dcx().err(msg).span(expr.span).emit());
+        };
+        if let Err(guar) = from.has_error().and(to.has_error()) {
+            return gcx.mk_ty_err(guar);
+        }
+        let to = if to.is_reference_type() {
+            to.with_loc(gcx, from.loc().unwrap_or(DataLocation::Memory))
+        } else {
+            to
+        };
+        let value = match from.kind {
+            TyKind::IntLiteral(_) => ConstantEvaluator::new(gcx).try_eval(arg).ok(),
+            _ => None,
+        };
+        let valid = from.convert_explicit_to(to, gcx)
+            && value.as_ref().is_none_or(|value| literal_convertible_to(gcx, arg, value, to));
+        if !valid {
+            let msg = format!(
+                "explicit type conversion not allowed from `{}` to `{}`",
+                from.display(gcx),
+                to.display(gcx),
+            );
+            let mut err = gcx.dcx().err(msg).span(expr.span);
+            if let Some(value) = value {
+                err = err.note(format!("the value of the literal is `{value}`"));
+            }
+            err.emit();
+        }
+        to
+    }
+
+    /// Returns the return type of `abi.decode`, which is given by its second argument.
+    fn abi_decode_returns(&self, args: &hir::CallArgs<'_>, arg_tys: &[Ty<'gcx>]) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let (hir::CallArgs::Unnamed([data, types]), &[data_ty, types_ty]) = (args, arg_tys) else {
+            let msg = "`abi.decode` requires exactly two unnamed arguments";
+            let span = args.span();
+            return gcx.mk_ty_err(gcx.dcx().err(msg).span(span).emit());
+        };
+        self.check_coercion(data, data_ty, gcx.types.bytes_ref.memory);
+        if let Err(guar) = types_ty.has_error() {
+            return gcx.mk_ty_err(guar);
+        }
+        let tys = match types_ty.kind {
+            TyKind::Tuple(tys) => tys,
+            _ => std::slice::from_ref(&types_ty),
+        };
+        let mut returns = SmallVec::<[_; 8]>::with_capacity(tys.len());
+        for &ty in tys {
+            let TyKind::Type(ty) = ty.kind else {
+                let msg = "the second argument to `abi.decode` has to be a tuple of types";
+                return gcx.mk_ty_err(gcx.dcx().err(msg).span(types.span).emit());
+            };
+            returns.push(ty.with_loc_if_ref(gcx, DataLocation::Memory));
+        }
+        self.returns_ty(&returns)
+    }
+
+    /// Returns the type of a call expression which returns the given types.
+    fn returns_ty(&self, returns: &[Ty<'gcx>]) -> Ty<'gcx> {
+        match returns {
+            [ty] => *ty,
+            _ => self.gcx.mk_ty(TyKind::Tuple(self.gcx.mk_tys(returns))),
+        }
+    }
+
+    // --- Name resolution ---
+
+    /// Resolves an identifier or member access that is not called.
+    ///
+    /// References to overloaded declarations are resolved with the type that the expression is
+    /// expected to have.
+    fn resolve_single(
+        &mut self,
+        expr: &hir::Expr<'_>,
+        candidates: &[Candidate<'gcx>],
+        expected: Option<Ty<'gcx>>,
+    ) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        let candidate = match *candidates {
+            [candidate] => candidate,
+            _ => {
+                if let Some(Err(guar)) = expected.map(|ty| ty.has_error()) {
+                    return gcx.mk_ty_err(guar);
+                }
+                let matching = candidates
+                    .iter()
+                    .filter(|c| expected.is_some_and(|ty| c.ty.convert_implicit_to(ty, gcx)))
+                    .copied()
+                    .collect::<Candidates<'gcx>>();
+                let [candidate] = matching[..] else {
+                    let name = self.overloaded_name(expr, candidates);
+                    let (msg, notes) = if expected.is_some() && matching.is_empty() {
+                        (format!("no matching declaration for{name}"), candidates)
+                    } else {
+                        let notes = if matching.is_empty() { candidates } else { &matching[..] };
+                        (format!("ambiguous reference to overloaded declaration{name}"), notes)
+                    };
+                    let mut err = gcx.dcx().err(msg).span(expr.span);
+                    if let Some(expected) = expected {
+                        err = err
+                            .span_label(expr.span, format!("expected `{}`", expected.display(gcx)));
+                    }
+                    return gcx.mk_ty_err(self.note_candidates(err, notes).emit());
+                };
+                candidate
+            }
+        };
+        if let Some(res) = candidate.res {
+            self.record_res(expr, res);
+        }
+        candidate.ty
+    }
+
+    /// Records the resolution of an identifier or member access.
+    fn record_res(&mut self, expr: &hir::Expr<'_>, res: Res) {
+        self.resolutions.insert(expr.id, res);
+        if let Res::Builtin(builtin) = res {
+            self.check_evm_version(expr, builtin);
+        }
+    }
+
+    /// Checks that the builtin referenced by `expr` is supported by the selected EVM version.
+    fn check_evm_version(&self, expr: &hir::Expr<'_>, builtin: Builtin) {
+        let Some(required) = builtin.min_evm_version() else { return };
+        let evm_version = self.gcx.sess.opts.evm_version;
+        if evm_version >= required {
+            return;
+        }
+        let (name, span) = match expr.kind {
+            hir::ExprKind::Member(base, member) => match base.peel_parens().kind {
+                hir::ExprKind::Ident([Res::Builtin(module)]) => {
+                    (format!("{}.{member}", module.name()), expr.span)
+                }
+                _ => (member.to_string(), member.span),
+            },
+            _ => (builtin.name().to_string(), expr.span),
+        };
+        crate::builtins::unsupported_evm_version(
+            self.gcx.dcx(),
+            span,
+            &name,
+            required,
+            evm_version,
+        );
+    }
+
+    fn ident_candidates(&self, expr: &hir::Expr<'_>, res: &[Res]) -> Candidates<'gcx> {
+        let mut candidates = Candidates::new();
+        for &res in res {
+            if let Res::Builtin(Builtin::Super) = res {
+                let msg = "`super` can only be used to access members of base contracts";
+                let ty = self.gcx.mk_ty_err(self.gcx.dcx().err(msg).span(expr.span).emit());
+                candidates.push(Candidate { ty, res: None });
+                continue;
+            }
+            // Getters are declared alongside their variable, but can only be called externally.
+            if let Res::Item(hir::ItemId::Function(id)) = res {
+                if self.gcx.hir.function(id).is_getter() {
+                    continue;
+                }
+            }
+            push_candidate(
+                self.gcx,
+                &mut candidates,
+                Candidate { ty: self.type_of_res(res), res: Some(res) },
+            );
+        }
+        candidates
+    }
+
+    /// Returns the type of an expression that refers to the given declaration.
+    fn type_of_res(&self, res: Res) -> Ty<'gcx> {
+        let gcx = self.gcx;
+        match res {
+            Res::Item(
+                id @ (hir::ItemId::Contract(_)
+                | hir::ItemId::Struct(_)
+                | hir::ItemId::Enum(_)
+                | hir::ItemId::Udvt(_)),
+            ) => gcx.type_of_item(id).make_type_type(gcx),
+            // Functions referenced by name are internal, unless they can only be called
+            // externally.
+            Res::Item(id @ hir::ItemId::Function(_)) => {
+                let ty = gcx.type_of_item(id);
+                match ty.kind {
+                    TyKind::FnPtr(f) if f.visibility != Visibility::External => {
+                        with_visibility(gcx, f, Visibility::Internal)
+                    }
+                    _ => ty,
+                }
+            }
+            // `this` and `super` are only declared in contracts.
+            Res::Builtin(Builtin::This) => {
+                gcx.mk_ty(TyKind::Contract(self.contract.expect("`this` outside of a contract")))
+            }
+            Res::Builtin(Builtin::Super) => unreachable!("`super` is only used in member accesses"),
+            _ => gcx.type_of_res(res),
+        }
+    }
+
+    /// Returns the members named `name` of the given expression.
+    fn member_candidates(&mut self, base: &'gcx hir::Expr<'gcx>, name: Ident) -> Candidates<'gcx> {
+        let gcx = self.gcx;
+        let mut candidates = Candidates::new();
+
+        // `super.f`
+        if let hir::ExprKind::Ident([Res::Builtin(Builtin::Super)]) = base.peel_parens().kind {
+            let contract = self.contract.expect("`super` outside of a contract");
+            self.record_ty(base, gcx.mk_ty(TyKind::Contract(contract)));
+            for &base in &gcx.hir.contract(contract).linearized_bases[1..] {
+                for f in gcx.hir.contract(base).functions() {
+                    if gcx.item_name_opt(f).is_some_and(|n| n.name == name.name) {
+                        let res = Res::Item(f.into());
+                        let ty = self.type_of_res(res);
+                        // The first definition in the linearization order is the one called.
+                        if !candidates.iter().any(|c| same_parameters(c.ty, ty)) {
+                            candidates.push(Candidate { ty, res: Some(res) });
+                        }
+                    }
+                }
+            }
+            if candidates.is_empty() {
+                let msg = format!("no member named `{name}` on `super`");
+                let guar = gcx.dcx().err(msg).span(name.span).emit();
+                candidates.push(Candidate { ty: gcx.mk_ty_err(guar), res: None });
+            }
+            return candidates;
+        }
+
+        let base_ty = self.check_expr(base);
+        if let Err(guar) = base_ty.has_error() {
+            candidates.push(Candidate { ty: gcx.mk_ty_err(guar), res: None });
+            return candidates;
+        }
+        let scope = match base_ty.kind {
+            TyKind::Type(ty) => match ty.kind {
+                TyKind::Contract(id) => Some(&gcx.symbol_resolver.contract_scopes[id]),
+                _ => None,
+            },
+            TyKind::Module(id) => Some(&gcx.symbol_resolver.source_scopes[id]),
+            _ => None,
+        };
+        if let Some(scope) = scope {
+            let is_library = match base_ty.kind {
+                TyKind::Type(ty) => matches!(
+                    ty.kind,
+                    TyKind::Contract(id) if gcx.hir.contract(id).kind.is_library()
+                ),
+                _ => false,
+            };
+            for decl in scope.resolve(name).unwrap_or_default() {
+                if matches!(decl.res, Res::Builtin(Builtin::This | Builtin::Super)) {
+                    continue;
+                }
+                let ty = match decl.res {
+                    // Public library functions are called with `delegatecall`.
+                    Res::Item(id @ hir::ItemId::Function(f))
+                        if is_library && gcx.hir.function(f).visibility >= Visibility::Public =>
+                    {
+                        gcx.type_of_item(id)
+                    }
+                    res => self.type_of_res(res),
+                };
+                push_candidate(gcx, &mut candidates, Candidate { ty, res: Some(decl.res) });
+            }
+        } else {
+            for member in gcx.members_of(base_ty).iter().filter(|m| m.name == name.name) {
+                let mut ty = member.ty;
+                // Functions accessed through a contract instance are called externally.
+                if let (TyKind::Contract(_), TyKind::FnPtr(f)) = (&base_ty.kind, &ty.kind) {
+                    ty = with_visibility(gcx, f, Visibility::External);
+                }
+                push_candidate(gcx, &mut candidates, Candidate { ty, res: member.res });
+            }
+            // `f.selector` where `f` refers to a public function by name.
+            if candidates.is_empty() && name.name == sym::selector {
+                if let Some(&Res::Item(hir::ItemId::Function(f))) = self.resolutions.get(&base.id) {
+                    if gcx.hir.function(f).is_part_of_external_interface() {
+                        let builtin = Builtin::FunctionSelector;
+                        candidates.push(Candidate {
+                            ty: builtin.ty(gcx),
+                            res: Some(Res::Builtin(builtin)),
+                        });
+                    }
+                }
+            }
+            // Functions attached with `using for` directives.
+            let attached = gcx.attached_functions((self.source, self.contract, base_ty));
+            for member in attached.iter().filter(|m| m.name == name.name) {
+                push_candidate(gcx, &mut candidates, Candidate { ty: member.ty, res: member.res });
+            }
+        }
+
+        if candidates.is_empty() {
+            let msg = format!("no member named `{name}` on type `{}`", base_ty.display(gcx));
+            let guar = gcx.dcx().err(msg).span(name.span).emit();
+            candidates.push(Candidate { ty: gcx.mk_ty_err(guar), res: None });
+        }
+        candidates
+    }
+
+    // --- Utils ---
+
+    /// Returns the type that is used when the expression's value is stored, for literals.
+    fn mobile_type(&self, expr: &hir::Expr<'_>, ty: Ty<'gcx>) -> Ty<'gcx> {
+        match ty.kind {
+            TyKind::IntLiteral(size)
+                if matches!(
+                    expr.peel_parens().kind,
+                    hir::ExprKind::Unary(UnOp { kind: UnOpKind::Neg, .. }, _)
+                ) =>
+            {
+                self.gcx.types.int_(size)
+            }
+            _ => ty.mobile(self.gcx),
+        }
+    }
+
+    /// Returns the value of the expression if it is a literal with a fractional part.
+    fn fractional_literal(&self, expr: &hir::Expr<'_>, ty: Ty<'gcx>) -> Option<ConstValue> {
+        if !matches!(ty.kind, TyKind::IntLiteral(_)) {
+            return None;
+        }
+        let value = ConstantEvaluator::new(self.gcx).try_eval(expr).ok()?;
+        value.as_integer().is_none().then_some(value)
+    }
+}
+
+/// Adds a candidate to the list, unless it is a function with the same parameters as a previous
+/// one that refers to the same declaration, or to one that it overrides or is overridden by.
+///
+/// Other functions with the same parameters are kept, so that calls to them are ambiguous.
+fn push_candidate<'gcx>(
+    gcx: Gcx<'gcx>,
+    candidates: &mut Candidates<'gcx>,
+    candidate: Candidate<'gcx>,
+) {
+    let is_duplicate = candidates.iter().any(|c| {
+        same_parameters(c.ty, candidate.ty)
+            && (c.res == candidate.res || in_override_chain(gcx, c.res, candidate.res))
+    });
+    if !is_duplicate {
+        candidates.push(candidate);
+    }
+}
+
+/// Returns `true` if both types are functions with the same parameters.
+fn same_parameters(a: Ty<'_>, b: Ty<'_>) -> bool {
+    matches!((&a.kind, &b.kind), (TyKind::FnPtr(a), TyKind::FnPtr(b)) if a.parameters == b.parameters)
+}
+
+/// Returns `true` if the contract of one of the declarations inherits from the contract of the
+/// other one.
+fn in_override_chain(gcx: Gcx<'_>, a: Option<Res>, b: Option<Res>) -> bool {
+    let (Some(Res::Item(a)), Some(Res::Item(b))) = (a, b) else { return false };
+    let (Some(a), Some(b)) = (gcx.hir.item(a).contract(), gcx.hir.item(b).contract()) else {
+        return false;
+    };
+    a != b
+        && (gcx.hir.contract(a).linearized_bases.contains(&b)
+            || gcx.hir.contract(b).linearized_bases.contains(&a))
+}
+
+/// Returns the functions bound to `operator` for the user-defined value type `ty` that take
+/// `arity` parameters.
+///
+/// Operators can only be defined in `global` directives, so only the source that defines the
+/// type is searched.
+fn operator_definitions(
+    gcx: Gcx<'_>,
+    ty: Ty<'_>,
+    operator: UserDefinableOperator,
+    arity: usize,
+) -> SmallVec<[hir::FunctionId; 1]> {
+    let TyKind::Udvt(_, id) = ty.kind else { return SmallVec::new() };
+    let mut definitions = SmallVec::new();
+    for using in gcx.hir.source(gcx.hir.udvt(id).source).usings {
+        if !using.global || !using.ty.as_ref().is_some_and(|t| gcx.type_of_hir_ty(t) == ty) {
+            continue;
+        }
+        let hir::UsingList::Functions(functions) = using.list else { continue };
+        for f in functions {
+            if f.operator == Some(operator)
+                && gcx.hir.function(f.id).parameters.len() == arity
+                && !definitions.contains(&f.id)
+            {
+                definitions.push(f.id);
+            }
+        }
+    }
+    definitions
+}
+
+fn with_visibility<'gcx>(gcx: Gcx<'gcx>, f: &TyFnPtr<'gcx>, visibility: Visibility) -> Ty<'gcx> {
+    gcx.mk_ty_fn_ptr(TyFnPtr { visibility, ..*f })
+}
+
+/// Returns the size of an integer literal with the given number of bits.
+fn int_literal_size(bits: u64) -> TypeSize {
+    TypeSize::new(bits.div_ceil(8).clamp(1, 32) as u8).unwrap()
+}
+
+/// Returns `true` if the value of the number literal `expr` can be explicitly converted to `to`.
+///
+/// Integers must be in range of the type, fixed bytes can only be created from zero or from hex
+/// literals of the same size, and addresses from integers with at most 160 bits.
+fn literal_convertible_to(
+    gcx: Gcx<'_>,
+    expr: &hir::Expr<'_>,
+    value: &ConstValue,
+    to: Ty<'_>,
+) -> bool {
+    let Some(integer) = value.as_integer() else {
+        return to.is_fixed_point();
+    };
+    match to.kind {
+        TyKind::Elementary(ElementaryType::Int(_) | ElementaryType::UInt(_)) => {
+            value.clone().convert(to, false).is_ok()
+        }
+        TyKind::Elementary(ElementaryType::FixedBytes(size)) => {
+            integer.is_zero() || hex_literal_len(expr) == Some(size.bytes() as usize)
+        }
+        TyKind::Elementary(ElementaryType::Address(_)) => {
+            integer.sign() != Sign::Minus && integer.bits() <= 160
+        }
+        TyKind::Enum(id) => {
+            integer.sign() != Sign::Minus && integer < gcx.hir.enumm(id).variants.len().into()
+        }
+        _ => true,
+    }
+}
+
+/// Returns the number of bytes of the hex number literal `expr`, if it is one.
+fn hex_literal_len(expr: &hir::Expr<'_>) -> Option<usize> {
+    let hir::ExprKind::Lit(lit) = expr.peel_parens().kind else { return None };
+    let digits = lit.symbol.as_str().strip_prefix("0x")?.chars().filter(|&c| c != '_').count();
+    (digits % 2 == 0).then_some(digits / 2)
+}
@@ -0,0 +1,521 @@
+//! Checks the data locations of the variables declared in functions, and of the values that are
+//! assigned, returned and passed to them.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/types.html#data-location>
+
+use crate::{
+    cfg::{BlockId, Cfg, Terminator},
+    hir::{self, DataLocation, Res, StmtKind, Visit},
+    ty::{Gcx, Ty, TyKind, TypeckResults},
+};
+use solar_data_structures::{index::IndexVec, Never};
+use solar_interface::Span;
+use std::ops::ControlFlow;
+
+/// Checks the data locations in the given function.
+pub(super) fn check(gcx: Gcx<'_>, id: hir::FunctionId) {
+    let f = gcx.hir.function(id);
+    if f.is_getter() {
+        return;
+    }
+    for var in f.variables() {
+        check_mapping_location(gcx, var);
+    }
+    check_calldata_parameters(gcx, id);
+    let Some(body) = f.body else { return };
+
+    let mut checker = DataLocationChecker::new(gcx, id);
+    for stmt in body {
+        let _ = checker.visit_stmt(stmt);
+    }
+    check_unassigned_pointers(gcx, id);
+}
+
+/// Checks that a variable with a mapping type is located in storage.
+fn check_mapping_location(gcx: Gcx<'_>, id: hir::VariableId) {
+    let var = gcx.hir.variable(id);
+    let ty = gcx.type_of_item(id.into());
+    if !ty.has_mapping() || ty.has_error().is_err() {
+        return;
+    }
+    if let Some(loc @ (DataLocation::Memory | DataLocation::Calldata)) = var.data_location {
+        let msg = format!(
+            "type `{}` is only valid in storage because it contains a (nested) mapping",
+            ty.peel_refs().display(gcx),
+        );
+        gcx.dcx().err(msg).span(var.span).help(format!("replace `{loc}` with `storage`")).emit();
+    }
+}
+
+/// Checks that the parameters of internal and private functions are not located in calldata when
+/// targeting a Solidity version before 0.6.9, which only allowed it in external functions.
+fn check_calldata_parameters(gcx: Gcx<'_>, id: hir::FunctionId) {
+    let f = gcx.hir.function(id);
+    let version = gcx.sess.opts.solc_version();
+    if f.visibility > hir::Visibility::Internal || version >= semver::Version::new(0, 6, 9) {
+        return;
+    }
+    for var in f.variables() {
+        let var = gcx.hir.variable(var);
+        if var.data_location == Some(DataLocation::Calldata) {
+            let msg = format!(
+                "`calldata` is only allowed for the parameters of {} functions since Solidity 0.6.9",
+                f.visibility
+            );
+            gcx.dcx()
+                .err(msg)
+                .span(var.span)
+                .note(format!("the active version is `{version}`"))
+                .help("use `memory` instead, or `--solc-version` to select a different version")
+                .emit();
+        }
+    }
+}
+
+/// Returns `true` if the variable is a storage or calldata pointer, which must be assigned before
+/// it is accessed.
+fn is_pointer(gcx: Gcx<'_>, id: hir::VariableId) -> bool {
+    let ty = gcx.type_of_item(id.into());
+    ty.is_ref_at(DataLocation::Storage) || ty.is_ref_at(DataLocation::Calldata)
+}
+
+/// Reports storage and calldata pointers which can be accessed, or returned when the function
+/// returns without a value, on a code path on which they are not assigned.
+///
+/// This is a definite assignment analysis on the control-flow graph of the function, like the one
+/// for return variables in `flow`.
+fn check_unassigned_pointers(gcx: Gcx<'_>, id: hir::FunctionId) {
+    let f = gcx.hir.function(id);
+    let Some(cfg) = gcx.cfg(id) else { return };
+
+    // The return variables come first, followed by the local variables declared without an
+    // initializer.
+    let mut pointers: Vec<_> =
+        f.returns.iter().copied().filter(|&var| is_pointer(gcx, var)).collect();
+    let returns = pointers.len();
+    for (_, block) in cfg.iter_blocks() {
+        for stmt in block.stmts {
+            match stmt.kind {
+                StmtKind::DeclSingle(var)
+                    if gcx.hir.variable(var).initializer.is_none() && is_pointer(gcx, var) =>
+                {
+                    pointers.push(var);
+                }
+                // Assembly blocks are not lowered, and could assign the pointers.
+                StmtKind::Assembly(_) => return,
+                _ => {}
+            }
+        }
+    }
+    if pointers.is_empty() {
+        return;
+    }
+
+    let reachable = cfg.reachable_blocks();
+    let mut predecessors =
+        IndexVec::<BlockId, Vec<BlockId>>::from_vec(vec![Vec::new(); cfg.blocks.len()]);
+    for (id, block) in cfg.iter_blocks() {
+        for succ in block.terminator.successors() {
+            predecessors[succ].push(id);
+        }
+    }
+
+    // The pointers that are definitely assigned at the end of each block.
+    let mut assigned_out =
+        IndexVec::<BlockId, _>::from_vec(vec![vec![true; pointers.len()]; cfg.blocks.len()]);
+    let assigned_in = |id: BlockId, assigned_out: &IndexVec<BlockId, Vec<bool>>| {
+        let mut assigned = vec![id != Cfg::ENTRY; pointers.len()];
+        for &p in predecessors[id].iter().filter(|&&p| reachable[p]) {
+            assigned.iter_mut().zip(&assigned_out[p]).for_each(|(a, &b)| *a &= b);
+        }
+        assigned
+    };
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, block) in cfg.iter_blocks() {
+            if !reachable[id] {
+                continue;
+            }
+            let mut v = PointerAssignments {
+                gcx,
+                pointers: &pointers,
+                returns,
+                assigned: assigned_in(id, &assigned_out),
+                reported: None,
+            };
+            v.visit_block(block);
+            if v.assigned != assigned_out[id] {
+                assigned_out[id] = v.assigned;
+                changed = true;
+            }
+        }
+    }
+
+    let mut reported = vec![false; pointers.len()];
+    for (id, block) in cfg.iter_blocks() {
+        if !reachable[id] {
+            continue;
+        }
+        let mut v = PointerAssignments {
+            gcx,
+            pointers: &pointers,
+            returns,
+            assigned: assigned_in(id, &assigned_out),
+            reported: Some(&mut reported),
+        };
+        v.visit_block(block);
+        if !matches!(block.terminator, Terminator::Return) {
+            continue;
+        }
+        // Return variables are read when the function returns without a value.
+        let assigned = v.assigned;
+        for i in 0..returns {
+            if !assigned[i] && !std::mem::replace(&mut reported[i], true) {
+                report_unassigned_pointer(gcx, pointers[i], "returned");
+            }
+        }
+    }
+}
+
+fn report_unassigned_pointer(gcx: Gcx<'_>, id: hir::VariableId, action: &str) {
+    let var = gcx.hir.variable(id);
+    let loc = gcx.type_of_item(id.into()).loc().unwrap();
+    let msg = format!(
+        "this variable is of {loc} pointer type and can be {action} without prior assignment, \
+         which would lead to undefined behaviour"
+    );
+    gcx.dcx()
+        .err(msg)
+        .span(var.span)
+        .help(format!("assign a `{loc}` reference to it, or declare it as `memory`"))
+        .emit();
+}
+
+struct DataLocationChecker<'gcx> {
+    gcx: Gcx<'gcx>,
+    results: TypeckResults<'gcx>,
+    function: &'gcx hir::Function<'gcx>,
+}
+
+impl<'gcx> DataLocationChecker<'gcx> {
+    fn new(gcx: Gcx<'gcx>, id: hir::FunctionId) -> Self {
+        Self { gcx, results: gcx.typeck(id.into()), function: gcx.hir.function(id) }
+    }
+
+    fn ty(&self, expr: &hir::Expr<'_>) -> Option<Ty<'gcx>> {
+        self.results.expr(expr.peel_parens().id).map(|expr| expr.ty)
+    }
+
+    /// Returns the local variable that the expression refers to, if any.
+    fn local_variable(&self, expr: &hir::Expr<'_>) -> Option<hir::VariableId> {
+        let hir::ExprKind::Ident(res) = expr.peel_parens().kind else { return None };
+        res.iter().find_map(|res| match *res {
+            Res::Item(hir::ItemId::Variable(id))
+                if !self.gcx.hir.variable(id).is_state_variable() =>
+            {
+                Some(id)
+            }
+            _ => None,
+        })
+    }
+
+    /// Checks that a value of type `found` can be stored in the variable `var`.
+    fn check_variable(&self, var: hir::VariableId, found: Ty<'gcx>, span: Span) {
+        let expected = self.gcx.type_of_item(var.into());
+        let name = self.gcx.hir.variable(var).name;
+        self.check_location(expected, found, span, |found| match name {
+            Some(name) => format!("declare `{name}` as `{found}` to copy the value"),
+            None => format!("declare the variable as `{found}` to copy the value"),
+        });
+    }
+
+    /// Checks that a value of type `found` can be used where a value of type `expected` is
+    /// expected.
+    ///
+    /// Values in other locations can be copied to storage, but storage pointers can only refer to
+    /// values that are already in storage.
+    fn check_location(
+        &self,
+        expected: Ty<'gcx>,
+        found: Ty<'gcx>,
+        span: Span,
+        help: impl FnOnce(DataLocation) -> String,
+    ) {
+        if !expected.is_ref_at(DataLocation::Storage) {
+            return;
+        }
+        let Some(found_loc) = found.loc() else { return };
+        if found_loc == DataLocation::Storage || found.has_error().is_err() {
+            return;
+        }
+        let label = format!("expected `storage`, found `{found_loc}`");
+        self.gcx
+            .dcx()
+            .err("mismatched data locations")
+            .span(span)
+            .span_label(span, label)
+            .help(help(found_loc))
+            .emit();
+    }
+
+    fn check_assign(&mut self, lhs: &'gcx hir::Expr<'gcx>, rhs_ty: Option<Ty<'gcx>>) {
+        let lhs = lhs.peel_parens();
+        match lhs.kind {
+            hir::ExprKind::Tuple(components) => {
+                let rhs_tys = match rhs_ty.map(|ty| &ty.kind) {
+                    Some(TyKind::Tuple(tys)) if tys.len() == components.len() => Some(*tys),
+                    _ => None,
+                };
+                for (i, component) in components.iter().enumerate() {
+                    if let Some(component) = component {
+                        self.check_assign(component, rhs_tys.map(|tys| tys[i]));
+                    }
+                }
+            }
+            _ => {
+                if let Some(var) = self.local_variable(lhs) {
+                    if let Some(rhs_ty) = rhs_ty {
+                        self.check_variable(var, rhs_ty, lhs.span);
+                    }
+                } else {
+                    self.check_calldata_write(lhs);
+                    let _ = self.visit_expr(lhs);
+                }
+            }
+        }
+    }
+
+    /// Checks that the expression doesn't modify a value in calldata.
+    fn check_calldata_write(&self, expr: &'gcx hir::Expr<'gcx>) {
+        let (hir::ExprKind::Member(base, _) | hir::ExprKind::Index(base, _)) =
+            expr.peel_parens().kind
+        else {
+            return;
+        };
+        if self.ty(base).is_some_and(|ty| ty.is_ref_at(DataLocation::Calldata)) {
+            self.gcx
+                .dcx()
+                .err("calldata is read-only")
+                .span(expr.span)
+                .help("copy the value to `memory` to modify it")
+                .emit();
+        }
+    }
+
+    fn check_call_args(&self, callee: &'gcx hir::Expr<'gcx>, args: &'gcx hir::CallArgs<'gcx>) {
+        let Some(TyKind::FnPtr(f)) = self.ty(callee).map(|ty| &ty.kind) else { return };
+        let decl = match self.results.expr(callee.peel_parens().id).and_then(|e| e.res) {
+            Some(Res::Item(hir::ItemId::Function(id))) => Some(self.gcx.hir.function(id)),
+            _ => None,
+        };
+        // Attached functions are called with their first parameter bound.
+        let names = decl.map(|decl| {
+            let skip = decl.parameters.len().saturating_sub(f.parameters.len());
+            &decl.parameters[skip..]
+        });
+        let name_of = |i: usize| names.and_then(|names| self.gcx.hir.variable(names[i]).name);
+
+        let arg_params = match args {
+            hir::CallArgs::Unnamed(exprs) => {
+                exprs.iter().enumerate().map(|(i, arg)| (arg, Some(i))).collect::<Vec<_>>()
+            }
+            hir::CallArgs::Named(named) => named
+                .iter()
+                .map(|arg| {
+                    let i = (0..f.parameters.len())
+                        .find(|&i| name_of(i).is_some_and(|n| n.name == arg.name.name));
+                    (&arg.value, i)
+                })
+                .collect(),
+        };
+        for (arg, i) in arg_params {
+            let (Some(i), Some(found)) = (i, self.ty(arg)) else { continue };
+            let Some(&expected) = f.parameters.get(i) else { continue };
+            self.check_location(expected, found, arg.span, |found| match name_of(i) {
+                Some(name) => {
+                    format!(
+                        "pass a `storage` reference, or declare parameter `{name}` as `{found}`"
+                    )
+                }
+                None => {
+                    format!("pass a `storage` reference, or declare the parameter as `{found}`")
+                }
+            });
+        }
+    }
+
+    fn check_return(&self, expr: &'gcx hir::Expr<'gcx>) {
+        let returns = self.function.returns;
+        let Some(found) = self.ty(expr) else { return };
+        let found_tys = match (&found.kind, returns.len()) {
+            (TyKind::Tuple(tys), len) if len != 1 => *tys,
+            _ => std::slice::from_ref(&found),
+        };
+        let exprs = match expr.peel_parens().kind {
+            hir::ExprKind::Tuple(exprs) if exprs.len() == returns.len() && returns.len() != 1 => {
+                Some(exprs)
+            }
+            _ => None,
+        };
+        for (i, (&var, &found)) in returns.iter().zip(found_tys).enumerate() {
+            let span = exprs.and_then(|exprs| exprs[i]).map_or(expr.span, |e| e.span);
+            let expected = self.gcx.type_of_item(var.into());
+            self.check_location(expected, found, span, |found| {
+                format!("declare the return variable as `{found}`")
+            });
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for DataLocationChecker<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match stmt.kind {
+            hir::StmtKind::DeclSingle(var) => {
+                check_mapping_location(self.gcx, var);
+                if let Some(init) = self.gcx.hir.variable(var).initializer {
+                    if let Some(ty) = self.ty(init) {
+                        self.check_variable(var, ty, init.span);
+                    }
+                }
+            }
+            hir::StmtKind::DeclMulti(vars, expr) => {
+                let tys = match self.ty(expr).map(|ty| &ty.kind) {
+                    Some(TyKind::Tuple(tys)) if tys.len() == vars.len() => Some(*tys),
+                    _ => None,
+                };
+                for (i, &var) in vars.iter().enumerate() {
+                    let Some(var) = var else { continue };
+                    check_mapping_location(self.gcx, var);
+                    if let Some(tys) = tys {
+                        self.check_variable(var, tys[i], self.gcx.hir.variable(var).span);
+                    }
+                }
+            }
+            hir::StmtKind::Return(Some(expr)) => self.check_return(expr),
+            hir::StmtKind::Try(try_) => {
+                for clause in try_.clauses {
+                    for &var in clause.args {
+                        check_mapping_location(self.gcx, var);
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            hir::ExprKind::Assign(lhs, op, rhs) => {
+                let rhs_ty = if op.is_none() { self.ty(rhs) } else { None };
+                self.check_assign(lhs, rhs_ty);
+                return self.visit_expr(rhs);
+            }
+            hir::ExprKind::Delete(operand) => self.check_calldata_write(operand),
+            hir::ExprKind::Call(callee, ref args, _) => self.check_call_args(callee, args),
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
+/// Tracks the pointers that are definitely assigned while visiting the statements of a block.
+struct PointerAssignments<'a, 'gcx> {
+    gcx: Gcx<'gcx>,
+    /// The tracked pointers, starting with the return variables.
+    pointers: &'a [hir::VariableId],
+    /// The number of return variables in `pointers`.
+    returns: usize,
+    /// Whether each of the pointers is definitely assigned.
+    assigned: Vec<bool>,
+    /// The pointers that have already been reported, if accesses to unassigned pointers should
+    /// be reported.
+    reported: Option<&'a mut Vec<bool>>,
+}
+
+impl<'gcx> PointerAssignments<'_, 'gcx> {
+    fn visit_block(&mut self, block: &crate::cfg::BasicBlock<'gcx>) {
+        for stmt in block.stmts {
+            let _ = self.visit_stmt(stmt);
+        }
+        match block.terminator {
+            Terminator::Branch { cond, .. } => _ = self.visit_expr(cond),
+            Terminator::Try { stmt, .. } => _ = self.visit_expr(&stmt.expr),
+            _ => {}
+        }
+    }
+
+    /// Returns the index of the pointer that the expression refers to, if any.
+    fn pointer(&self, expr: &hir::Expr<'_>) -> Option<usize> {
+        let hir::ExprKind::Ident(res) = expr.peel_parens().kind else { return None };
+        res.iter().find_map(|res| match *res {
+            Res::Item(hir::ItemId::Variable(var)) => self.pointers.iter().position(|&p| p == var),
+            _ => None,
+        })
+    }
+
+    fn assign(&mut self, lhs: &'gcx hir::Expr<'gcx>) {
+        if let hir::ExprKind::Tuple(components) = lhs.peel_parens().kind {
+            for component in components.iter().flatten() {
+                self.assign(component);
+            }
+        } else if let Some(i) = self.pointer(lhs) {
+            self.assigned[i] = true;
+        } else {
+            let _ = self.visit_expr(lhs);
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for PointerAssignments<'_, 'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match stmt.kind {
+            // Declarations in loops are unassigned again in each iteration.
+            StmtKind::DeclSingle(var) => {
+                if let Some(i) = self.pointers.iter().position(|&p| p == var) {
+                    self.assigned[i] = false;
+                }
+            }
+            StmtKind::Return(Some(expr)) => {
+                self.visit_expr(expr)?;
+                self.assigned[..self.returns].fill(true);
+                return ControlFlow::Continue(());
+            }
+            _ => {}
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            // The right-hand side is evaluated first.
+            hir::ExprKind::Assign(lhs, None, rhs) => {
+                self.visit_expr(rhs)?;
+                self.assign(lhs);
+                return ControlFlow::Continue(());
+            }
+            hir::ExprKind::Ident(_) => {
+                if let (Some(i), Some(reported)) = (self.pointer(expr), &mut self.reported) {
+                    if !self.assigned[i] && !std::mem::replace(&mut reported[i], true) {
+                        report_unassigned_pointer(self.gcx, self.pointers[i], "accessed");
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
@@ -0,0 +1,211 @@
+//! Control-flow checks: unreachable code and return variables which can remain unassigned.
+
+use crate::{
+    cfg::{BlockId, Cfg, Terminator},
+    hir::{self, Res, StmtKind, Visit},
+    ty::Gcx,
+};
+use solar_data_structures::{index::IndexVec, Never};
+use solar_interface::Span;
+use std::ops::ControlFlow;
+
+/// Checks the control flow of the given function or modifier body.
+pub(super) fn check(gcx: Gcx<'_>, id: hir::FunctionId) {
+    let f = gcx.hir.function(id);
+    if f.is_getter() {
+        return;
+    }
+    let Some(cfg) = gcx.cfg(id) else { return };
+    let reachable = cfg.reachable_blocks();
+    check_unreachable_code(gcx, cfg, &reachable);
+    check_unassigned_returns(gcx, f, cfg, &reachable);
+}
+
+/// Warns about code that can't be reached, once per connected region of unreachable blocks.
+fn check_unreachable_code(gcx: Gcx<'_>, cfg: &Cfg<'_>, reachable: &IndexVec<BlockId, bool>) {
+    let mut visited = reachable.clone();
+    for (start, _) in cfg.iter_blocks() {
+        if visited[start] {
+            continue;
+        }
+
+        let mut span: Option<Span> = None;
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if std::mem::replace(&mut visited[id], true) {
+                continue;
+            }
+            let block = cfg.block(id);
+            let cond_span = match block.terminator {
+                Terminator::Branch { cond, .. } => Some(cond.span),
+                Terminator::Try { stmt, .. } => Some(stmt.expr.span),
+                Terminator::Placeholder { span, .. } => Some(span),
+                _ => None,
+            };
+            for s in block.stmts.iter().map(|stmt| stmt.span).chain(cond_span) {
+                span = Some(span.map_or(s, |span| span.to(s)));
+            }
+            stack.extend(block.terminator.successors());
+        }
+
+        if let Some(span) = span {
+            gcx.dcx().warn("unreachable code").span(span).emit();
+        }
+    }
+}
+
+/// Warns about return variables which can remain unassigned when the function returns without a
+/// value.
+///
+/// Like in solc, unnamed return variables must be assigned on every such path. Named return
+/// variables are commonly left at their default value on some paths, so they are only reported
+/// if no path assigns them.
+fn check_unassigned_returns<'gcx>(
+    gcx: Gcx<'gcx>,
+    f: &'gcx hir::Function<'gcx>,
+    cfg: &'gcx Cfg<'gcx>,
+    reachable: &IndexVec<BlockId, bool>,
+) {
+    let returns = f.returns;
+    if returns.is_empty() {
+        return;
+    }
+    // Empty bodies are usually stubs which are meant to return the default values of the named
+    // return variables.
+    let is_stub = f.body.is_some_and(|body| body.is_empty());
+    // Assembly blocks are not lowered, and could assign the return variables.
+    let has_assembly = cfg.iter_blocks().any(|(_, block)| {
+        block.stmts.iter().any(|stmt| matches!(stmt.kind, StmtKind::Assembly(_)))
+    });
+    if has_assembly {
+        return;
+    }
+
+    // The variables assigned in each block.
+    let assigned_in = cfg
+        .iter_blocks()
+        .map(|(_, block)| {
+            let mut v = AssignedVariables { gcx, returns, assigned: vec![false; returns.len()] };
+            for stmt in block.stmts {
+                let _ = v.visit_stmt(stmt);
+            }
+            match block.terminator {
+                Terminator::Branch { cond, .. } => _ = v.visit_expr(cond),
+                Terminator::Try { stmt, .. } => _ = v.visit_expr(&stmt.expr),
+                _ => {}
+            }
+            v.assigned
+        })
+        .collect::<IndexVec<BlockId, _>>();
+
+    // The variables that are definitely assigned at the end of each block.
+    let mut assigned_out =
+        IndexVec::<BlockId, _>::from_vec(vec![vec![true; returns.len()]; cfg.blocks.len()]);
+    let mut predecessors =
+        IndexVec::<BlockId, Vec<BlockId>>::from_vec(vec![Vec::new(); cfg.blocks.len()]);
+    for (id, block) in cfg.iter_blocks() {
+        for succ in block.terminator.successors() {
+            predecessors[succ].push(id);
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, _) in cfg.iter_blocks() {
+            if !reachable[id] {
+                continue;
+            }
+            let mut out = vec![id != Cfg::ENTRY; returns.len()];
+            for &p in predecessors[id].iter().filter(|&&p| reachable[p]) {
+                out.iter_mut().zip(&assigned_out[p]).for_each(|(a, &b)| *a &= b);
+            }
+            out.iter_mut().zip(&assigned_in[id]).for_each(|(a, &b)| *a |= b);
+            if out != assigned_out[id] {
+                assigned_out[id] = out;
+                changed = true;
+            }
+        }
+    }
+
+    let mut reported = vec![false; returns.len()];
+    for (id, block) in cfg.iter_blocks() {
+        if !reachable[id] || !matches!(block.terminator, Terminator::Return) {
+            continue;
+        }
+        if block.stmts.last().is_some_and(|stmt| matches!(stmt.kind, StmtKind::Return(Some(_)))) {
+            continue;
+        }
+        for (i, &var) in returns.iter().enumerate() {
+            let var = gcx.hir.variable(var);
+            if var.name.is_some() && (is_stub || assigned_in.iter().any(|assigned| assigned[i])) {
+                continue;
+            }
+            if assigned_out[id][i] || std::mem::replace(&mut reported[i], true) {
+                continue;
+            }
+            let (msg, help) = match var.name {
+                Some(name) => (
+                    format!("return variable `{name}` can remain unassigned"),
+                    "assign it on all non-reverting code paths, or return a value explicitly",
+                ),
+                None => (
+                    "unnamed return variable can remain unassigned".to_string(),
+                    "add an explicit `return` with a value to all non-reverting code paths, \
+                     or name the variable",
+                ),
+            };
+            gcx.dcx().warn(msg).span(var.span).help(help).emit();
+        }
+    }
+}
+
+/// Collects the return variables that are assigned in a statement.
+struct AssignedVariables<'gcx> {
+    gcx: Gcx<'gcx>,
+    returns: &'gcx [hir::VariableId],
+    assigned: Vec<bool>,
+}
+
+impl AssignedVariables<'_> {
+    fn assign(&mut self, lhs: &hir::Expr<'_>) {
+        match lhs.peel_parens().kind {
+            hir::ExprKind::Tuple(exprs) => {
+                for expr in exprs.iter().flatten() {
+                    self.assign(expr);
+                }
+            }
+            hir::ExprKind::Ident(res) => {
+                for res in res {
+                    if let Res::Item(hir::ItemId::Variable(var)) = *res {
+                        if let Some(i) = self.returns.iter().position(|&r| r == var) {
+                            self.assigned[i] = true;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for AssignedVariables<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        if let StmtKind::Return(Some(_)) = stmt.kind {
+            self.assigned.fill(true);
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        if let hir::ExprKind::Assign(lhs, _, _) = expr.kind {
+            self.assign(lhs);
+        }
+        self.walk_expr(expr)
+    }
+}
@@ -0,0 +1,52 @@
+//! Checks that non-abstract contracts implement all the functions and modifiers they declare or
+//! inherit.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/contracts.html#abstract-contracts>
+
+use super::overrides::{description, function_name, name_span, signatures_match};
+use crate::{
+    hir::{ContractId, FunctionId},
+    ty::Gcx,
+};
+
+/// Reports every function or modifier that is left unimplemented in the given contract.
+pub(super) fn check(gcx: Gcx<'_>, id: ContractId) {
+    let contract = gcx.hir.contract(id);
+    if !contract.kind.is_contract() {
+        return;
+    }
+
+    // Walk the bases from the most derived one, so that a definition shadows all the ones with
+    // the same signature in the contracts that come after it.
+    let mut seen = Vec::<FunctionId>::new();
+    for &base in contract.linearized_bases {
+        for f in gcx.hir.contract(base).all_functions() {
+            let func = gcx.hir.function(f);
+            if func.kind.is_constructor() || seen.iter().any(|&g| signatures_match(gcx, g, f)) {
+                continue;
+            }
+            seen.push(f);
+            if func.body.is_some() || func.is_getter() {
+                continue;
+            }
+
+            let descr = description(gcx, f);
+            let msg = format!(
+                "contract `{}` is missing an implementation of {descr} `{}`",
+                contract.name,
+                function_name(gcx, f),
+            );
+            let note = if base == id {
+                format!("{descr} declared here")
+            } else {
+                format!("{descr} inherited from `{}` declared here", gcx.hir.contract(base).name)
+            };
+            gcx.dcx()
+                .err(msg)
+                .span(contract.name.span)
+                .span_note(name_span(gcx, f), note)
+                .help("implement it or mark the contract as `abstract`")
+                .emit();
+        }
+    }
+}
@@ -1,21 +1,48 @@
 use crate::{
     ast_lowering::resolve::{Declaration, Declarations},
     hir::{self, Res},
-    ty::{Gcx, Ty},
+    ty::{Gcx, Ty, TyKind},
 };
 use rayon::prelude::*;
+use solar_ast::StateMutability;
 use solar_data_structures::{map::FxHashSet, parallel};
 
+mod checker;
+pub(crate) use checker::typeck;
+
+mod data_locations;
+mod flow;
+mod implementations;
+mod mutability;
+mod overrides;
+
 pub(crate) fn check(gcx: Gcx<'_>) {
     parallel!(
         gcx.sess,
         gcx.hir.par_contract_ids().for_each(|id| {
             check_duplicate_definitions(gcx, &gcx.symbol_resolver.contract_scopes[id]);
+            check_operator_definitions(gcx, gcx.hir.contract(id).usings);
+            overrides::check(gcx, id);
+            implementations::check(gcx, id);
+            _ = gcx.typeck(id.into());
         }),
         gcx.hir.par_source_ids().for_each(|id| {
             check_duplicate_definitions(gcx, &gcx.symbol_resolver.source_scopes[id]);
+            check_operator_definitions(gcx, gcx.hir.source(id).usings);
         }),
+        gcx.hir.par_function_ids().for_each(|id| _ = gcx.typeck(id.into())),
+        gcx.hir.par_variable_ids().for_each(|id| _ = gcx.typeck(id.into())),
     );
+
+    gcx.hir.par_function_ids().for_each(|id| data_locations::check(gcx, id));
+
+    // Like in solc, state mutability and control flow are only checked once the bodies type check.
+    if gcx.dcx().has_errors().is_ok() {
+        gcx.hir.par_function_ids().for_each(|id| {
+            mutability::check(gcx, id);
+            flow::check(gcx, id);
+        });
+    }
 }
 
 /// Checks for definitions that have the same name and parameter types in the given scope.
@@ -76,6 +103,64 @@ fn check_duplicate_definitions(gcx: Gcx<'_>, scope: &Declarations) {
     }
 }
 
+/// Checks the functions that are bound to operators in the given `using for` directives.
+fn check_operator_definitions(gcx: Gcx<'_>, usings: &[hir::UsingDirective<'_>]) {
+    for using in usings {
+        let hir::UsingList::Functions(functions) = using.list else { continue };
+        for f in functions {
+            let Some(operator) = f.operator else { continue };
+            let err = |msg: String| gcx.dcx().err(msg).span(f.span).emit();
+
+            if !using.global {
+                err("operators can only be defined in a global `using for` directive".into());
+                continue;
+            }
+            let ty = using.ty.as_ref().map(|ty| gcx.type_of_hir_ty(ty));
+            let Some(ty) = ty.filter(|ty| matches!(ty.kind, TyKind::Udvt(..))) else {
+                if ty.is_none_or(|ty| ty.has_error().is_ok()) {
+                    err("operators can only be implemented for user-defined value types".into());
+                }
+                continue;
+            };
+
+            let func = gcx.hir.function(f.id);
+            if func.contract.is_some() || func.state_mutability != StateMutability::Pure {
+                err("only pure free functions can be used to define operators".into());
+            }
+
+            let TyKind::FnPtr(fn_ty) = gcx.type_of_item(f.id.into()).kind else { unreachable!() };
+            let name = gcx.item_name(f.id);
+            let arity_ok = match fn_ty.parameters.len() {
+                1 => operator.is_unary(),
+                2 => operator.is_binary(),
+                _ => false,
+            };
+            if !arity_ok || fn_ty.parameters.iter().any(|&param| param != ty) {
+                let count = match (operator.is_unary(), operator.is_binary()) {
+                    (true, true) => "one or two parameters",
+                    (true, false) => "exactly one parameter",
+                    _ => "exactly two parameters",
+                };
+                err(format!(
+                    "wrong parameters in operator definition: the function `{name}` needs to have \
+                     {count} of type `{}` to be used for the operator `{operator}`",
+                    ty.display(gcx),
+                ));
+            }
+
+            let ret = if operator.is_comparison() { gcx.types.bool } else { ty };
+            if fn_ty.returns != [ret] {
+                err(format!(
+                    "wrong return parameters in operator definition: the function `{name}` needs \
+                     to return exactly one value of type `{}` to be used for the operator \
+                     `{operator}`",
+                    ret.display(gcx),
+                ));
+            }
+        }
+    }
+}
+
 fn same_external_params<'gcx>(gcx: Gcx<'gcx>, a: Ty<'gcx>, b: Ty<'gcx>) -> bool {
     let key = |ty: Ty<'gcx>| ty.as_externally_callable_function(gcx).parameters().unwrap();
     key(a) == key(b)
@@ -0,0 +1,371 @@
+//! Checks function bodies against their declared state mutability.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/contracts.html#state-mutability>
+
+use crate::{
+    builtins::Builtin,
+    hir::{self, Res, StateMutability, Visibility, Visit},
+    ty::{Gcx, Ty, TyKind, TypeckResults},
+};
+use solar_ast::{DataLocation, LitKind, UnOpKind};
+use solar_data_structures::Never;
+use solar_interface::{
+    diagnostics::{DiagBuilder, ErrorGuaranteed},
+    Span,
+};
+use std::ops::ControlFlow;
+
+/// Checks that the body of the given function, including the modifiers it invokes, doesn't
+/// access the state more than its declared state mutability allows, and warns if the function
+/// could be declared with a stricter one.
+pub(super) fn check(gcx: Gcx<'_>, id: hir::FunctionId) {
+    let f = gcx.hir.function(id);
+    let Some(body) = f.body else { return };
+    if f.kind.is_modifier() || f.is_getter() {
+        return;
+    }
+
+    let mut checker = MutabilityChecker::new(gcx, id, Some(f.state_mutability));
+    let _ = checker.visit_nested_function(id);
+    let mut required = checker.required;
+    let mut opaque = checker.opaque;
+
+    for modifier in f.modifiers {
+        let hir::ItemId::Function(modifier) = modifier.id else { continue };
+        let mut checker = MutabilityChecker::new(gcx, modifier, None);
+        let _ = checker.visit_nested_function(modifier);
+        let modifier_required = checker.required;
+        opaque |= checker.opaque;
+        if rank(modifier_required) > rank(required) {
+            required = modifier_required;
+        }
+        if rank(modifier_required) > rank(f.state_mutability) {
+            let subject = format!("modifier `{}`", gcx.item_name(modifier));
+            report(gcx, f.state_mutability, modifier_required, &subject, name_span(f))
+                .span_note(gcx.item_name(modifier).span, "modifier defined here")
+                .emit();
+        }
+    }
+
+    let can_be_restricted =
+        matches!(f.state_mutability, StateMutability::View | StateMutability::NonPayable)
+            && rank(required) < rank(f.state_mutability)
+            && f.kind.is_function()
+            && !f.virtual_
+            && !body.is_empty()
+            && !opaque;
+    if can_be_restricted {
+        let msg = format!("function state mutability can be restricted to `{required}`");
+        gcx.dcx().warn(msg).span(name_span(f)).emit();
+    }
+}
+
+struct MutabilityChecker<'gcx> {
+    gcx: Gcx<'gcx>,
+    function: &'gcx hir::Function<'gcx>,
+    results: TypeckResults<'gcx>,
+    /// The declared state mutability that accesses are reported against, if any.
+    declared: Option<StateMutability>,
+    /// The strictest state mutability that the visited code requires.
+    required: StateMutability,
+    /// Whether the visited code contains statements that can't be fully analyzed, like inline
+    /// assembly. This only prevents suggesting a stricter state mutability.
+    opaque: bool,
+}
+
+impl<'gcx> MutabilityChecker<'gcx> {
+    fn new(gcx: Gcx<'gcx>, id: hir::FunctionId, declared: Option<StateMutability>) -> Self {
+        Self {
+            gcx,
+            function: gcx.hir.function(id),
+            results: gcx.typeck(id.into()),
+            declared,
+            required: StateMutability::Pure,
+            opaque: false,
+        }
+    }
+
+    /// Records that the expression at `span` requires the given state mutability.
+    fn require(&mut self, mutability: StateMutability, span: Span) {
+        let mutability = match mutability {
+            StateMutability::Payable => StateMutability::NonPayable,
+            mutability => mutability,
+        };
+        if rank(mutability) > rank(self.required) {
+            self.required = mutability;
+        }
+        if let Some(declared) = self.declared {
+            if rank(mutability) > rank(declared) {
+                report(self.gcx, declared, mutability, "this expression", span).emit();
+            }
+        }
+    }
+
+    fn ty(&self, expr: &hir::Expr<'_>) -> Option<Ty<'gcx>> {
+        self.results.expr(expr.peel_parens().id).map(|expr| expr.ty)
+    }
+
+    fn res(&self, expr: &hir::Expr<'_>) -> Option<Res> {
+        let expr = expr.peel_parens();
+        match self.results.expr(expr.id).and_then(|expr| expr.res) {
+            Some(res) => Some(res),
+            None => match expr.kind {
+                hir::ExprKind::Ident(&[res]) => Some(res),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns `true` if the expression refers to a non-constant state variable.
+    fn is_state_variable(&self, expr: &hir::Expr<'_>) -> bool {
+        let Some(Res::Item(hir::ItemId::Variable(id))) = self.res(expr) else { return false };
+        let var = self.gcx.hir.variable(id);
+        var.is_state_variable() && !var.is_constant()
+    }
+
+    /// Returns `true` if the value of the expression is located in storage.
+    fn is_in_storage(&self, expr: &hir::Expr<'_>) -> bool {
+        self.ty(expr).is_some_and(|ty| {
+            matches!(
+                ty.kind,
+                TyKind::Ref(_, DataLocation::Storage | DataLocation::Transient)
+                    | TyKind::Mapping(..)
+            )
+        })
+    }
+
+    /// Returns `true` if accessing a member of or indexing into the given expression reads from
+    /// storage, and the read hasn't already been recorded by visiting the expression itself.
+    fn reads_storage(&self, base: &hir::Expr<'_>) -> bool {
+        self.is_in_storage(base) && !self.reads_state(base)
+    }
+
+    /// Returns `true` if visiting the expression records a read of the state.
+    fn reads_state(&self, expr: &hir::Expr<'_>) -> bool {
+        let expr = expr.peel_parens();
+        match expr.kind {
+            hir::ExprKind::Ident(_) => self.is_state_variable(expr),
+            hir::ExprKind::Member(base, _) | hir::ExprKind::Index(base, _) => {
+                self.is_in_storage(base) || self.reads_state(base)
+            }
+            _ => false,
+        }
+    }
+
+    /// Visits an expression that is assigned to or deleted.
+    fn visit_lvalue(&mut self, expr: &'gcx hir::Expr<'gcx>) {
+        let expr = expr.peel_parens();
+        match expr.kind {
+            hir::ExprKind::Tuple(exprs) => {
+                for expr in exprs.iter().flatten() {
+                    self.visit_lvalue(expr);
+                }
+            }
+            hir::ExprKind::Ident(_) => {
+                if self.is_state_variable(expr) {
+                    self.require(StateMutability::NonPayable, expr.span);
+                }
+            }
+            hir::ExprKind::Member(base, _) | hir::ExprKind::Index(base, _) => {
+                if self.is_in_storage(base) {
+                    self.require(StateMutability::NonPayable, expr.span);
+                    self.visit_written_base(base);
+                } else {
+                    self.visit_lvalue(base);
+                }
+                if let hir::ExprKind::Index(_, Some(index)) = expr.kind {
+                    let _ = self.visit_expr(index);
+                }
+            }
+            _ => {
+                let _ = self.visit_expr(expr);
+            }
+        }
+    }
+
+    /// Visits the base of a storage location that is written to, which is not read itself.
+    fn visit_written_base(&mut self, expr: &'gcx hir::Expr<'gcx>) {
+        let expr = expr.peel_parens();
+        match expr.kind {
+            hir::ExprKind::Ident(_) => {}
+            hir::ExprKind::Member(base, _) => self.visit_written_base(base),
+            hir::ExprKind::Index(base, index) => {
+                self.visit_written_base(base);
+                if let Some(index) = index {
+                    let _ = self.visit_expr(index);
+                }
+            }
+            _ => {
+                let _ = self.visit_expr(expr);
+            }
+        }
+    }
+
+    fn check_member(&mut self, expr: &'gcx hir::Expr<'gcx>, base: &'gcx hir::Expr<'gcx>) {
+        match self.res(expr) {
+            Some(Res::Builtin(builtin)) => match builtin {
+                Builtin::MsgValue => self.check_msg_value(expr.span),
+                Builtin::BlockCoinbase
+                | Builtin::BlockTimestamp
+                | Builtin::BlockDifficulty
+                | Builtin::BlockPrevrandao
+                | Builtin::BlockNumber
+                | Builtin::BlockGaslimit
+                | Builtin::BlockChainid
+                | Builtin::BlockBasefee
+                | Builtin::BlockBlobbasefee
+                | Builtin::MsgSender
+                | Builtin::MsgGas
+                | Builtin::MsgData
+                | Builtin::MsgSig
+                | Builtin::TxOrigin
+                | Builtin::TxGasPrice
+                | Builtin::AddressBalance
+                | Builtin::AddressCode
+                | Builtin::AddressCodehash => self.require(StateMutability::View, expr.span),
+                _ => {}
+            },
+            // Reading a storage value, but not referring to a function attached to it.
+            _ => {
+                if self.reads_storage(base)
+                    && !self.ty(expr).is_some_and(|ty| matches!(ty.kind, TyKind::FnPtr(_)))
+                {
+                    self.require(StateMutability::View, expr.span);
+                }
+            }
+        }
+    }
+
+    fn check_msg_value(&mut self, span: Span) {
+        let f = self.function;
+        if self.declared.is_none()
+            || f.kind.is_modifier()
+            || f.visibility < Visibility::Public
+            || f.state_mutability == StateMutability::Payable
+        {
+            self.require(StateMutability::View, span);
+            return;
+        }
+        // The function has to be `payable`, so it can't be restricted any further.
+        self.required = StateMutability::NonPayable;
+        self.gcx
+            .dcx()
+            .err("`msg.value` can only be used in payable public functions")
+            .span(span)
+            .help("make the function `payable` or use an internal function to avoid this error")
+            .emit();
+    }
+}
+
+impl<'gcx> Visit<'gcx> for MutabilityChecker<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match stmt.kind {
+            hir::StmtKind::Emit(expr) => self.require(StateMutability::NonPayable, expr.span),
+            hir::StmtKind::Assembly(assembly) => {
+                for &(mutability, span) in assembly.state_accesses {
+                    self.require(mutability, span);
+                }
+                // The rest of the Yul code isn't analyzed.
+                self.opaque = true;
+            }
+            _ => {}
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            hir::ExprKind::Assign(lhs, _, rhs) => {
+                self.visit_lvalue(lhs);
+                return self.visit_expr(rhs);
+            }
+            hir::ExprKind::Delete(operand) => {
+                self.visit_lvalue(operand);
+                return ControlFlow::Continue(());
+            }
+            hir::ExprKind::Unary(op, operand)
+                if matches!(
+                    op.kind,
+                    UnOpKind::PreInc | UnOpKind::PreDec | UnOpKind::PostInc | UnOpKind::PostDec
+                ) =>
+            {
+                self.visit_lvalue(operand);
+                return ControlFlow::Continue(());
+            }
+            hir::ExprKind::Ident(_) => {
+                if let Some(Res::Item(hir::ItemId::Variable(id))) = self.res(expr) {
+                    let var = self.gcx.hir.variable(id);
+                    // Immutables that are assigned a number literal are compile-time constants.
+                    let is_literal = var.is_immutable()
+                        && var.initializer.is_some_and(|init| {
+                            matches!(
+                                init.peel_parens().kind,
+                                hir::ExprKind::Lit(hir::Lit {
+                                    kind: LitKind::Number(_) | LitKind::Rational(_),
+                                    ..
+                                })
+                            )
+                        });
+                    if self.is_state_variable(expr) && !is_literal {
+                        self.require(StateMutability::View, expr.span);
+                    }
+                } else if let Some(Res::Builtin(Builtin::This)) = self.res(expr) {
+                    self.require(StateMutability::View, expr.span);
+                }
+            }
+            hir::ExprKind::Member(base, _) => self.check_member(expr, base),
+            hir::ExprKind::Index(base, _) if self.reads_storage(base) => {
+                self.require(StateMutability::View, expr.span);
+            }
+            hir::ExprKind::Call(callee, ..) => {
+                if let Some(TyKind::FnPtr(f)) = self.ty(callee).map(|ty| &ty.kind) {
+                    self.require(f.state_mutability, expr.span);
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
+/// Reports that `subject` requires the `required` state mutability, which is not allowed by the
+/// `declared` one.
+fn report<'a>(
+    gcx: Gcx<'a>,
+    declared: StateMutability,
+    required: StateMutability,
+    subject: &str,
+    span: Span,
+) -> DiagBuilder<'a, ErrorGuaranteed> {
+    let msg = if required == StateMutability::View {
+        format!(
+            "function declared as `{declared}`, but {subject} (potentially) reads from the \
+             environment or state and thus requires `view`"
+        )
+    } else {
+        format!(
+            "function declared as `{declared}`, but {subject} (potentially) modifies the state \
+             and thus requires non-payable (the default) or payable"
+        )
+    };
+    gcx.dcx().err(msg).span(span)
+}
+
+/// Orders state mutabilities from the most to the least restrictive.
+fn rank(mutability: StateMutability) -> u8 {
+    match mutability {
+        StateMutability::Pure => 0,
+        StateMutability::View => 1,
+        StateMutability::NonPayable | StateMutability::Payable => 2,
+    }
+}
+
+fn name_span(f: &hir::Function<'_>) -> Span {
+    f.name.map_or(f.span, |name| name.span)
+}
@@ -0,0 +1,293 @@
+//! Checks the overriding of inherited functions, modifiers and public state variables.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/contracts.html#function-overriding>
+
+use crate::{
+    hir::{self, ContractId, FunctionId, StateMutability, Visibility},
+    ty::{Gcx, Ty},
+};
+use solar_data_structures::{map::FxHashSet, smallvec::SmallVec};
+use solar_interface::Span;
+
+type Functions = SmallVec<[FunctionId; 4]>;
+type Contracts = SmallVec<[ContractId; 4]>;
+
+/// Checks the functions, modifiers and public state variables declared in the given contract
+/// against the ones they override in its bases.
+pub(super) fn check(gcx: Gcx<'_>, id: ContractId) {
+    let contract = gcx.hir.contract(id);
+    let bases = &contract.linearized_bases[1..];
+
+    for f in contract.all_functions() {
+        if gcx.hir.function(f).kind.is_constructor() {
+            continue;
+        }
+        check_function(gcx, f, &overridden_functions(gcx, bases, f));
+    }
+
+    for var in contract.variables() {
+        let v = gcx.hir.variable(var);
+        if v.override_ && v.getter.is_none() {
+            let msg = "`override` can only be used with public state variables";
+            gcx.dcx().err(msg).span(v.span).emit();
+        }
+    }
+
+    check_ambiguous_overrides(gcx, id);
+}
+
+/// Checks a function that overrides the given base functions.
+fn check_function(gcx: Gcx<'_>, id: FunctionId, overridden: &[FunctionId]) {
+    let f = gcx.hir.function(id);
+    let span = name_span(gcx, id);
+    let descr = description(gcx, id);
+
+    let Some(&first) = overridden.first() else {
+        if f.override_ {
+            let msg = format!("{descr} has `override` specified but does not override anything");
+            gcx.dcx().err(msg).span(span).emit();
+        }
+        return;
+    };
+
+    // Implementing a single interface function doesn't require `override`.
+    let is_single_interface_function = overridden.len() == 1
+        && gcx
+            .hir
+            .function(first)
+            .contract
+            .is_some_and(|c| gcx.hir.contract(c).kind.is_interface());
+    if !f.override_ && !is_single_interface_function {
+        let msg = format!("overriding {descr} is missing `override` specifier");
+        let mut err = gcx.dcx().err(msg).span(span);
+        for &base in overridden {
+            err = err.span_note(name_span(gcx, base), "overridden function is here");
+        }
+        err.emit();
+    }
+
+    // The override list must contain exactly the contracts that define the overridden functions.
+    let expected = overridden.iter().filter_map(|&base| gcx.hir.function(base).contract);
+    let expected = expected.collect::<Contracts>();
+    if f.override_ && (overridden.len() > 1 || !f.overrides.is_empty()) {
+        let missing =
+            expected.iter().copied().filter(|c| !f.overrides.contains(c)).collect::<Contracts>();
+        if !missing.is_empty() {
+            let names = missing
+                .iter()
+                .map(|&c| format!("`{}`", gcx.hir.contract(c).name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let s = if missing.len() == 1 { "" } else { "s" };
+            let msg = format!("{descr} needs to specify overridden contract{s} {names}");
+            gcx.dcx().err(msg).span(span).emit();
+        }
+        for &c in f.overrides {
+            if !expected.contains(&c) {
+                let msg = format!(
+                    "invalid contract specified in override list: `{}`",
+                    gcx.hir.contract(c).name
+                );
+                gcx.dcx().err(msg).span(span).emit();
+            }
+        }
+    }
+
+    for &base_id in overridden {
+        check_override(gcx, id, base_id);
+    }
+}
+
+/// Checks that the function `id` can override the base function `base_id`.
+fn check_override(gcx: Gcx<'_>, id: FunctionId, base_id: FunctionId) {
+    let f = gcx.hir.function(id);
+    let base = gcx.hir.function(base_id);
+    let span = name_span(gcx, id);
+    let descr = description(gcx, id);
+    let err = |msg: String| {
+        let note = format!("overridden {} is here", description(gcx, base_id));
+        gcx.dcx().err(msg).span(span).span_note(name_span(gcx, base_id), note).emit();
+    };
+
+    if base.is_getter() {
+        err("cannot override public state variable".into());
+        return;
+    }
+
+    if !base.virtual_ {
+        err(format!("trying to override non-virtual {}", base.kind));
+    }
+
+    if f.is_getter() {
+        if base.visibility != Visibility::External {
+            err("public state variables can only override functions with external visibility"
+                .into());
+        }
+    } else if f.visibility != base.visibility
+        && !(base.visibility == Visibility::External && f.visibility == Visibility::Public)
+    {
+        err(format!("overriding {descr} visibility differs from `{}`", base.visibility));
+    }
+
+    if !mutability_can_override(base.state_mutability, f.state_mutability) {
+        err(format!(
+            "overriding {descr} changes state mutability from `{}` to `{}`",
+            base.state_mutability, f.state_mutability,
+        ));
+    }
+
+    if f.kind.is_function() {
+        let (f_ty, base_ty) = comparable_types(gcx, id, base_id);
+        if f_ty.returns() != base_ty.returns() {
+            err(format!("overriding {descr} return types differ"));
+        }
+    }
+}
+
+/// Checks that functions that are defined in multiple bases are overridden in the contract.
+fn check_ambiguous_overrides(gcx: Gcx<'_>, id: ContractId) {
+    let contract = gcx.hir.contract(id);
+    let bases = &contract.linearized_bases[1..];
+    let mut checked = FxHashSet::default();
+    for &base in bases {
+        for f in gcx.hir.contract(base).all_functions() {
+            if !checked.insert(f) || !is_inherited(gcx, f) {
+                continue;
+            }
+            // Overridden in the contract itself.
+            if contract.all_functions().any(|own| signatures_match(gcx, own, f)) {
+                continue;
+            }
+            let mut overridden = matching_functions(gcx, bases, f);
+            checked.extend(overridden.iter().copied());
+            overridden.push(f);
+            let overridden = most_derived(gcx, overridden);
+            if overridden.len() <= 1 {
+                continue;
+            }
+
+            let descr = description(gcx, f);
+            let name = function_name(gcx, f);
+            let msg = format!("derived contract must override {descr} `{name}`");
+            let mut err = gcx.dcx().err(msg).span(contract.name.span).note(
+                "two or more base classes define a function with the same name and parameter types",
+            );
+            for &base_f in &overridden {
+                err = err.span_note(name_span(gcx, base_f), "defined here");
+            }
+            err.emit();
+        }
+    }
+}
+
+/// Returns the functions in `bases` that are overridden by `id`.
+fn overridden_functions(gcx: Gcx<'_>, bases: &[ContractId], id: FunctionId) -> Functions {
+    most_derived(gcx, matching_functions(gcx, bases, id))
+}
+
+/// Returns all the functions in `bases` with the same signature as `id`.
+fn matching_functions(gcx: Gcx<'_>, bases: &[ContractId], id: FunctionId) -> Functions {
+    let mut found = Functions::new();
+    for &base in bases {
+        for f in gcx.hir.contract(base).all_functions() {
+            if f != id && is_inherited(gcx, f) && signatures_match(gcx, id, f) {
+                found.push(f);
+            }
+        }
+    }
+    found
+}
+
+/// Removes the functions that are overridden by another one in the list, so that only the most
+/// derived definitions remain.
+fn most_derived(gcx: Gcx<'_>, mut functions: Functions) -> Functions {
+    let contracts =
+        functions.iter().map(|&f| gcx.hir.function(f).contract.unwrap()).collect::<Contracts>();
+    let mut i = 0;
+    functions.retain(|_| {
+        let c = contracts[i];
+        i += 1;
+        !contracts
+            .iter()
+            .any(|&other| other != c && gcx.hir.contract(other).linearized_bases[1..].contains(&c))
+    });
+    functions
+}
+
+/// Returns `true` if the function can be overridden in derived contracts.
+fn is_inherited(gcx: Gcx<'_>, id: FunctionId) -> bool {
+    let f = gcx.hir.function(id);
+    !f.kind.is_constructor() && f.visibility != Visibility::Private
+}
+
+/// Returns `true` if `a` and `b` have the same kind, name and parameter types.
+pub(super) fn signatures_match(gcx: Gcx<'_>, a: FunctionId, b: FunctionId) -> bool {
+    let (fa, fb) = (gcx.hir.function(a), gcx.hir.function(b));
+    if fa.kind != fb.kind {
+        return false;
+    }
+    match fa.kind {
+        hir::FunctionKind::Function => {
+            if fa.name.map(|n| n.name) != fb.name.map(|n| n.name) {
+                return false;
+            }
+            let (ta, tb) = comparable_types(gcx, a, b);
+            ta.parameters() == tb.parameters()
+        }
+        hir::FunctionKind::Modifier => fa.name.map(|n| n.name) == fb.name.map(|n| n.name),
+        hir::FunctionKind::Fallback | hir::FunctionKind::Receive => true,
+        hir::FunctionKind::Constructor => false,
+    }
+}
+
+/// Returns the types of the two functions in a form in which they can be compared.
+///
+/// Functions that are both part of the external interface are compared by their externally
+/// callable types, which ignore the `calldata` and `memory` data locations.
+fn comparable_types<'gcx>(gcx: Gcx<'gcx>, a: FunctionId, b: FunctionId) -> (Ty<'gcx>, Ty<'gcx>) {
+    let (ta, tb) = (gcx.type_of_item(a.into()), gcx.type_of_item(b.into()));
+    if gcx.hir.function(a).is_part_of_external_interface()
+        && gcx.hir.function(b).is_part_of_external_interface()
+    {
+        (ta.as_externally_callable_function(gcx), tb.as_externally_callable_function(gcx))
+    } else {
+        (ta, tb)
+    }
+}
+
+/// Returns `true` if a function with mutability `base` can be overridden by one with mutability
+/// `f`. The mutability can only be made stricter, except for `payable`.
+fn mutability_can_override(base: StateMutability, f: StateMutability) -> bool {
+    use StateMutability::*;
+
+    match base {
+        Payable => f == Payable,
+        NonPayable => f != Payable,
+        View => matches!(f, View | Pure),
+        Pure => f == Pure,
+    }
+}
+
+pub(super) fn description(gcx: Gcx<'_>, id: FunctionId) -> &'static str {
+    let f = gcx.hir.function(id);
+    if f.is_getter() {
+        "public state variable"
+    } else if f.kind.is_modifier() {
+        "modifier"
+    } else {
+        "function"
+    }
+}
+
+pub(super) fn name_span(gcx: Gcx<'_>, id: FunctionId) -> Span {
+    let f = gcx.hir.function(id);
+    match f.gettee {
+        Some(var) => gcx.hir.variable(var).name.map_or(f.span, |name| name.span),
+        None => f.name.map_or(f.span, |name| name.span),
+    }
+}
+
+pub(super) fn function_name(gcx: Gcx<'_>, id: FunctionId) -> String {
+    let f = gcx.hir.function(id);
+    f.name.map_or_else(|| f.kind.to_str().into(), |name| name.to_string())
+}
@@ -0,0 +1,52 @@
+//@ignore-host: windows
+//@compile-flags: --emit=abi --pretty-json
+
+// Events and errors that are emitted or reverted with in reachable code are part of the ABI, even
+// if they are declared outside of the contract. Public library functions are called with
+// `delegatecall`, so the events and errors used in them are not part of the ABI.
+
+event FreeEvent(uint256 a);
+event UnusedFreeEvent();
+error FreeError(uint256 a);
+error UnusedFreeError();
+
+interface I {
+    event InterfaceEvent(address indexed a);
+}
+
+library L {
+    event LibraryEvent(uint256 a);
+    event UnusedLibraryEvent();
+    error LibraryError();
+
+    function emitEvent() internal {
+        emit LibraryEvent(1);
+        freeFunction();
+    }
+
+    function check(bool c) public pure {
+        require(c, LibraryError());
+    }
+}
+
+function freeFunction() pure {
+    revert FreeError(2);
+}
+
+contract Base {
+    event BaseEvent();
+    error BaseError();
+}
+
+contract C is Base {
+    modifier m() {
+        emit I.InterfaceEvent(msg.sender);
+        _;
+    }
+
+    function f() public m {
+        emit FreeEvent(0);
+        L.emitEvent();
+        L.check(true);
+    }
+}
@@ -0,0 +1,63 @@
+//@compile-flags: -Zdump=cfg
+
+contract C {
+    uint256 x;
+
+    modifier onlyPositive() {
+        require(x > 0);
+        _;
+    }
+
+    modifier twice() {
+        _;
+        if (x > 1) {
+            _;
+        }
+        x = 0;
+    }
+
+    modifier noBody() {
+        return;
+        _; //~ WARN: unreachable code
+    }
+
+    function loops(uint256 n) public onlyPositive {
+        for (uint256 i = 0; i < n; i++) {
+            if (i == 3) continue;
+            if (i == 5) break;
+            x += i;
+        }
+        while (n > 0) {
+            n--;
+        }
+        do {
+            x--;
+            if (x == 1) continue;
+        } while (x > 0);
+    }
+
+    function branches(uint256 a) public returns (uint256) {
+        if (a == 0) {
+            return 1;
+        } else if (a == 1) {
+            revert();
+        }
+        x = a;
+        return 2;
+        x = 0; //~ WARN: unreachable code
+    }
+
+    function tryCatch() public returns (uint256 r) {
+        try this.value() returns (uint256 v) {
+            r = v;
+        } catch Error(string memory) {
+            assert(false);
+        } catch {
+            x = 0;
+        }
+    }
+
+    function value() external returns (uint256) {
+        return x++;
+    }
+}
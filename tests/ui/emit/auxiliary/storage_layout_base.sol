@@ -0,0 +1,20 @@
+// The previous version of `storage_layout_diff.sol`. `storage_layout_base.json` is its storage
+// layout, emitted with `--emit=storage-layout`.
+
+contract C {
+    struct S {
+        uint128 a;
+        uint128 b;
+    }
+
+    uint128 a;
+    uint128 b;
+    uint256 c;
+    S s;
+    uint256 e;
+    uint256[4] d;
+}
+
+contract Unchanged {
+    uint256 x;
+}
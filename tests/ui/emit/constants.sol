@@ -0,0 +1,27 @@
+//@ignore-host: windows
+//@compile-flags: --emit=constants --pretty-json
+
+uint constant FILE_LEVEL = 1e18;
+
+contract Roles {
+    bytes32 public constant ADMIN_ROLE = keccak256("ADMIN_ROLE");
+    bytes32 public constant MINTER_ROLE = keccak256(bytes("MINTER_ROLE"));
+    uint256 internal constant FEE_DENOMINATOR = FILE_LEVEL / 1e14;
+    uint16 private constant FEE = uint16(FEE_DENOMINATOR * 3 / 1000);
+    int8 constant MIN = type(int8).min;
+    string public constant VERSION = "1.2.0";
+    bytes constant MAGIC = hex"c0ffee";
+    bytes constant PRINTABLE = hex"616263";
+    bytes constant TEXT = "text";
+    bytes4 constant SELECTOR = bytes4(keccak256("transfer(address,uint256)"));
+    address constant OWNER = 0x5B38Da6a701c568545dCfcB03FcB875f56beddC4;
+    bool constant ENABLED = !false;
+
+    // Not evaluated at compile time.
+    bytes32 constant PACKED = keccak256(abi.encodePacked("a", uint256(1)));
+
+    uint256 public notConstant = 1;
+    uint256 public immutable IMMUTABLE = 2;
+}
+
+contract Empty {}
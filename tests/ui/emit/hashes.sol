@@ -0,0 +1,28 @@
+//@ignore-host: windows
+//@compile-flags: --emit=hashes,method-identifiers --pretty-json
+
+library L {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    function transfer(address to, uint256 value) internal {
+        emit Transfer(msg.sender, to, value);
+    }
+}
+
+error Unauthorized(address caller);
+
+contract C {
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+    event Log(string message) anonymous;
+    error InsufficientBalance(uint256 available, uint256 required);
+
+    function transfer(address to, uint256 value) public {
+        if (to == address(0)) revert Unauthorized(msg.sender);
+        L.transfer(to, value);
+    }
+
+    function approve(address spender, uint256 value) external returns (bool) {
+        emit Approval(msg.sender, spender, value);
+        return true;
+    }
+}
@@ -0,0 +1,43 @@
+//@ignore-host: windows
+//@compile-flags: --emit=storage-layout --pretty-json
+
+contract Base {
+    uint128 a;
+    uint64 b;
+    address owner;
+    bool flag;
+}
+
+contract C is Base {
+    struct S {
+        uint8 x;
+        uint256 y;
+        mapping(uint256 => S) children;
+    }
+
+    enum E {
+        A,
+        B
+    }
+
+    type U is uint16;
+
+    uint256 constant K = 1;
+    uint256 immutable IM = 2;
+
+    S s;
+    uint8[40] small;
+    bytes32[2] big;
+    uint256[] dyn;
+    mapping(string => mapping(address => bool)) m;
+    string name;
+    E e;
+    U u;
+    Base other;
+    function(uint256) internal returns (bool) fi;
+    function(uint256) external view fe;
+    address payable pay;
+
+    bool transient locked;
+    uint256 transient depth;
+}
@@ -0,0 +1,34 @@
+//@ignore-host: windows
+//@compile-flags: --emit=storage-layout-diff --pretty-json
+//@compile-flags: --storage-layout-base ../../tests/ui/emit/auxiliary/storage_layout_base.json
+
+// Compared against the layout of `auxiliary/storage_layout_base.sol`.
+contract C { //~ ERROR: storage variable `e` was removed
+    struct S {
+        uint128 b;
+        uint128 a;
+    }
+
+    uint128 b; //~ ERROR: storage variable `b` was moved
+    uint128 a; //~ ERROR: storage variable `a` was moved
+    int256 c; //~ ERROR: type of storage variable `c` changed from `uint256` to `int256`
+    S s; //~ ERROR: layout of member `a` of storage variable `s` changed
+    address g; //~ ERROR: storage variable `g` was inserted before existing variables
+    uint256[2] d; //~ ERROR: storage variable `d` was shrunk from `uint256[4]` to `uint256[2]`
+}
+
+contract Unchanged {
+    uint256 x;
+}
+
+contract Added { //~ WARN: contract `Added` is not in the storage layout base
+    uint256 y;
+}
+
+abstract contract AbstractAdded {
+    uint256 z;
+}
+
+library LibraryAdded {}
+
+interface InterfaceAdded {}
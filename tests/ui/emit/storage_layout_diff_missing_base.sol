@@ -0,0 +1,7 @@
+//@ignore-host: windows
+//@compile-flags: --storage-layout-base ../../tests/ui/emit/auxiliary/missing.json
+//@error-in-other-file: failed to read storage layout base
+
+contract C {
+    uint256 x;
+}
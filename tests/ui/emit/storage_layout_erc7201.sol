@@ -0,0 +1,30 @@
+//@ignore-host: windows
+//@compile-flags: --emit=storage-layout --pretty-json
+
+// https://eips.ethereum.org/EIPS/eip-7201
+contract Example {
+    /// @custom:storage-location erc7201:example.main
+    struct MainStorage {
+        uint256 x;
+        uint128 y;
+        uint128 z;
+    }
+
+    uint256 regular;
+}
+
+contract OwnableUpgradeable {
+    /**
+     * @dev Storage of the Ownable contract.
+     * @custom:storage-location erc7201:openzeppelin.storage.Ownable
+     */
+    struct OwnableStorage {
+        address _owner;
+    }
+}
+
+contract C is Example, OwnableUpgradeable {
+    struct NotNamespaced {
+        uint256 a;
+    }
+}
@@ -0,0 +1,18 @@
+//@ignore-host: windows
+//@compile-flags: --emit=storage-layout --pretty-json
+
+// Transient state variables are laid out independently of the persistent ones, starting at slot 0
+// and continuing along the inheritance hierarchy.
+contract Base {
+    uint256 a;
+    bool transient entered;
+    uint128 b;
+    uint128 transient counter;
+}
+
+contract C is Base {
+    address transient caller;
+    uint256 c;
+    uint256 transient total;
+    bytes32 d;
+}
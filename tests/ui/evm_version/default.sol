@@ -0,0 +1,11 @@
+contract C {
+    function f() public returns (uint256) {
+        assembly {
+            pop(blobbasefee())
+            pop(blobhash(0))
+            tstore(0, tload(0))
+            mcopy(0, 0, 0)
+        }
+        return block.blobbasefee + uint256(blobhash(0)) + block.prevrandao;
+    }
+}
@@ -0,0 +1,8 @@
+//@compile-flags: --evm-version homestead
+
+contract C {
+    function f() public view {
+        address(this).codehash; //~ ERROR: `codehash` is not supported by the selected EVM version
+        address(this).staticcall(""); //~ ERROR: `staticcall` is not supported by the selected EVM version
+    }
+}
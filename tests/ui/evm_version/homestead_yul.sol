@@ -0,0 +1,10 @@
+//@compile-flags: --evm-version homestead
+
+contract C {
+    function f() public view {
+        assembly {
+            pop(shl(1, 2)) //~ ERROR: `shl` is not supported by the selected EVM version
+            pop(returndatasize()) //~ ERROR: `returndatasize` is not supported by the selected EVM version
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//@compile-flags: --evm-version london
+
+contract C {
+    function f() public view returns (uint256) {
+        return block.basefee + block.chainid + block.prevrandao; //~ ERROR: `block.prevrandao` is not supported by the selected EVM version
+    }
+}
@@ -0,0 +1,17 @@
+//@compile-flags: --evm-version petersburg
+
+contract C {
+    function builtins() public view {
+        block.chainid; //~ ERROR: `block.chainid` is not supported by the selected EVM version
+        block.basefee; //~ ERROR: `block.basefee` is not supported by the selected EVM version
+        block.prevrandao; //~ ERROR: `block.prevrandao` is not supported by the selected EVM version
+        block.blobbasefee; //~ ERROR: `block.blobbasefee` is not supported by the selected EVM version
+        blobhash(0); //~ ERROR: `blobhash` is not supported by the selected EVM version
+        address(this).codehash;
+        address(this).staticcall("");
+
+        block.timestamp;
+        block.difficulty;
+        blockhash(0);
+    }
+}
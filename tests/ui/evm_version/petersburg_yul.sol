@@ -0,0 +1,18 @@
+//@compile-flags: --evm-version petersburg
+
+contract C {
+    function yul() public view {
+        assembly {
+            pop(chainid()) //~ ERROR: `chainid` is not supported by the selected EVM version
+            pop(selfbalance()) //~ ERROR: `selfbalance` is not supported by the selected EVM version
+            pop(basefee()) //~ ERROR: `basefee` is not supported by the selected EVM version
+            pop(prevrandao()) //~ ERROR: `prevrandao` is not supported by the selected EVM version
+            pop(blobbasefee()) //~ ERROR: `blobbasefee` is not supported by the selected EVM version
+            pop(blobhash(0)) //~ ERROR: `blobhash` is not supported by the selected EVM version
+            pop(tload(0)) //~ ERROR: `tload` is not supported by the selected EVM version
+            pop(shl(1, 2))
+            pop(extcodehash(0))
+            pop(difficulty())
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//@compile-flags: --evm-version shanghai
+
+contract C {
+    bool transient locked; //~ ERROR: `transient` is not supported by the selected EVM version
+    bool unlocked;
+}
@@ -0,0 +1,3 @@
+contract N {
+    function f() public {}
+}
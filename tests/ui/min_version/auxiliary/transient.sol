@@ -0,0 +1,3 @@
+contract T {
+    uint256 transient locked;
+}
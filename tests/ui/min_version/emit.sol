@@ -0,0 +1,14 @@
+//@ignore-host: windows
+//@compile-flags: --emit=min-version --pretty-json
+
+import "./auxiliary/transient.sol";
+import "./auxiliary/none.sol";
+
+contract C {
+    error E();
+
+    function f(bool x) public pure returns (string memory) {
+        if (!x) revert E();
+        return string.concat("a", "b");
+    }
+}
@@ -0,0 +1,22 @@
+pragma solidity ^0.8.0; //~ WARN: allows compiler versions that are too old
+pragma solidity >=0.8.4 <0.9.0; //~ WARN: allows compiler versions that are too old
+pragma solidity ^0.8.19;
+pragma solidity >=0.8.19 || ^0.8.0; //~ WARN: allows compiler versions that are too old
+
+type Fixed is uint256;
+
+using {add as +} for Fixed global;
+
+function add(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b));
+}
+
+error Unauthorized();
+
+contract C {
+    mapping(address => uint256) balances;
+
+    function f() public pure {
+        unchecked {}
+    }
+}
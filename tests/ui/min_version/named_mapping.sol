@@ -0,0 +1,5 @@
+pragma solidity ^0.8.13; //~ WARN: allows compiler versions that are too old
+
+contract C {
+    mapping(address owner => uint256) balances;
+}
@@ -0,0 +1,9 @@
+// `^` is the bitwise XOR operator, and `**` is right associative. It binds tighter than the other
+// binary operators, but not tighter than the unary operators.
+contract C {
+    uint[3 ^ 3] xor; //~ ERROR: array length must be greater than zero
+    uint[2 ** 3 ** 2 - 512] rightAssoc; //~ ERROR: array length must be greater than zero
+    uint[1 + 2 ** 2 * 3 - 13] precedence; //~ ERROR: array length must be greater than zero
+    // Parsed as `(-1) ** 2 - 1`, not `-(1 ** 2) - 1`.
+    uint[-1 ** 2 - 1] unary; //~ ERROR: array length must be greater than zero
+}
@@ -1,4 +1,4 @@
-function f() {
+function f() pure {
     uint i;
     do ++i; while (false);
     do i += 1; while (true && false);
@@ -0,0 +1,5 @@
+//@compile-flags: --solc-version 0.7.6
+
+pragma solidity ^0.7.0;
+pragma solidity >=0.6.2 <0.9.0;
+pragma solidity ^0.8.0; //~ ERROR: source file requires a different compiler version
@@ -0,0 +1,6 @@
+pragma solidity ^0.8.0;
+pragma solidity >=0.6.2 <0.9.0;
+pragma solidity 0.8.* || ^0.7;
+pragma solidity ^0.4.24; //~ ERROR: source file requires a different compiler version
+pragma solidity >=0.4.22 <0.8.0; //~ ERROR: source file requires a different compiler version
+pragma solidity ^0.9; //~ ERROR: source file requires a different compiler version
@@ -1,4 +1,4 @@
-function f() {
+function f() view {
     uint x = block.timestamp;
     x;
 }
@@ -7,7 +7,9 @@ contract U1 {
 }
 
 interface U2 {
-    function c() {} //~ERROR: no visibility specified
+    function c() {}
+    //~^ ERROR: no visibility specified
+    //~^^ ERROR: functions in interfaces cannot have an implementation
 }
 
 contract U3 {
@@ -0,0 +1,14 @@
+interface I {
+    uint256 x; //~ ERROR: variables cannot be declared in interfaces
+    uint256 constant C = 1; //~ ERROR: variables cannot be declared in interfaces
+
+    constructor() {} //~ ERROR: constructors cannot be defined in interfaces
+
+    function a() external;
+    function b() public; //~ ERROR: functions in interfaces must be declared external
+    function c() internal; //~ ERROR: functions in interfaces must be declared external
+    function d() external {} //~ ERROR: functions in interfaces cannot have an implementation
+
+    fallback() external;
+    receive() external payable;
+}
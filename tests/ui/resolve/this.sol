@@ -1,12 +1,12 @@
 contract C {
     uint public x = 1;
-    function f() public returns(uint y) {
+    function f() public view returns(uint y) {
         y = this.x();
     }
 }
 
 contract D is C {
-    function g() public returns(uint z) {
+    function g() public view returns(uint z) {
         z = this.f() + 1;
     }
 }
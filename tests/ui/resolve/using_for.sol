@@ -0,0 +1,46 @@
+import {Fixed} from "../typeck/auxiliary/fixed.sol";
+
+library L {
+    function f(uint256 a) internal pure returns (uint256) {
+        return a;
+    }
+}
+
+struct S {
+    uint256 x;
+}
+
+contract C {
+    struct Inner {
+        uint256 x;
+    }
+
+    function f(uint256 a) public pure returns (uint256) {
+        return a;
+    }
+
+    function m(uint256 a) internal pure returns (uint256) {
+        return a;
+    }
+
+    function m(bool a) internal pure returns (bool) {
+        return a;
+    }
+}
+
+function g(S memory s) pure returns (uint256) {
+    return s.x;
+}
+
+using L for uint256;
+using {g} for S global;
+
+using C for uint256; //~ ERROR: expected library, found contract
+using g for S; //~ ERROR: expected library, found function
+using {C.f} for uint256; //~ ERROR: only file-level functions and library functions can be attached to a type in a `using for` directive
+using {S} for uint256; //~ ERROR: only file-level functions and library functions
+using {C.m} for uint256; //~ ERROR: resolved to multiple declarations
+using {Unknown} for uint256; //~ ERROR: unresolved symbol
+using {L.f} for uint256 global; //~ ERROR: can only use `global` with user-defined types
+using {L.f} for Fixed global; //~ ERROR: can only use `global` with types defined in the same source unit at file level
+using {L.f} for C.Inner global; //~ ERROR: can only use `global` with types defined in the same source unit at file level
@@ -0,0 +1,49 @@
+contract C {
+    function pureWrites() public pure {
+        assembly {
+            sstore(0, 1) //~ ERROR: function declared as `pure`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        }
+    }
+
+    function pureReads() public pure returns (uint256 r) {
+        assembly {
+            r := add(sload(0), caller()) //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+            //~^ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+            if iszero(r) {
+                r := balance(address()) //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+                //~^ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+            }
+        }
+    }
+
+    function viewWrites() public view {
+        assembly {
+            function f() {
+                tstore(0, 1) //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+            }
+            log0(0, 0) //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+            pop(call(gas(), 0, 0, 0, 0, 0, 0)) //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+            pop(create(0, 0, 0)) //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        }
+    }
+
+    function viewReads() public view returns (uint256 r) {
+        assembly {
+            r := add(sload(0), tload(0))
+        }
+    }
+
+    function pureMemory() public pure returns (uint256 r) {
+        assembly {
+            mstore(0, 1)
+            r := keccak256(0, 32)
+        }
+    }
+
+    // No suggestion to restrict the state mutability, since the assembly is not fully analyzed.
+    function couldBePure() public returns (uint256 r) {
+        assembly {
+            r := mload(0x40)
+        }
+    }
+}
@@ -0,0 +1,12 @@
+type Fixed is uint256;
+
+using {add} for Fixed global;
+using {sub} for Fixed;
+
+function add(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b));
+}
+
+function sub(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) - Fixed.unwrap(b));
+}
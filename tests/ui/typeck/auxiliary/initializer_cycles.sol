@@ -0,0 +1,3 @@
+import {CrossFile} from "../initializer_cycles.sol";
+
+uint constant OTHER_FILE = CrossFile;
@@ -0,0 +1,20 @@
+//@compile-flags: --solc-version 0.6.8
+
+contract C {
+    function ext(uint256[] calldata a) external pure returns (uint256) {
+        return a.length;
+    }
+
+    function int_(uint256[] calldata a) internal pure returns (uint256) { //~ ERROR: `calldata` is only allowed for the parameters of internal functions since Solidity 0.6.9
+        return a.length;
+    }
+
+    function priv(bytes calldata b) private pure returns (bytes calldata) { //~ ERROR: `calldata` is only allowed for the parameters of private functions since Solidity 0.6.9
+        //~^ ERROR: `calldata` is only allowed for the parameters of private functions since Solidity 0.6.9
+        return b;
+    }
+
+    function mem(uint256[] memory a) internal pure returns (uint256) {
+        return a.length;
+    }
+}
@@ -0,0 +1,50 @@
+uint8 constant MAX8 = type(uint8).max;
+int8 constant MIN8 = type(int8).min;
+uint8 constant TOO_BIG = MAX8 + 1; //~ ERROR: this arithmetic operation will overflow
+
+contract C {
+    uint constant ZERO = 0;
+
+    function literals() public pure {
+        uint8 a = 255 + 1; //~ ERROR: mismatched types
+        uint8 b = 2 ** 8; //~ ERROR: mismatched types
+        int8 c = -129; //~ ERROR: mismatched types
+        uint8 d = 255;
+        uint e = 1 / 0; //~ ERROR: division by zero
+        a; b; c; d; e;
+    }
+
+    function constants(uint x, int8 y, bytes4 z) public pure {
+        uint8 a = MAX8 + 1; //~ ERROR: this arithmetic operation will overflow
+        int8 b = MIN8 - 1; //~ ERROR: this arithmetic operation will overflow
+        int8 c = MIN8 * -1; //~ ERROR: this arithmetic operation will overflow
+        uint8 d = MAX8 * 2 + 1; //~ ERROR: this arithmetic operation will overflow
+        uint8 e = MAX8 ** 2; //~ ERROR: this arithmetic operation will overflow
+        uint8 f = MAX8 << 1;
+        uint8 g = MAX8 - 1 + 1;
+
+        uint h = x / ZERO; //~ ERROR: division by zero
+        uint i = x % (ZERO * 2); //~ ERROR: modulo by zero
+        x /= 0; //~ ERROR: division by zero
+        uint j = x / 1;
+
+        uint k = x << 256; //~ ERROR: shift amount is too large
+        int8 l = y >> 8; //~ ERROR: shift amount is too large
+        bytes4 m = z << 32; //~ ERROR: shift amount is too large
+        uint n = x << 255;
+
+        a; b; c; d; e; f; g; h; i; j; k; l; m; n;
+    }
+
+    function uncheckedBlock(uint x) public pure {
+        unchecked {
+            uint8 a = MAX8 + 1;
+            int8 b = MIN8 - 1;
+            uint c = x / 0; //~ ERROR: division by zero
+            uint d = x << 256; //~ ERROR: shift amount is too large
+            a; b; c; d;
+        }
+        uint8 e = MAX8 + 1; //~ ERROR: this arithmetic operation will overflow
+        e;
+    }
+}
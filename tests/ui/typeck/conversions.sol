@@ -0,0 +1,104 @@
+enum E {
+    A,
+    B
+}
+
+contract D {}
+
+contract P {
+    receive() external payable {}
+}
+
+contract C {
+    function explicit(
+        uint256 u256,
+        uint160 u160,
+        uint8 u8,
+        int8 i8,
+        int256 i256,
+        bytes4 b4,
+        bytes20 b20,
+        bytes32 b32,
+        address a,
+        address payable ap,
+        E e,
+        D d,
+        P p,
+        bytes memory bm,
+        string memory sm
+    ) public pure {
+        uint8(u256);
+        int8(u8);
+        int16(i8);
+        uint16(i8); //~ ERROR: explicit type conversion not allowed from `int8` to `uint16`
+        uint256(i8); //~ ERROR: explicit type conversion not allowed from `int8` to `uint256`
+
+        address(u160);
+        uint160(a);
+        address(u256); //~ ERROR: explicit type conversion not allowed from `uint256` to `address`
+        uint256(a); //~ ERROR: explicit type conversion not allowed from `address` to `uint256`
+        address(b20);
+        bytes20(a);
+        bytes20(ap); //~ ERROR: explicit type conversion not allowed from `address payable` to `bytes20`
+        address(b32); //~ ERROR: explicit type conversion not allowed from `bytes32` to `address`
+
+        bytes4(b32);
+        bytes32(b4);
+        bytes32(u256);
+        uint256(b32);
+        int256(b32);
+        bytes4(uint256(1)); //~ ERROR: explicit type conversion not allowed from `uint256` to `bytes4`
+        bytes32(i256); //~ ERROR: explicit type conversion not allowed from `int256` to `bytes32`
+
+        E(u8);
+        uint8(e);
+        E(b4); //~ ERROR: explicit type conversion not allowed from `bytes4` to `enum E`
+
+        address(d);
+        D(a);
+        D(ap);
+        P(ap);
+        P(a); //~ ERROR: explicit type conversion not allowed from `address` to `contract P`
+        D(p); //~ ERROR: explicit type conversion not allowed from `contract P` to `contract D`
+        uint160(d); //~ ERROR: explicit type conversion not allowed from `contract D` to `uint160`
+
+        string(bm);
+        bytes(sm);
+        bytes4(bm);
+        bytes4(sm); //~ ERROR: explicit type conversion not allowed from `string memory` to `bytes4`
+        bool(u8); //~ ERROR: explicit type conversion not allowed from `uint8` to `bool`
+    }
+
+    function literals() public pure {
+        uint8(255);
+        uint8(256); //~ ERROR: explicit type conversion not allowed from `integer literal` to `uint8`
+        uint256(-1); //~ ERROR: explicit type conversion not allowed from `integer literal` to `uint256`
+        int8(-128);
+        uint8("abc"); //~ ERROR: explicit type conversion not allowed from `string literal` to `uint8`
+        uint256(0.5); //~ ERROR: explicit type conversion not allowed from `integer literal` to `uint256`
+
+        bytes4(0);
+        bytes4(0x12345678);
+        bytes4(1); //~ ERROR: explicit type conversion not allowed from `integer literal` to `bytes4`
+        bytes4(0x1234); //~ ERROR: explicit type conversion not allowed from `integer literal` to `bytes4`
+        bytes4("abcd");
+        bytes2("abc"); //~ ERROR: explicit type conversion not allowed from `string literal` to `bytes2`
+
+        address(0);
+        address(1);
+        address(0x10000000000000000000000000000000000000000); //~ ERROR: explicit type conversion not allowed from `integer literal` to `address`
+
+        E(1);
+        E(2); //~ ERROR: explicit type conversion not allowed from `integer literal` to `enum E`
+    }
+
+    // Literals with a fractional part can't be converted to integers implicitly either.
+    function rationals(uint256 x) public pure {
+        uint256 a = 0.5; //~ ERROR: mismatched types
+        uint256 b = 1 / 2; //~ ERROR: mismatched types
+        uint256 c = 0.5 * 2;
+        x = x + 0.5; //~ ERROR: built-in binary operator `+` cannot be applied to types `uint256` and `integer literal`
+        x += 2.5; //~ ERROR: built-in binary operator `+` cannot be applied to types `uint256` and `integer literal`
+        a; b; c;
+    }
+}
@@ -0,0 +1,144 @@
+contract C {
+    struct S {
+        uint256 x;
+        mapping(uint256 => uint256) m;
+    }
+
+    struct T {
+        uint256 x;
+    }
+
+    T t;
+    T[] ts;
+
+    function mappingParam(mapping(uint256 => uint256) memory m) internal {} //~ ERROR: only valid in storage
+    function structParam(S calldata s) internal {} //~ ERROR: only valid in storage
+    function structReturn() internal returns (S memory) {} //~ ERROR: only valid in storage
+    function storageParam(mapping(uint256 => uint256) storage m, S storage s) internal {}
+
+    function mappingLocal() internal {
+        S memory s; //~ ERROR: only valid in storage
+        s;
+    }
+
+    function ptr(T storage p) internal {}
+
+    function assignments(T memory m, T calldata c) external {
+        T storage a = m; //~ ERROR: mismatched data locations
+        T storage b = t;
+        b = m; //~ ERROR: mismatched data locations
+        b = t;
+        (b, a) = (t, m); //~ ERROR: mismatched data locations
+        T memory copy = c;
+        copy = t;
+
+        // Copies.
+        t = m;
+        t = c;
+        ts[0] = m;
+        ts.push(m);
+        m.x = 1;
+    }
+
+    function arguments(T memory m) internal {
+        ptr(m); //~ ERROR: mismatched data locations
+        ptr({p: m}); //~ ERROR: mismatched data locations
+        ptr(t);
+        ptr(ts[0]);
+    }
+
+    function calldataWrites(T calldata c, uint256[] calldata a) external {
+        c.x = 1; //~ ERROR: calldata is read-only
+        a[0] = 1; //~ ERROR: calldata is read-only
+        delete c.x; //~ ERROR: calldata is read-only
+    }
+
+    function returnValue(T memory m) internal returns (T storage) {
+        return m; //~ ERROR: mismatched data locations
+    }
+
+    function returnValues(T memory m) internal returns (T storage, T memory) {
+        return (m, m); //~ ERROR: mismatched data locations
+    }
+
+    function returnStorage() internal returns (T storage, T memory) {
+        return (t, t);
+    }
+
+    function unassignedReturn() internal returns (T storage r) {} //~ ERROR: without prior assignment
+    function unassignedReturnUnnamed() internal returns (T storage) {} //~ ERROR: without prior assignment
+
+    function assignedReturn() internal returns (T storage r) {
+        r = t;
+    }
+
+    function unassignedLocal() internal returns (uint256) {
+        T storage p; //~ ERROR: without prior assignment
+        return p.x;
+    }
+
+    function assignedLocal() internal returns (uint256) {
+        T storage p;
+        p = t;
+        return p.x;
+    }
+
+    function unusedLocal() internal {
+        T storage p;
+    }
+
+    function assignedInOneBranch(bool b) internal returns (uint256) {
+        T storage p; //~ ERROR: without prior assignment
+        if (b) {
+            p = t;
+        }
+        return p.x;
+    }
+
+    function assignedInBothBranches(bool b) internal returns (uint256) {
+        T storage p;
+        if (b) {
+            p = t;
+        } else {
+            p = t;
+        }
+        return p.x;
+    }
+
+    function assignedAfterAccess() internal returns (uint256 x) {
+        T storage p; //~ ERROR: without prior assignment
+        x = p.x;
+        p = t;
+    }
+
+    function assignedInLoop(uint256 n) internal returns (uint256) {
+        T storage p; //~ ERROR: without prior assignment
+        for (uint256 i = 0; i < n; i++) {
+            p = t;
+        }
+        return p.x;
+    }
+
+    function returnAssignedOnOnePath(bool b) internal returns (T storage r) { //~ ERROR: without prior assignment
+        if (b) {
+            return t;
+        }
+    }
+
+    function returnAssignedOnAllPaths(bool b) internal returns (T storage r) {
+        if (b) {
+            return t;
+        }
+        r = t;
+    }
+
+    function unassignedAfterRevert(bool b) internal returns (uint256) {
+        T storage p;
+        if (b) {
+            p = t;
+        } else {
+            revert();
+        }
+        return p.x;
+    }
+}
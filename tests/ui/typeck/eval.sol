@@ -1,6 +1,6 @@
 uint constant x = (69 + (((420))));
 
-uint constant rec1 = rec1;
+uint constant rec1 = rec1; //~ ERROR: cycle detected
 uint constant rec2 = rec1;
 
 uint constant bigLiteral = 115792089237316195423570985008687907853269984665640564039457584007913129639935;
@@ -11,6 +11,10 @@ contract C {
     uint public constant zeroPublic = x / x - 1;
     uint[zero] public zeroArray; //~ ERROR: array length must be greater than zero
     uint[zeroPublic + 1] public oneArray;
+    uint[10 - 4 - 6] public leftAssoc; //~ ERROR: array length must be greater than zero
+    uint[2 ** 3 ** 0 - 2] public rightAssoc; //~ ERROR: array length must be greater than zero
+    uint[1 + 2 ** 2 - 5] public powPrecedence; //~ ERROR: array length must be greater than zero
+    uint[3 ^ 3] public xor; //~ ERROR: array length must be greater than zero
 
     uint[bigLiteral] public big;
     uint[bigLiteral + 1] public tooBig1; //~ ERROR: evaluation of constant value failed
@@ -23,8 +27,8 @@ contract C {
     function b(uint[x] memory) public {}
     function c(uint[x * 2] memory) public {}
     function d(uint[0 - 1] memory) public {} //~ ERROR: evaluation of constant value failed
-    function e(uint[rec1] memory) public {} //~ ERROR: evaluation of constant value failed
-    function f(uint[rec2] memory) public {} //~ ERROR: evaluation of constant value failed
+    function e(uint[rec1] memory) public {}
+    function f(uint[rec2] memory) public {}
 
     function g(uint[0] memory) public {} //~ ERROR: array length must be greater than zero
     function h(uint[zero] memory) public {} //~ ERROR: array length must be greater than zero
@@ -0,0 +1,29 @@
+uint constant HALF = 1 / 2 * 4;
+uint constant SCALED = 2.5 * 4;
+uint8 constant MAX = type(uint8).max;
+int constant MIN = type(int8).min;
+uint8 constant TRUNC = uint8(uint16(0x1234));
+int8 constant SIGNED = int8(uint8(0xff));
+bytes4 constant SELECTOR = bytes4(keccak256("transfer(address,uint256)"));
+bytes2 constant LEFT = bytes2(bytes4(0x12345678));
+bytes4 constant RIGHT = bytes4(bytes2(0x1234));
+bytes4 constant FROM_STR = "abcd";
+bytes4 constant FROM_HEX = hex"deadbeef";
+uint32 constant FROM_BYTES = uint32(bytes4(0x01020304));
+
+contract C {
+    uint[HALF] public half;
+    uint[SCALED - 9] public scaled;
+    uint[MAX / 2] public halfMax;
+    uint[uint(int(MIN) * -1)] public minAbs;
+    uint[TRUNC] public trunc;
+    uint[uint8(SIGNED)] public signed;
+    uint[uint32(SELECTOR) >> 28] public selector;
+    uint[uint16(LEFT)] public left;
+    uint[uint32(RIGHT) >> 16] public right;
+    uint[FROM_BYTES >> 24] public fromBytes;
+    uint[true ? 1 : 2] public ternary;
+    uint[1 < 2 ? 3 : 4] public comparison;
+    uint[uint8(bytes1(FROM_STR))] public fromStr;
+    uint[uint8(bytes1(FROM_HEX)) - 0xde + 1] public fromHex;
+}
@@ -0,0 +1,12 @@
+uint8 constant MAX = type(uint8).max;
+string constant NAME = "name";
+uint8 constant NOT_A_BYTE = 256;
+
+contract C {
+    uint[1 / 2] public fraction; //~ ERROR: evaluation of constant value failed
+    uint[-1] public negative; //~ ERROR: evaluation of constant value failed
+    uint[NAME] public str; //~ ERROR: evaluation of constant value failed
+    uint[MAX + 1] public overflow; //~ ERROR: evaluation of constant value failed
+    uint[NOT_A_BYTE] public tooBig; //~ ERROR: evaluation of constant value failed
+    uint[uint8(1.5)] public convertFraction; //~ ERROR: evaluation of constant value failed
+}
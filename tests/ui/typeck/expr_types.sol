@@ -0,0 +1,84 @@
+contract C {
+    struct S {
+        uint256 a;
+    }
+
+    uint256 constant C1 = 1;
+    uint8 constant TOO_BIG = 256; //~ ERROR: mismatched types
+    uint256[] arr;
+    mapping(uint256 => bool) m;
+
+    event Ev(uint256 a);
+    error Er(uint256 a);
+
+    function f(uint256 x, int256 y, bool b, bytes32 h, string memory s) public {
+        uint8 a = x; //~ ERROR: mismatched types
+        uint256 ok = uint8(1);
+        int256 c = x; //~ ERROR: mismatched types
+        bool d = 1; //~ ERROR: mismatched types
+        bytes4 e = h; //~ ERROR: mismatched types
+        address f_ = "a"; //~ ERROR: mismatched types
+
+        x = x + y; //~ ERROR: built-in binary operator `+` cannot be applied to types `uint256` and `int256`
+        b = x && b; //~ ERROR: built-in binary operator `&&` cannot be applied to types `uint256` and `bool`
+        x = h + 1; //~ ERROR: built-in binary operator `+` cannot be applied to types `bytes32` and `integer literal`
+        b = s == s; //~ ERROR: built-in binary operator `==` cannot be applied to types `string memory` and `string memory`
+        x = x << y; //~ ERROR: built-in binary operator `<<` cannot be applied to types `uint256` and `int256`
+        x = -x; //~ ERROR: unary negation is only allowed for signed integers
+        b = !x; //~ ERROR: built-in unary operator `!` cannot be applied to type `uint256`
+        b++; //~ ERROR: built-in unary operator `++` cannot be applied to type `bool`
+
+        if (x) {} //~ ERROR: mismatched types
+        while (y) {} //~ ERROR: mismatched types
+        x = b ? 1 : s; //~ ERROR: true expression's type `integer literal` does not match false expression's type `string memory`
+
+        C1 = 2; //~ ERROR: cannot assign to a constant variable
+        1 = x; //~ ERROR: expression has to be an lvalue
+        arr.length = 3; //~ ERROR: member `length` is read-only and cannot be used to resize arrays
+        this.f = f; //~ ERROR: expression has to be an lvalue
+        (x, ) = (1, 2);
+        (x, ) = (1, ); //~ ERROR: tuple component cannot be empty
+        super; //~ ERROR: `super` can only be used to access members of base contracts
+        (x, y) = (1, 2, 3); //~ ERROR: different number of components on the left hand side (2) than on the right hand side (3)
+        (x, b) = (b, x);
+        //~^ ERROR: mismatched types
+        //~^^ ERROR: mismatched types
+
+        m[x] = 1; //~ ERROR: mismatched types
+        m[b] = true; //~ ERROR: mismatched types
+        arr[b] = 1; //~ ERROR: mismatched types
+        s[0]; //~ ERROR: index access for `string` is not possible
+        x[0]; //~ ERROR: indexed expression has to be a type, mapping or array (is `uint256`)
+
+        g(b); //~ ERROR: mismatched types
+        g(1, 2); //~ ERROR: wrong argument count for function call: 2 arguments given but expected 1
+        g({y: 1}); //~ ERROR: named argument `y` does not match function declaration
+        S memory st = S(b); //~ ERROR: mismatched types
+        st = S(1, 2); //~ ERROR: wrong argument count for struct constructor: 2 arguments given but expected 1
+        x = uint256(1, 2); //~ ERROR: exactly one unnamed argument expected for explicit type conversion
+        x(); //~ ERROR: expression of type `uint256` is not callable
+        this.g{value: b}(1); //~ ERROR: mismatched types
+        this.g{foo: 1}(1); //~ ERROR: unknown call option `foo`
+
+        emit Ev(b); //~ ERROR: mismatched types
+        revert Er(b); //~ ERROR: mismatched types
+    }
+
+    function g(uint256 x) public payable returns (uint256) {
+        return; 
+    }
+
+    function h() public {
+        return 1; //~ ERROR: return arguments not allowed
+    }
+
+    function i() public returns (uint256, bool) {
+        return (true, 1);
+        //~^ ERROR: mismatched types
+        //~^^ ERROR: mismatched types
+    }
+
+    function j() public returns (uint256, bool) {
+        return 1; //~ ERROR: different number of arguments in return statement than in returns declaration
+    }
+}
@@ -0,0 +1,113 @@
+contract C {
+    uint256 x;
+    error E();
+
+    modifier m() {
+        revert E();
+        _; //~ WARN: unreachable code
+    }
+
+    modifier n() {
+        _;
+        return;
+        x = 1; //~ WARN: unreachable code
+    }
+
+    function afterReturn() public {
+        x = 1;
+        return;
+        x = 2; //~ WARN: unreachable code
+        x = 3;
+    }
+
+    function afterRevertCall() public {
+        revert();
+        if (x == 0) { //~ WARN: unreachable code
+            x = 1;
+        }
+    }
+
+    function afterAssert() public {
+        assert(false);
+        x = 1; //~ WARN: unreachable code
+    }
+
+    function afterRequire() public view {
+        require(x > 0);
+        require(false, "no");
+        x; //~ WARN: unreachable code
+    }
+
+    function inLoops() public {
+        for (uint256 i = 0; i < 10; i++) { //~ WARN: unreachable code
+            break;
+            x = i; //~ WARN: unreachable code
+        }
+        while (x > 0) {
+            x--;
+            continue;
+            x++; //~ WARN: unreachable code
+        }
+        for (uint256 i = 0; i < 10; i++) { //~ WARN: unreachable code
+            return;
+        }
+    }
+
+    function afterInfiniteLoop() public {
+        while (true) {
+            x++;
+        }
+        x = 0; //~ WARN: unreachable code
+    }
+
+    function infiniteLoopWithBreak() public {
+        while (true) {
+            if (x++ > 10) break;
+        }
+        x = 0;
+    }
+
+    function bothBranchesReturn(bool b) public pure returns (uint256) {
+        if (b) {
+            return 1;
+        } else {
+            return 2;
+        }
+    }
+
+    function unnamedReturn(bool b) public pure returns (uint256) { //~ WARN: unnamed return variable can remain unassigned
+        if (b) {
+            return 1;
+        }
+    }
+
+    function unnamedReturnRevert(bool b) public pure returns (uint256) {
+        if (b) {
+            return 1;
+        }
+        revert();
+    }
+
+    function namedReturn() public pure returns (uint256 r, uint256 s) { //~ WARN: return variable `s` can remain unassigned
+        r = 1;
+    }
+
+    function namedReturnSomePaths(bool b) public pure returns (uint256 r) {
+        if (b) {
+            r = 1;
+        }
+    }
+
+    function namedReturnTuple() public pure returns (uint256 r, uint256 s) {
+        (r, s) = (1, 2);
+    }
+
+    function namedReturnExplicit() public pure returns (uint256 r) {
+        return 1;
+    }
+
+    function emptyBody() public pure returns (uint256) {} //~ WARN: unnamed return variable can remain unassigned
+
+    // Named return variables of stubs are meant to be returned with their default values.
+    function emptyBodyNamed() public pure returns (uint256 r) {}
+}
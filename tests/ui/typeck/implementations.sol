@@ -0,0 +1,50 @@
+interface IERC20 {
+    function totalSupply() external view returns (uint256);
+    function balanceOf(address account) external view returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+}
+
+contract Token is IERC20 { //~ ERROR: contract `Token` is missing an implementation of function `allowance`
+    uint256 public totalSupply;
+
+    function balanceOf(address) external pure returns (uint256) {
+        return 0;
+    }
+}
+
+abstract contract AbstractToken is IERC20 {
+    function balanceOf(address) public view virtual returns (uint256);
+}
+
+contract ConcreteToken is AbstractToken { //~ ERROR: contract `ConcreteToken` is missing an implementation of function `totalSupply`
+    //~^ ERROR: contract `ConcreteToken` is missing an implementation of function `balanceOf`
+    //~^^ ERROR: contract `ConcreteToken` is missing an implementation of function `allowance`
+}
+
+contract FullToken is AbstractToken {
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    function totalSupply() external pure returns (uint256) {
+        return 0;
+    }
+
+    function balanceOf(address) public pure override returns (uint256) {
+        return 0;
+    }
+}
+
+contract Own { //~ ERROR: contract `Own` is missing an implementation of function `f`
+    //~^ ERROR: contract `Own` is missing an implementation of modifier `m`
+    function f() public virtual;
+    modifier m() virtual;
+}
+
+abstract contract Base {
+    function f() public virtual;
+    function g() public virtual {}
+}
+
+contract Redeclared is Base { //~ ERROR: contract `Redeclared` is missing an implementation of function `g`
+    function f() public override {}
+    function g() public virtual override;
+}
@@ -0,0 +1,25 @@
+import "./auxiliary/initializer_cycles.sol";
+
+uint constant A = B; //~ ERROR: cycle detected
+uint constant B = A;
+
+uint constant C = C + 1; //~ ERROR: cycle detected
+
+uint constant D = uint(E) * 2; //~ ERROR: cycle detected
+uint128 constant E = uint128(F);
+uint constant F = D > 0 ? D : 1;
+
+uint constant DependsOnCycle = A;
+uint constant CrossFile = OTHER_FILE + 1; //~ ERROR: cycle detected
+
+contract X {
+    uint immutable I = J + 1; //~ ERROR: cycle detected
+    uint immutable J = I;
+
+    uint constant NotCyclic = A0 + A0;
+    uint constant A0 = 1;
+
+    uint[DependsOnCycle] arr;
+    uint[E] arr2;
+    uint[NotCyclic] arr3;
+}
@@ -1,7 +1,7 @@
 // https://github.com/paradigmxyz/solar/issues/128
 
 // SPDX-License-Identifier: UNLICENSED
-pragma solidity ^0.8.13;
+pragma solidity ^0.8.13; //~ WARN: allows compiler versions that are too old
 
 struct StructTest {
     uint256 a;
@@ -9,7 +9,7 @@ struct StructTest {
 }
 
 library TestLibrary {
-    function testFunction(StructTest memory testParameter) external view returns (uint256) {
+    function testFunction(StructTest memory testParameter) external view returns (uint256) { //~ ERROR: only valid in storage
         return testParameter.a;
     }
 }
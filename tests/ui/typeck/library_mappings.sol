@@ -1,5 +1,5 @@
 contract L {
-    function f(mapping(uint=>uint) storage x, mapping(uint=>uint) storage y) internal {
+    function f(mapping(uint=>uint) storage x, mapping(uint=>uint) storage y) internal pure {
         // TODO: disallow assignment
         x = y;
     }
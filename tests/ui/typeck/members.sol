@@ -0,0 +1,78 @@
+library L {
+    struct S {
+        uint256 x;
+    }
+
+    function f() internal {}
+}
+
+interface I {
+    function g() external;
+}
+
+enum E {
+    A
+}
+
+contract C {
+    struct S {
+        uint256 a;
+        bytes32 b;
+    }
+
+    S s;
+    uint256[] arr;
+    bytes bs;
+    I i;
+
+    function f(uint256 x, address a, address payable p) public {
+        msg.sender;
+        msg.sendr; //~ ERROR: no member named `sendr` on type `msg`
+        block.timestamp;
+        block.time; //~ ERROR: no member named `time` on type `block`
+        abi.encod(1); //~ ERROR: no member named `encod` on type `abi`
+
+        s.a = 1;
+        s.c = 1; //~ ERROR: no member named `c` on type `struct C.S storage`
+        arr.push(1);
+        arr.length;
+        arr.size; //~ ERROR: no member named `size` on type `uint256[] storage`
+        bs.pop();
+
+        a.balance;
+        a.transfer(1); //~ ERROR: no member named `transfer` on type `address`
+        p.transfer(1);
+        x.max; //~ ERROR: no member named `max` on type `uint256`
+        type(uint256).max;
+        type(uint256).maximum; //~ ERROR: no member named `maximum` on type `type(uint256)`
+
+        E.A;
+        E.B; //~ ERROR: no member named `B` on type `type(enum E)`
+        L.f();
+        L.g(); //~ ERROR: no member named `g` on type `type(library L)`
+        L.S memory ls;
+        ls.x;
+
+        i.g();
+        i.h(); //~ ERROR: no member named `h` on type `contract I`
+        i.g.selector;
+        i.g.address;
+        I.g.selector;
+        this.f.selector;
+        this.f.foo; //~ ERROR: no member named `foo` on type `function (uint256,address,address payable) external`
+        super.f(); //~ ERROR: no member named `f` on `super`
+    }
+}
+
+// Members are also resolved in modifier and base constructor arguments.
+contract Base {
+    constructor(uint256 x) {}
+}
+
+contract Args is Base(block.timestampp) { //~ ERROR: no member named `timestampp` on type `block`
+    modifier m(address a) {
+        _;
+    }
+
+    function f() public m(msg.sendr) {} //~ ERROR: no member named `sendr` on type `msg`
+}
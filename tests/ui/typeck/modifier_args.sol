@@ -0,0 +1,41 @@
+contract B {
+    constructor(uint256 x) {}
+}
+
+contract Empty {}
+
+contract C is B {
+    modifier m(bool b) {
+        _;
+    }
+
+    modifier n() {
+        _;
+    }
+
+    constructor() B("not a uint") m(true) {} //~ ERROR: mismatched types
+
+    function g(uint256 x) public m(x) {} //~ ERROR: mismatched types
+
+    function h() public m {} //~ ERROR: wrong argument count for modifier invocation: 0 arguments given but expected 1
+
+    function i() public m(true, false) n(1) {}
+    //~^ ERROR: wrong argument count for modifier invocation: 2 arguments given but expected 1
+    //~^^ ERROR: wrong argument count for modifier invocation: 1 arguments given but expected 0
+
+    function j(bool b) public m(b) n {}
+
+    function k(bool b) public m({b: b}) {}
+
+    function l(bool b) public m({c: b}) {} //~ ERROR: named argument `c` does not match function declaration
+}
+
+contract D is B(true) {} //~ ERROR: mismatched types
+
+contract E is B(1, 2) {} //~ ERROR: wrong argument count for constructor call: 2 arguments given but expected 1
+
+contract F is Empty(1) {} //~ ERROR: wrong argument count for constructor call: 1 arguments given but expected 0
+
+contract G is B(X) {
+    uint256 constant X = 1;
+}
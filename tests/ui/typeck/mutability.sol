@@ -0,0 +1,123 @@
+interface IToken {
+    function balanceOf(address) external view returns (uint256);
+    function transfer(address, uint256) external returns (bool);
+}
+
+contract C {
+    struct S {
+        uint256 a;
+    }
+
+    event E();
+
+    uint256 constant K = 1;
+    uint256 immutable LIT = 2;
+    bool immutable FLAG = true;
+    address immutable OWNER = 0x0000000000000000000000000000000000000001;
+    bytes32 immutable NAME = "name";
+    uint256 immutable computed;
+    uint256 x;
+    uint256[] arr;
+    mapping(address => S) map;
+    IToken token;
+
+    constructor() {
+        computed = block.number;
+        uint256 v = msg.value; //~ ERROR: `msg.value` can only be used in payable public functions
+        v;
+    }
+
+    modifier reads() {
+        require(x > 0);
+        _;
+    }
+
+    modifier writes() {
+        x = 1;
+        _;
+    }
+
+    function pureReads() public pure returns (uint256) {
+        uint256 a = x; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += arr.length; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += map[msg.sender].a; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        //~^ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += block.timestamp; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += address(this).balance; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        //~^ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += computed; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        a += token.balanceOf(address(0)); //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        //~^ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        return a + K + LIT + uint256(keccak256(""));
+    }
+
+    function pureImmutables() public pure returns (bool, address, bytes32) {
+        return (
+            FLAG, //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+            OWNER, //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+            NAME //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+        );
+    }
+
+    function viewWrites() public view {
+        x = 1; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        x++; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        map[msg.sender].a = 2; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        S storage s = map[msg.sender];
+        s.a = 3; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        delete arr; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        arr.push(1); //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        emit E(); //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+        token.transfer(address(0), 1); //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state and thus requires non-payable (the default) or payable
+    }
+
+    function viewMemoryWrites() public view returns (uint256) {
+        S memory s = S(x);
+        s.a = 1;
+        uint256[] memory m = new uint256[](1);
+        m[0] = s.a;
+        return m[0];
+    }
+
+    function pureModifier() public pure reads {} //~ ERROR: function declared as `pure`, but modifier `reads` (potentially) reads from the environment or state and thus requires `view`
+
+    function viewModifier() public view writes {} //~ ERROR: function declared as `view`, but modifier `writes` (potentially) modifies the state and thus requires non-payable (the default) or payable
+
+    function value() external returns (uint256) {
+        return msg.value; //~ ERROR: `msg.value` can only be used in payable public functions
+    }
+
+    function payableValue() external payable returns (uint256) {
+        return msg.value;
+    }
+
+    function internalValue() internal view returns (uint256) {
+        return msg.value;
+    }
+
+    function couldBePure(uint256 a) public returns (uint256) { //~ WARN: function state mutability can be restricted to `pure`
+        return a + K;
+    }
+
+    function couldBeView() public returns (uint256) { //~ WARN: function state mutability can be restricted to `view`
+        return x;
+    }
+
+    function viewCouldBePure() public view returns (uint256) { //~ WARN: function state mutability can be restricted to `pure`
+        return LIT;
+    }
+
+    function withModifier() public reads returns (uint256) { //~ WARN: function state mutability can be restricted to `view`
+        return 1;
+    }
+
+    function virtualFunction() public virtual returns (uint256) {
+        return 1;
+    }
+
+    function writesState() public {
+        x = 2;
+    }
+
+    function empty() public {}
+}
@@ -0,0 +1,51 @@
+type Fixed is uint256;
+
+struct S {
+    uint256 x;
+}
+
+library L {
+    function add(Fixed a, Fixed b) internal pure returns (Fixed) {
+        return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b));
+    }
+}
+
+function add(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b));
+}
+
+function addView(Fixed a, Fixed b) view returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b) + block.number);
+}
+
+function one(Fixed a) pure returns (Fixed) {
+    return a;
+}
+
+function three(Fixed a, Fixed, Fixed) pure returns (Fixed) {
+    return a;
+}
+
+function mixed(Fixed a, uint256) pure returns (Fixed) {
+    return a;
+}
+
+function noReturn(Fixed, Fixed) pure {}
+
+function cmp(Fixed, Fixed) pure returns (Fixed) {}
+
+function addS(S memory a, S memory) pure returns (S memory) {
+    return a;
+}
+
+using {add as +} for Fixed global;
+using {add as *} for Fixed; //~ ERROR: operators can only be defined in a global `using for` directive
+using {addS as +} for S global; //~ ERROR: operators can only be implemented for user-defined value types
+using {L.add as /} for Fixed global; //~ ERROR: only pure free functions can be used to define operators
+using {addView as %} for Fixed global; //~ ERROR: only pure free functions can be used to define operators
+using {one as |} for Fixed global; //~ ERROR: the function `one` needs to have exactly two parameters of type `Fixed` to be used for the operator `|`
+using {add as ~} for Fixed global; //~ ERROR: the function `add` needs to have exactly one parameter of type `Fixed` to be used for the operator `~`
+using {three as -} for Fixed global; //~ ERROR: the function `three` needs to have one or two parameters of type `Fixed` to be used for the operator `-`
+using {mixed as &} for Fixed global; //~ ERROR: the function `mixed` needs to have exactly two parameters of type `Fixed` to be used for the operator `&`
+using {noReturn as ^} for Fixed global; //~ ERROR: the function `noReturn` needs to return exactly one value of type `Fixed` to be used for the operator `^`
+using {cmp as ==} for Fixed global; //~ ERROR: the function `cmp` needs to return exactly one value of type `bool` to be used for the operator `==`
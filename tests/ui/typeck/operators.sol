@@ -0,0 +1,63 @@
+type Fixed is int256;
+type Other is uint256;
+
+using {add as +, sub as -, neg as -, lt as <, eq as ==, inv as ~} for Fixed global;
+using {add2 as +} for Other global;
+using {add3 as +} for Other global;
+
+function add(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) + Fixed.unwrap(b));
+}
+
+function sub(Fixed a, Fixed b) pure returns (Fixed) {
+    return Fixed.wrap(Fixed.unwrap(a) - Fixed.unwrap(b));
+}
+
+function neg(Fixed a) pure returns (Fixed) {
+    return Fixed.wrap(-Fixed.unwrap(a));
+}
+
+function lt(Fixed a, Fixed b) pure returns (bool) {
+    return Fixed.unwrap(a) < Fixed.unwrap(b);
+}
+
+function eq(Fixed a, Fixed b) pure returns (bool) {
+    return Fixed.unwrap(a) == Fixed.unwrap(b);
+}
+
+function inv(Fixed a) pure returns (Fixed) {
+    return Fixed.wrap(~Fixed.unwrap(a));
+}
+
+function add2(Other a, Other b) pure returns (Other) {
+    return Other.wrap(Other.unwrap(a) + Other.unwrap(b));
+}
+
+function add3(Other a, Other b) pure returns (Other) {
+    return Other.wrap(Other.unwrap(a) + Other.unwrap(b));
+}
+
+contract C {
+    function f(Fixed a, Fixed b, Other o, int256 x) public pure {
+        Fixed c = a + b - a;
+        c = -c;
+        c = ~c;
+        bool lt_ = a < b;
+        bool eq_ = a == b;
+        c = (a + b) + -(a - b);
+
+        a * b; //~ ERROR: built-in binary operator `*` cannot be applied to types `Fixed` and `Fixed`
+        a > b; //~ ERROR: built-in binary operator `>` cannot be applied to types `Fixed` and `Fixed`
+        a + x; //~ ERROR: built-in binary operator `+` cannot be applied to types `Fixed` and `int256`
+        x + a; //~ ERROR: built-in binary operator `+` cannot be applied to types `int256` and `Fixed`
+        a + o; //~ ERROR: built-in binary operator `+` cannot be applied to types `Fixed` and `Other`
+        !a; //~ ERROR: built-in unary operator `!` cannot be applied to type `Fixed`
+        c += a; //~ ERROR: built-in binary operator `+` cannot be applied to types `Fixed` and `Fixed`
+        c++; //~ ERROR: built-in unary operator `++` cannot be applied to type `Fixed`
+        Fixed d = a + 1; //~ ERROR: built-in binary operator `+` cannot be applied to types `Fixed` and `integer literal`
+        uint256 e = a + b; //~ ERROR: mismatched types
+
+        o + o; //~ ERROR: user-defined binary operator `+` has more than one definition matching the operand type visible in the current scope
+        -o; //~ ERROR: built-in unary operator `-` cannot be applied to type `Other`
+    }
+}
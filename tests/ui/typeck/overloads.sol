@@ -0,0 +1,62 @@
+library Math {
+    function add(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a + b;
+    }
+
+    function add(int256 a, int256 b) internal pure returns (int256) {
+        return a + b;
+    }
+}
+
+contract C {
+    event Log(uint256 a);
+    event Log(string s);
+
+    function log(uint256 a) internal {}
+    function log(int256 a) internal {}
+    function log(string memory s) internal {}
+    function log(uint256 a, bool b) internal {}
+
+    function named(uint256 a, bool b) internal {}
+    function named(bool b, uint256 a) internal {}
+
+    function f(uint256 x, int256 y, bool b) public {
+        log(x);
+        log(y);
+        log("hello");
+        log(x, b);
+        log({a: x, b: b});
+        log(1); //~ ERROR: ambiguous call to overloaded function `log`
+        log(b); //~ ERROR: no matching overload for call to `log`
+        log(x, y); //~ ERROR: no matching overload for call to `log`
+
+        named(x, b);
+        named(b, x);
+        named({a: x, b: b}); //~ ERROR: ambiguous call to overloaded function `named`
+
+        uint256 u = Math.add(x, x);
+        int256 i = Math.add(y, y);
+        Math.add(x, y); //~ ERROR: no matching overload for call to `add`
+        u; i;
+
+        emit Log(x);
+        emit Log("a");
+        emit Log(b); //~ ERROR: no matching overload for call to `Log`
+    }
+
+    // References to overloaded functions are resolved with the expected type.
+    function refs() public {
+        function(uint256) internal l1 = log;
+        function(string memory) internal l2 = (log);
+        function(uint256, bool) internal l3;
+        l3 = log;
+        function(uint256) internal pure l4 = Math.add; //~ ERROR: no matching declaration for `add`
+        function(bool) internal l5 = log; //~ ERROR: no matching declaration for `log`
+        log; //~ ERROR: ambiguous reference to overloaded declaration `log`
+        l1; l2; l4; l5;
+    }
+
+    function ret() internal pure returns (function(int256, int256) internal pure returns (int256)) {
+        return Math.add;
+    }
+}
@@ -0,0 +1,113 @@
+interface IERC20 {
+    function totalSupply() external view returns (uint256);
+    function balanceOf(address account) external view returns (uint256);
+    function transfer(address to, uint256 value) external returns (bool);
+}
+
+interface IERC20Metadata is IERC20 {
+    function name() external view returns (string memory);
+}
+
+abstract contract Context {
+    function _msgSender() internal view virtual returns (address) {
+        return msg.sender;
+    }
+}
+
+abstract contract ERC20 is Context, IERC20Metadata {
+    mapping(address => uint256) private _balances;
+    uint256 public override totalSupply;
+
+    function name() public view virtual returns (string memory) {
+        return "";
+    }
+
+    function balanceOf(address account) public view virtual override returns (uint256) {
+        return _balances[account];
+    }
+
+    function transfer(address, uint256) public virtual returns (bool) {
+        return true;
+    }
+
+    function _update(address, address, uint256) internal virtual {}
+}
+
+abstract contract Pausable is Context {
+    function _update(address, address, uint256) internal virtual {}
+    function paused() public view virtual returns (bool) {}
+}
+
+contract Token is ERC20, Pausable {
+    function _update(address from, address to, uint256 value) internal override(ERC20, Pausable) {
+        super._update(from, to, value);
+    }
+
+    function _msgSender() internal view override returns (address) {
+        return address(0);
+    }
+}
+
+contract Base {
+    function f() public virtual {}
+    function g() public {}
+    function h() external virtual {}
+    function i() internal virtual {}
+    function j() public virtual {}
+    function k() public payable virtual {}
+    function l() public view virtual {}
+    function m() public virtual returns (uint256) {}
+    function n() external pure virtual returns (uint256) {}
+    function o() external virtual returns (uint256) {}
+    function p() public virtual returns (uint256) {}
+    modifier mod() virtual { _; }
+    modifier mod2() { _; }
+}
+
+contract Other {
+    function f() public virtual {}
+}
+
+contract Derived is Base, Other {
+    function f() public override(Base) {} //~ ERROR: function needs to specify overridden contract `Other`
+    function g() public override {} //~ ERROR: trying to override non-virtual function
+    function h() public override {}
+    function i() public override {} //~ ERROR: overriding function visibility differs from `internal`
+    function j() public {} //~ ERROR: overriding function is missing `override` specifier
+    function k() public override {} //~ ERROR: overriding function changes state mutability from `payable` to `nonpayable`
+    function l() public override {} //~ ERROR: overriding function changes state mutability from `view` to `nonpayable`
+    function m() public override returns (int256) {} //~ ERROR: overriding function return types differ
+    uint256 public override n; //~ ERROR: overriding public state variable changes state mutability from `pure` to `view`
+    uint256 public override o;
+    uint256 public override p; //~ ERROR: public state variables can only override functions with external visibility
+    function r() public override {} //~ ERROR: function has `override` specified but does not override anything
+    uint256 internal override s; //~ ERROR: `override` can only be used with public state variables
+    function m(uint256) public override(Base, Other) {} //~ ERROR: function has `override` specified but does not override anything
+    modifier mod() override { _; }
+    modifier mod2() override { _; } //~ ERROR: trying to override non-virtual modifier
+}
+
+contract Invalid is Base {
+    function f() public override(Other) {} //~ ERROR: function needs to specify overridden contract `Base`
+    //~^ ERROR: invalid contract specified in override list: `Other`
+}
+
+contract A {
+    function f() public virtual {}
+}
+
+contract B is A {
+    function f() public virtual override {}
+}
+
+contract C is A {
+    function f() public virtual override {}
+}
+
+contract D is B, C {} //~ ERROR: derived contract must override function `f`
+
+contract E is A, B {}
+
+contract F is B, C {
+    function f() public override(B, C) {}
+}
@@ -0,0 +1,29 @@
+contract C {
+    /// @custom:storage-location erc7201:example.main
+    struct Main {
+        uint256 x;
+    }
+
+    /// @custom:storage-location erc7201:
+    struct Empty { //~^ ERROR: unsupported storage location `erc7201:`
+        uint256 x;
+    }
+
+    /**
+     * @custom:storage-location keccak:example.other
+     */
+    struct Other { //~^^^ ERROR: unsupported storage location `keccak:example.other`
+        uint256 x;
+    }
+
+    /// @custom:storage-location erc7201:example.var
+    uint256 x; //~^ ERROR: `@custom:storage-location` can only be used on structs
+
+    /// @custom:storage-locations erc7201:example.typo
+    struct Typo {
+        uint256 x;
+    }
+}
+
+/// @custom:storage-location erc7201:example.contract
+contract D {} //~^ ERROR: `@custom:storage-location` can only be used on structs
@@ -0,0 +1,28 @@
+contract ReentrancyGuard {
+    bool transient locked;
+    uint256 transient public depth;
+
+    modifier nonReentrant() {
+        require(!locked);
+        locked = true;
+        _;
+        locked = false;
+    }
+
+    function guarded() external nonReentrant returns (uint256) {
+        depth++;
+        return depth;
+    }
+
+    function read() external view returns (bool) {
+        return locked;
+    }
+
+    function readPure() external pure returns (bool) {
+        return locked; //~ ERROR: function declared as `pure`, but this expression (potentially) reads from the environment or state and thus requires `view`
+    }
+
+    function write() external view {
+        locked = true; //~ ERROR: function declared as `view`, but this expression (potentially) modifies the state
+    }
+}
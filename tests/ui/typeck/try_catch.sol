@@ -0,0 +1,69 @@
+library L {
+    function pub() public pure returns (uint256) {
+        return 1;
+    }
+
+    function int_() internal pure returns (uint256) {
+        return 1;
+    }
+}
+
+contract D {
+    constructor(uint256) {}
+}
+
+contract C {
+    function ext() external pure returns (uint256) {
+        return 1;
+    }
+
+    function pair() external pure returns (uint256, string memory) {
+        return (1, "");
+    }
+
+    function internal_() internal pure returns (uint256) {
+        return 1;
+    }
+
+    function calls() public {
+        try this.ext() returns (uint256 x) {
+            x;
+        } catch {}
+        try this.ext() {} catch {}
+        try this.pair() returns (uint256, string memory s) {
+            s;
+        } catch {}
+        try new D(1) returns (D d) {
+            d;
+        } catch {}
+        try L.pub() returns (uint256) {} catch {}
+
+        try 1 + 1 {} catch {} //~ ERROR: try can only be used with external function calls and contract creation calls
+        try internal_() {} catch {} //~ ERROR: try can only be used with external function calls and contract creation calls
+        try L.int_() {} catch {} //~ ERROR: try can only be used with external function calls and contract creation calls
+        try address(this).call("") {} catch {} //~ ERROR: try can only be used with external function calls and contract creation calls
+    }
+
+    function returns_() public {
+        try this.ext() returns (bool b, string memory s) {} catch {} //~ ERROR: function returns 1 values, but the returns clause has 2 variables
+        try this.ext() returns (bool b) {} catch {} //~ ERROR: mismatched types
+        try this.pair() returns (uint256, bytes memory) {} catch {} //~ ERROR: mismatched types
+        try new D(1) returns (C) {} catch {} //~ ERROR: mismatched types
+    }
+
+    function catches() public {
+        try this.ext() {} catch Error(string memory reason) {
+            reason;
+        } catch Panic(uint256 code) {
+            code;
+        } catch (bytes memory data) {
+            data;
+        }
+
+        try this.ext() {} catch Error(bytes memory) {} //~ ERROR: mismatched types
+        try this.ext() {} catch Panic(uint8) {} //~ ERROR: mismatched types
+        try this.ext() {} catch (string memory) {} //~ ERROR: mismatched types
+        try this.ext() {} catch Error(string memory, uint256) {} //~ ERROR: wrong number of catch clause parameters
+        try this.ext() {} catch Other(bytes memory) {} //~ ERROR: invalid catch clause name
+    }
+}
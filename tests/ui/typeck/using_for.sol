@@ -0,0 +1,121 @@
+import {Fixed} from "./auxiliary/fixed.sol";
+
+library SafeMath {
+    function add(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a + b;
+    }
+
+    function mul(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a * b;
+    }
+
+    function hidden(uint256 a) private pure returns (uint256) {
+        return a;
+    }
+}
+
+library Address {
+    function sendValue(address payable recipient, uint256 amount) internal {
+        recipient.transfer(amount);
+    }
+
+    function isContract(address account) internal view returns (bool) {
+        return account.code.length > 0;
+    }
+}
+
+library Arrays {
+    function sum(uint256[] storage arr) internal view returns (uint256 s) {
+        for (uint256 i = 0; i < arr.length; i++) {
+            s += arr[i];
+        }
+    }
+
+    function first(uint256[] memory arr) internal pure returns (uint256) {
+        return arr[0];
+    }
+}
+
+struct Point {
+    uint256 x;
+    uint256 y;
+}
+
+using {norm, scale} for Point global;
+using {Arrays.first} for uint256[];
+
+function norm(Point memory p) pure returns (uint256) {
+    return p.x * p.x + p.y * p.y;
+}
+
+function scale(Point memory p, uint256 k) pure returns (Point memory) {
+    return Point(p.x * k, p.y * k);
+}
+
+contract C {
+    using SafeMath for uint256;
+    using Address for address payable;
+    using Address for address;
+    using Arrays for *;
+
+    uint256[] arr;
+
+    function f(uint256 x, uint8 y, address a, address payable p) public {
+        x.add(1).mul(2);
+        x.add({b: 1});
+        x.add({a: 1}); //~ ERROR: named argument `a` does not match function declaration
+        x.add(true); //~ ERROR: mismatched types
+        x.add(); //~ ERROR: wrong argument count for function call: 0 arguments given but expected 1
+        x.hidden(); //~ ERROR: no member named `hidden` on type `uint256`
+        y.add(1); //~ ERROR: no member named `add` on type `uint8`
+        x.sub(1); //~ ERROR: no member named `sub` on type `uint256`
+
+        p.sendValue(x);
+        p.isContract();
+        a.isContract();
+        a.sendValue(x); //~ ERROR: no member named `sendValue` on type `address`
+
+        arr.sum();
+        arr.first();
+        uint256[] memory m = arr;
+        m.first();
+        m.sum(); //~ ERROR: no member named `sum` on type `uint256[] memory`
+
+        Point memory pt = Point(1, 2);
+        uint256 n = pt.norm();
+        pt = pt.scale(n);
+
+        Fixed fx = Fixed.wrap(x);
+        fx = fx.add(fx);
+        fx.sub(fx); //~ ERROR: no member named `sub` on type `Fixed`
+    }
+}
+
+contract D is C {
+    function g(uint256 x) public pure {
+        // Directives are not inherited.
+        x.add(1); //~ ERROR: no member named `add` on type `uint256`
+    }
+}
+
+library Double {
+    function twice(uint256 a) internal pure returns (uint256) {
+        return a * 2;
+    }
+}
+
+library Add {
+    function twice(uint256 a) internal pure returns (uint256) {
+        return a + a;
+    }
+}
+
+contract Ambiguous {
+    using Double for uint256;
+    using Add for uint256;
+    using Double for uint256;
+
+    function f(uint256 x) public pure {
+        x.twice(); //~ ERROR: ambiguous call to overloaded function `twice`
+    }
+}
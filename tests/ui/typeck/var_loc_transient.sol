@@ -0,0 +1,18 @@
+struct S {
+    uint x;
+}
+
+contract C {
+    bool transient locked;
+    uint256 transient public counter;
+    address transient owner;
+
+    uint[] transient a;                 //~ ERROR: transient storage state variables must have a value type
+    S transient b;                      //~ ERROR: transient storage state variables must have a value type
+    mapping(uint => uint) transient c;  //~ ERROR: transient storage state variables must have a value type
+    string transient d;                 //~ ERROR: transient storage state variables must have a value type
+
+    uint transient constant E = 1;      //~ ERROR: transient cannot be used as data location for constant or immutable variables
+    uint transient immutable F;         //~ ERROR: transient cannot be used as data location for constant or immutable variables
+    uint transient g = 1;               //~ ERROR: initialization of transient storage state variables is not supported
+}